@@ -5,6 +5,7 @@ use std::io::Write;
 use std::path::Path;
 
 mod textures;
+mod trace;
 
 fn main() {
     let dest = env::var("OUT_DIR").unwrap();
@@ -12,12 +13,18 @@ fn main() {
 
     textures::build_texture_file(&mut File::create(&dest.join("textures.rs")).unwrap());
     println!("cargo:rerun-if-changed=build/main.rs");
+    println!("cargo:rerun-if-changed=build/trace.rs");
 
     let mut file_output = File::create(&dest.join("gl_bindings.rs")).unwrap();
-    generate_gl_bindings(&mut file_output);
+    let registry = generate_gl_bindings(&mut file_output);
+
+    if env::var_os("CARGO_FEATURE_TRACE").is_some() {
+        let mut file_output = File::create(&dest.join("gl_trace.rs")).unwrap();
+        trace::build_trace_file(&registry, &mut file_output);
+    }
 }
 
-fn generate_gl_bindings<W>(dest: &mut W)
+fn generate_gl_bindings<W>(dest: &mut W) -> Registry
 where
     W: Write,
 {
@@ -67,6 +74,7 @@ where
             "GL_ATI_draw_buffers",
             "GL_ATI_meminfo",
             "GL_EXT_debug_marker",
+            "GL_EXT_depth_bounds_test",
             "GL_EXT_direct_state_access",
             "GL_EXT_memory_object",
             "GL_EXT_memory_object_fd",
@@ -128,7 +136,9 @@ where
         ],
     );
 
-    (gl_registry + gles_registry)
+    let registry = gl_registry + gles_registry;
+    registry
         .write_bindings(gl_generator::StructGenerator, dest)
         .unwrap();
+    registry
 }
@@ -308,6 +308,7 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
 
             use crate::texture::any::{{self, TextureAny, TextureAnyLayer, TextureAnyMipmap}};
             use crate::texture::any::{{TextureAnyLayerMipmap, TextureAnyImage, Dimensions}};
+            use crate::texture::any::TextureTransfer;
             use crate::texture::bindless::{{ResidentTexture, BindlessTexturesNotSupportedError}};
             use crate::texture::get_format::{{InternalFormat, InternalFormatType, GetFormatError}};
             use crate::texture::pixel_buffer::PixelBuffer;
@@ -624,6 +625,12 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         (writeln!(dest, "
                 /// Builds a new texture with a specific format. The input data must also be of the
                 /// specified compressed format.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the length of `data` doesn't match the dimensions of the texture.
+                /// The dimensions are rounded up to a whole number of blocks of the format,
+                /// for example blocks of 4x4 texels for S3TC or ETC2 and up to 12x12 for ASTC.
                 #[inline]
                 pub fn with_compressed_data<F: ?Sized>(facade: &F, data: {param}, {dim_params},
                                                       format: {format}, mipmaps: {mipmaps})
@@ -825,6 +832,44 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 }}
         ", format = relevant_format, name = name)).unwrap();
 
+    // writing the 'new_view' function
+    (writeln!(dest, "
+                /// Builds a new texture that shares the storage of `source`, without copying anything.
+                ///
+                /// See `TextureAny::new_view` for the meaning of `levels` and `layers`.
+                #[inline]
+                pub fn new_view(source: &TextureAny, format: {format},
+                                levels: ::std::ops::Range<u32>, layers: ::std::ops::Range<u32>)
+                                -> Result<{name}, crate::texture::TextureViewCreationError>
+                {{
+                    let format = format.to_texture_format();
+                    let ty = crate::texture::TextureViewType::{dimensions:?};
+                    Ok({name}(TextureAny::new_view(source, format, ty, levels, layers)?))
+                }}
+        ", format = relevant_format, name = name, dimensions = dimensions)).unwrap();
+
+    // writing the 'into_transfer' and 'from_transfer' functions
+    (writeln!(dest, "
+                /// Prepares the texture for being sent to another context of the same share group.
+                ///
+                /// See `TextureAny::into_transfer`.
+                #[inline]
+                pub fn into_transfer(self) -> Result<TextureTransfer<{name}>, {name}> {{
+                    any::into_transfer(self.0).map_err({name})
+                }}
+
+                /// Takes ownership of a texture that has been sent by another context with
+                /// `into_transfer`.
+                ///
+                /// See `TextureAny::from_transfer`.
+                #[inline]
+                pub fn from_transfer<F: Facade + ?Sized>(facade: &F, transfer: TextureTransfer<{name}>)
+                                                         -> Result<{name}, TextureTransfer<{name}>>
+                {{
+                    any::from_transfer(facade, transfer).map({name})
+                }}
+        ", name = name)).unwrap();
+
     // writing the 'new_from_fd' function
     (writeln!(dest, r#"
                 /// Builds a new texture reference from an existing texture, externally created by a foreign
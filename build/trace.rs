@@ -0,0 +1,137 @@
+use gl_generator::{Api, Cmd, Registry};
+use std::io::Write;
+
+/// Writes the tracing layer over the OpenGL bindings.
+///
+/// The generated file is meant to be included next to a `bindings` module that contains the
+/// output of `StructGenerator`. It defines a `Gl` struct with the same methods as the one of the
+/// bindings, except that every call is reported to a `trace::Recorder` if one is installed. It
+/// also defines `replay_command`, which calls a command by its name with arguments read from a
+/// trace.
+pub fn build_trace_file<W: Write>(registry: &Registry, dest: &mut W) {
+    // same name as the struct written by `StructGenerator`
+    let api = match registry.api {
+        Api::Gl => "Gl",
+        Api::Glx => "Glx",
+        Api::Wgl => "Wgl",
+        Api::Egl => "Egl",
+        Api::GlCore => "GlCore",
+        Api::Gles1 => "Gles1",
+        Api::Gles2 => "Gles2",
+        Api::Glsc2 => "Glsc2",
+    };
+
+    writeln!(dest, r#"
+        mod __gl_imports {{
+            pub use std::os::raw;
+        }}
+
+        /// Same as the generated bindings, except that calls are reported to a recorder.
+        pub struct {api} {{
+            bindings: bindings::{api},
+            recorder: Option<crate::trace::Recorder>,
+        }}
+
+        #[allow(dead_code)]
+        impl {api} {{
+            /// Loads each OpenGL symbol using a custom load function.
+            pub fn load_with<F>(loadfn: F) -> {api}
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void
+            {{
+                {api} {{
+                    bindings: bindings::{api}::load_with(loadfn),
+                    recorder: None,
+                }}
+            }}
+
+            /// Starts reporting every call to the given recorder.
+            pub(crate) fn set_recorder(&mut self, recorder: crate::trace::Recorder) {{
+                self.recorder = Some(recorder);
+            }}
+
+            /// Returns the recorder that calls are reported to, if any.
+            pub(crate) fn recorder(&self) -> Option<&crate::trace::Recorder> {{
+                self.recorder.as_ref()
+            }}
+    "#, api = api).unwrap();
+
+    for cmd in &registry.cmds {
+        let idents = parameters(cmd, false);
+
+        writeln!(dest, r#"
+            #[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline]
+            pub unsafe fn {name}(&self, {params}) -> {ret} {{
+                let result = self.bindings.{name}({idents});
+                if let Some(ref recorder) = self.recorder {{
+                    recorder.record("{name}", &[{values}],
+                                    crate::trace::TraceValue::to_value(&result));
+                }}
+                result
+            }}
+        "#,
+            name = cmd.proto.ident,
+            params = parameters(cmd, true).join(", "),
+            ret = cmd.proto.ty,
+            idents = idents.join(", "),
+            values = idents.iter()
+                           .map(|i| format!("crate::trace::TraceValue::to_value(&{})", i))
+                           .collect::<Vec<_>>()
+                           .join(", "),
+        ).unwrap();
+    }
+
+    writeln!(dest, r#"
+        }}
+
+        /// Calls the command whose name is `name` with the given arguments.
+        ///
+        /// Returns `None` if the command doesn't exist, if the number of arguments doesn't match,
+        /// or if the command couldn't be loaded from the backend.
+        #[allow(dead_code, unused_variables)]
+        pub(crate) unsafe fn replay_command(gl: &{api}, name: &str, args: &[crate::trace::Value])
+                                     -> Option<crate::trace::Value>
+        {{
+            use crate::trace::TraceValue;
+
+            match (name, args.len()) {{
+    "#, api = api).unwrap();
+
+    for cmd in &registry.cmds {
+        writeln!(dest, r#"
+                ("{name}", {num}) => {{
+                    if !gl.bindings.{name}.is_loaded() {{
+                        return None;
+                    }}
+                    Some(gl.bindings.{name}({args}).to_value())
+                }},
+        "#,
+            name = cmd.proto.ident,
+            num = cmd.params.len(),
+            args = (0 .. cmd.params.len())
+                        .map(|i| format!("TraceValue::from_value(args[{}])", i))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+        ).unwrap();
+    }
+
+    writeln!(dest, r#"
+                _ => None,
+            }}
+        }}
+    "#).unwrap();
+}
+
+/// Returns the parameters of a command, with or without their types.
+fn parameters(cmd: &Cmd, with_types: bool) -> Vec<String> {
+    cmd.params
+        .iter()
+        .map(|binding| {
+            if with_types {
+                format!("{}: {}", binding.ident, binding.ty)
+            } else {
+                binding.ident.clone()
+            }
+        })
+        .collect()
+}
@@ -51,24 +51,35 @@ fn main() {
     let mut buffer: glium::uniforms::UniformBuffer<Data> =
               glium::uniforms::UniformBuffer::empty(&display).unwrap();
 
+    // keep a copy of the input in order to check the result
+    let power: f32 = rand::random();
+    let input: Vec<[f32; 4]> = (0 .. NUM_VALUES / 4)
+        .map(|_| [rand::random(), rand::random(), rand::random(), rand::random()])
+        .collect();
+
     {
         let mut mapping = buffer.map();
-        mapping.power = rand::random();
-        for val in mapping.values.iter_mut() {
-            *val = [rand::random::<f32>(),rand::random::<f32>(),rand::random::<f32>(),rand::random::<f32>()];
-        }
+        mapping.power = power;
+        mapping.values.copy_from_slice(&input);
     }
 
     program.execute(uniform! { MyBlock: &*buffer }, NUM_VALUES as u32 / 4, 1, 1);
 
+    // the result is copied in the background, which lets us compute the expected values on the
+    // CPU in the meantime
+    let pending = buffer.read_async().unwrap();
+    let expected: Vec<[f32; 4]> = input.iter()
+        .map(|v| [v[0].powf(power), v[1].powf(power), v[2].powf(power), v[3].powf(power)])
+        .collect();
+
     {
-        let mapping = buffer.map();
-        println!("Power is: {:?}", mapping.power);
-        for val in mapping.values.iter().take(3) {
-            println!("{:?}", val[0]);
-            println!("{:?}", val[1]);
-            println!("{:?}", val[2]);
-            println!("{:?}", val[3]);
+        // blocks until the copy is over if the GPU hasn't finished yet
+        let data = pending.wait().unwrap();
+        println!("Power is: {:?}", data.power);
+        for (val, expected) in data.values.iter().zip(expected.iter()).take(3) {
+            for (val, expected) in val.iter().zip(expected.iter()) {
+                println!("{:?} (expected {:?})", val, expected);
+            }
         }
         println!("...");
     }
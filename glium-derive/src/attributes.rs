@@ -0,0 +1,135 @@
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Lit, Meta, NestedMeta};
+
+/// Options that can be passed to a field with `#[glium(...)]`.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// `name = "..."`: name of the field on the GLSL side.
+    pub name: Option<String>,
+    /// `normalize`: whether integer attributes must be normalized.
+    pub normalize: bool,
+    /// `location = N`: location of the attribute.
+    pub location: Option<i32>,
+    /// `flatten`: the content of the field is merged into the parent.
+    pub flatten: bool,
+    /// `skip`: the field is ignored.
+    pub skip: bool,
+}
+
+/// Layout of a block, as passed with `#[glium(layout = "...")]` on a struct.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Layout {
+    Std140,
+    Std430,
+}
+
+/// Options that can be passed to a struct with `#[glium(...)]`.
+#[derive(Default)]
+pub struct StructOptions {
+    /// `layout = "std140"` or `layout = "std430"`.
+    pub layout: Option<Layout>,
+}
+
+/// Parses the `#[glium(...)]` attributes of a field. Only the options in `allowed` are accepted.
+pub fn parse_field(attrs: &[Attribute], allowed: &[&str]) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for meta in glium_metas(attrs)? {
+        let key = option_name(&meta, allowed)?;
+
+        match (key.as_str(), &meta) {
+            ("name", Meta::NameValue(nv)) => match &nv.lit {
+                Lit::Str(s) => options.name = Some(s.value()),
+                lit => return Err(Error::new(lit.span(), "expected a string")),
+            },
+            ("location", Meta::NameValue(nv)) => match &nv.lit {
+                Lit::Int(i) => options.location = Some(i.base10_parse()?),
+                lit => return Err(Error::new(lit.span(), "expected an integer")),
+            },
+            ("normalize", meta) => options.normalize = parse_flag(meta)?,
+            ("flatten", meta) => options.flatten = parse_flag(meta)?,
+            ("skip", meta) => options.skip = parse_flag(meta)?,
+            (_, meta) => return Err(Error::new(meta.span(), "invalid syntax for this option")),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parses the `#[glium(...)]` attributes of a struct. Only the options in `allowed` are accepted.
+pub fn parse_struct(attrs: &[Attribute], allowed: &[&str]) -> syn::Result<StructOptions> {
+    let mut options = StructOptions::default();
+
+    for meta in glium_metas(attrs)? {
+        let key = option_name(&meta, allowed)?;
+
+        match (key.as_str(), &meta) {
+            ("layout", Meta::NameValue(nv)) => match &nv.lit {
+                Lit::Str(s) if s.value() == "std140" => options.layout = Some(Layout::Std140),
+                Lit::Str(s) if s.value() == "std430" => options.layout = Some(Layout::Std430),
+                lit => return Err(Error::new(lit.span(), "expected \"std140\" or \"std430\"")),
+            },
+            (_, meta) => return Err(Error::new(meta.span(), "invalid syntax for this option")),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Returns true if the struct has a `#[repr(C)]` attribute.
+pub fn is_repr_c(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+         .filter(|attr| attr.path.is_ident("repr"))
+         .filter_map(|attr| attr.parse_meta().ok())
+         .any(|meta| match meta {
+             Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                 NestedMeta::Meta(Meta::Path(path)) => path.is_ident("C"),
+                 _ => false,
+             }),
+             _ => false,
+         })
+}
+
+/// Returns the content of all the `#[glium(...)]` attributes.
+fn glium_metas(attrs: &[Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("glium")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Lit(lit) => return Err(Error::new(lit.span(), "expected an option")),
+                    }
+                }
+            },
+            meta => return Err(Error::new(meta.span(), "expected `#[glium(...)]`")),
+        }
+    }
+
+    Ok(metas)
+}
+
+/// Returns the name of an option, or an error if it isn't allowed.
+fn option_name(meta: &Meta, allowed: &[&str]) -> syn::Result<String> {
+    let name = meta.path().get_ident().map(|ident| ident.to_string());
+
+    match name {
+        Some(name) if allowed.contains(&name.as_str()) => Ok(name),
+        _ => Err(Error::new(meta.path().span(),
+                            format!("unknown option, expected one of: {}", allowed.join(", ")))),
+    }
+}
+
+/// Parses either `option` or `option = true/false`.
+fn parse_flag(meta: &Meta) -> syn::Result<bool> {
+    match meta {
+        Meta::Path(_) => Ok(true),
+        Meta::NameValue(nv) => match &nv.lit {
+            Lit::Bool(b) => Ok(b.value),
+            lit => Err(Error::new(lit.span(), "expected a boolean")),
+        },
+        Meta::List(list) => Err(Error::new(list.span(), "invalid syntax for this option")),
+    }
+}
@@ -0,0 +1,642 @@
+/*!
+Derive macros for glium.
+
+This crate provides `#[derive(Vertex)]`, `#[derive(UniformBlock)]` and `#[derive(Uniforms)]`.
+You normally don't use it directly: enable the `derive` feature of glium instead, which
+re-exports the macros at the root of the `glium` crate.
+
+The behavior of the derives can be customized with `#[glium(...)]` attributes, described in the
+documentation of each macro.
+
+*/
+#![recursion_limit = "128"]
+
+extern crate proc_macro;
+
+mod attributes;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Generics, Type, WherePredicate};
+
+use crate::attributes::Layout;
+
+/// Implements the `glium::vertex::Vertex` trait for a struct.
+///
+/// Each field of the struct is an attribute whose name is the name of the field. The type of
+/// each field must implement `glium::vertex::Attribute`.
+///
+/// ## Options
+///
+/// The following options can be put on a field with `#[glium(...)]`:
+///
+///  - `name = "..."`: the name of the attribute in the shader, if different from the name of
+///    the field.
+///  - `normalize`: integer values are normalized to the `[0.0, 1.0]` or `[-1.0, 1.0]` range.
+///  - `location = N`: binds the attribute to the given location instead of matching its name.
+///  - `flatten`: the type of the field implements `Vertex` itself, and its attributes are
+///    added to the ones of this struct.
+///  - `skip`: the field isn't an attribute, for example because it is padding.
+///
+/// ## Example
+///
+/// ```
+/// use glium::Vertex;
+///
+/// #[derive(Copy, Clone, Vertex)]
+/// struct Color {
+///     #[glium(normalize)]
+///     color: [u8; 4],
+/// }
+///
+/// #[derive(Copy, Clone, Vertex)]
+/// struct MyVertex<T: Copy + glium::vertex::Attribute> {
+///     position: [f32; 3],
+///     #[glium(name = "texcoords")]
+///     tex_coords: T,
+///     #[glium(flatten)]
+///     color: Color,
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(Vertex, attributes(glium))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Implements the `glium::uniforms::UniformBlock` trait for a struct.
+///
+/// Each field of the struct is a member of the block whose name is the name of the field. The
+/// type of each field must implement `UniformBlock`. The last field can be a slice, in which
+/// case the struct must be `#[repr(C)]`.
+///
+/// ## Layout checking
+///
+/// If you put `#[glium(layout = "std140")]` or `#[glium(layout = "std430")]` on a
+/// `#[repr(C)]` struct, the derive checks at compile time that each field has the same offset
+/// and size in Rust as in the given GLSL layout, and also implements
+/// `glium::uniforms::StdLayout` so that the struct can be nested in other checked structs.
+/// The type of each field must implement `StdLayout`.
+///
+/// Since Rust doesn't know about the alignment rules of GLSL, you usually have to add padding
+/// fields, marked with `#[glium(skip)]`.
+///
+/// ## Options
+///
+/// The following options can be put on a field with `#[glium(...)]`:
+///
+///  - `name = "..."`: the name of the member in the shader, if different from the name of the
+///    field.
+///  - `skip`: the field isn't a member of the block, for example because it is padding.
+///
+/// ## Example
+///
+/// ```
+/// use glium::UniformBlock;
+///
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[glium(layout = "std140")]
+/// #[repr(C)]
+/// struct Light {
+///     position: [f32; 3],
+///     intensity: f32,
+///     color: [f32; 3],
+///     #[glium(skip)]
+///     _padding: f32,
+/// }
+///
+/// #[derive(Copy, Clone, UniformBlock)]
+/// #[glium(layout = "std140")]
+/// #[repr(C)]
+/// struct Lights {
+///     #[glium(name = "lightsCount")]
+///     count: u32,
+///     #[glium(skip)]
+///     _padding: [u32; 3],
+///     lights: [Light; 8],
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(UniformBlock, attributes(glium))]
+pub fn derive_uniform_block(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniform_block(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Implements the `glium::uniforms::Uniforms` trait for a struct.
+///
+/// Each field of the struct is a uniform whose name is the name of the field. The type of each
+/// field must implement `glium::uniforms::AsUniformValue`. This can be used instead of the
+/// `uniform!` macro when the same set of uniforms is used in several places.
+///
+/// ## Options
+///
+/// The following options can be put on a field with `#[glium(...)]`:
+///
+///  - `name = "..."`: the name of the uniform in the shader, if different from the name of the
+///    field.
+///  - `flatten`: the type of the field implements `Uniforms` itself, and its uniforms are added
+///    to the ones of this struct.
+///  - `skip`: the field isn't a uniform.
+///
+/// ## Example
+///
+/// ```
+/// use glium::Uniforms;
+///
+/// #[derive(Uniforms)]
+/// struct Camera {
+///     view: [[f32; 4]; 4],
+///     perspective: [[f32; 4]; 4],
+/// }
+///
+/// #[derive(Uniforms)]
+/// struct MyUniforms<'a> {
+///     #[glium(flatten)]
+///     camera: Camera,
+///     #[glium(name = "tex")]
+///     texture: &'a glium::texture::Texture2d,
+///     #[glium(skip)]
+///     frame: u64,
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(Uniforms, attributes(glium))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniforms(&input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn vertex(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    attributes::parse_struct(&input.attrs, &[])?;
+
+    let mut bounds = Vec::new();
+    let mut bindings = Vec::new();
+
+    for field in named_fields(input, "Vertex")? {
+        let options = attributes::parse_field(&field.attrs,
+                                              &["name", "normalize", "location", "flatten", "skip"])?;
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        if options.skip {
+            continue;
+        }
+
+        if options.flatten {
+            if options.name.is_some() || options.location.is_some() || options.normalize {
+                return Err(Error::new(field.span(), "`flatten` can't be used with other options"));
+            }
+
+            bounds.push(syn::parse_quote!(#ty: ::glium::vertex::Vertex));
+            bindings.push(quote! {
+                let offset = ::glium::__glium_offset_of!(Self, #ident);
+                for (name, field_offset, location, ty, normalize) in
+                    <#ty as ::glium::vertex::Vertex>::build_bindings().iter().cloned()
+                {
+                    bindings.push((name, offset + field_offset, location, ty, normalize));
+                }
+            });
+            continue;
+        }
+
+        let name = options.name.unwrap_or_else(|| ident.to_string());
+        let location = options.location.unwrap_or(-1);
+        let normalize = options.normalize;
+
+        bounds.push(syn::parse_quote!(#ty: ::glium::vertex::Attribute));
+        bindings.push(quote! {
+            bindings.push((
+                ::std::borrow::Cow::Borrowed(#name),
+                ::glium::__glium_offset_of!(Self, #ident),
+                #location,
+                <#ty as ::glium::vertex::Attribute>::get_type(),
+                #normalize,
+            ));
+        });
+    }
+
+    let generics = with_bounds(&input.generics, bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics ::glium::vertex::Vertex for #struct_name #ty_generics #where_clause {
+            fn build_bindings() -> ::glium::vertex::VertexFormat {
+                let mut bindings = ::std::vec::Vec::new();
+                #(#bindings)*
+                ::std::borrow::Cow::Owned(bindings)
+            }
+        }
+    })
+}
+
+fn uniform_block(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let options = attributes::parse_struct(&input.attrs, &["layout"])?;
+    let fields = named_fields(input, "UniformBlock")?;
+    let repr_c = attributes::is_repr_c(&input.attrs);
+
+    // a slice as the last field makes the struct unsized
+    let unsized_field = match fields.last() {
+        Some(field) => match &field.ty {
+            Type::Slice(slice) => Some(&*slice.elem),
+            _ => None,
+        },
+        None => None,
+    };
+
+    if unsized_field.is_some() && !repr_c {
+        return Err(Error::new(input.ident.span(),
+                              "structs with a dynamically-sized field must be `#[repr(C)]`"));
+    }
+
+    if options.layout.is_some() && !repr_c {
+        return Err(Error::new(input.ident.span(),
+                              "the layout can only be checked for `#[repr(C)]` structs"));
+    }
+
+    let mut bounds: Vec<WherePredicate> = Vec::new();
+    let mut members = Vec::new();
+    let mut offsets = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let field_options = attributes::parse_field(&field.attrs, &["name", "skip"])?;
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        // the offsets of the fields of unsized structs are computed with the rules of `repr(C)`,
+        // because `offset_of!` requires the struct to be sized
+        offsets.push(match (unsized_field, &field.ty) {
+            (None, _) => quote!(::glium::__glium_offset_of!(Self, #ident)),
+            (Some(_), Type::Slice(slice)) => {
+                let elem = &slice.elem;
+                quote! {{
+                    let alignment = ::std::mem::align_of::<#elem>();
+                    (end + alignment - 1) / alignment * alignment
+                }}
+            },
+            (Some(_), ty) => quote! {{
+                let alignment = ::std::mem::align_of::<#ty>();
+                let offset = (end + alignment - 1) / alignment * alignment;
+                end = offset + ::std::mem::size_of::<#ty>();
+                offset
+            }},
+        });
+
+        skipped.push(field_options.skip);
+        if field_options.skip {
+            continue;
+        }
+
+        let name = field_options.name.unwrap_or_else(|| ident.to_string());
+        bounds.push(syn::parse_quote!(#ty: ::glium::uniforms::UniformBlock));
+        members.push((name, index, ty));
+    }
+
+    let num_fields = offsets.len();
+    let compute_offsets = quote! {
+        #[allow(unused_mut, unused_variables, unused_assignments)]
+        let offsets: [usize; #num_fields] = {
+            let mut end = 0usize;
+            [#(#offsets),*]
+        };
+    };
+
+    // layout checking
+    let mut check = TokenStream2::new();
+    let mut use_check = TokenStream2::new();
+    if let Some(layout) = options.layout {
+        for (field, &skip) in fields.iter().zip(skipped.iter()) {
+            if skip {
+                continue;
+            }
+
+            match &field.ty {
+                Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    bounds.push(syn::parse_quote!(#elem: ::glium::uniforms::StdLayout));
+                },
+                ty => bounds.push(syn::parse_quote!(#ty: ::glium::uniforms::StdLayout)),
+            }
+        }
+
+        let generics = with_bounds(&input.generics, bounds.clone());
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let struct_name = &input.ident;
+        let checks = layout_checks(&fields, &skipped, layout);
+
+        check = quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #[doc(hidden)]
+                #[allow(unused_assignments)]
+                const __GLIUM_LAYOUT_CHECK: () = {
+                    let mut end = 0usize;
+                    #(#checks)*
+                };
+            }
+        };
+
+        // without generic parameters, the check can be done when the struct is defined
+        if input.generics.params.is_empty() {
+            check.extend(quote! {
+                const _: () = #struct_name::__GLIUM_LAYOUT_CHECK;
+            });
+        }
+
+        if unsized_field.is_none() {
+            check.extend(std_layout_impl(input, &fields, &skipped, &generics));
+        }
+
+        use_check = quote! {
+            let () = Self::__GLIUM_LAYOUT_CHECK;
+        };
+    }
+
+    let generics = with_bounds(&input.generics, bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let names = members.iter().map(|m| &m.0).collect::<Vec<_>>();
+    let indices = members.iter().map(|m| m.1).collect::<Vec<_>>();
+    let types = members.iter().map(|m| m.2).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::glium::uniforms::UniformBlock for #struct_name #ty_generics #where_clause {
+            fn matches(layout: &::glium::program::BlockLayout, base_offset: usize)
+                       -> ::std::result::Result<(), ::glium::uniforms::LayoutMismatchError>
+            {
+                use ::glium::program::BlockLayout;
+                use ::glium::uniforms::LayoutMismatchError;
+
+                #use_check
+                #compute_offsets
+
+                if let BlockLayout::Struct { members } = layout {
+                    // checking that each member exists in the input struct
+                    for (name, _) in members {
+                        if #(name != #names &&)* true {
+                            return Err(LayoutMismatchError::MissingField {
+                                name: name.clone(),
+                            });
+                        }
+                    }
+
+                    // checking that each field of the input struct is correct in the reflection
+                    #(
+                        let reflected_ty = match members.iter().find(|(name, _)| name == #names) {
+                            Some(member) => &member.1,
+                            None => return Err(LayoutMismatchError::MissingField {
+                                name: #names.to_owned(),
+                            }),
+                        };
+
+                        if let Err(err) = <#types as ::glium::uniforms::UniformBlock>::matches(
+                            reflected_ty, base_offset + offsets[#indices])
+                        {
+                            return Err(LayoutMismatchError::MemberMismatch {
+                                member: #names.to_owned(),
+                                err: Box::new(err),
+                            });
+                        }
+                    )*
+
+                    Ok(())
+
+                } else {
+                    Err(LayoutMismatchError::LayoutMismatch {
+                        expected: layout.clone(),
+                        obtained: <Self as ::glium::uniforms::UniformBlock>::build_layout(base_offset),
+                    })
+                }
+            }
+
+            fn build_layout(base_offset: usize) -> ::glium::program::BlockLayout {
+                #use_check
+                #compute_offsets
+
+                ::glium::program::BlockLayout::Struct {
+                    members: vec![
+                        #(
+                            (
+                                #names.to_owned(),
+                                <#types as ::glium::uniforms::UniformBlock>::build_layout(
+                                    base_offset + offsets[#indices])
+                            ),
+                        )*
+                    ],
+                }
+            }
+        }
+
+        #check
+    })
+}
+
+/// Builds the statements that check at compile time that the fields of a `#[repr(C)]` struct
+/// match the given layout. They update an `end` variable that contains the end of the previous
+/// field.
+fn layout_checks(fields: &[&Field], skipped: &[bool], layout: Layout) -> Vec<TokenStream2> {
+    let (layout_name, alignment, size) = match layout {
+        Layout::Std140 => ("std140", quote!(STD140_ALIGNMENT), quote!(STD140_SIZE)),
+        Layout::Std430 => ("std430", quote!(STD430_ALIGNMENT), quote!(STD430_SIZE)),
+    };
+
+    fields.iter().zip(skipped.iter()).map(|(field, &skip)| {
+        let ident = field.ident.as_ref().unwrap();
+        let offset_msg = format!("the offset of the field `{}` doesn't match the {} layout",
+                                 ident, layout_name);
+        let size_msg = format!("the size of the field `{}` doesn't match the {} layout",
+                               ident, layout_name);
+
+        match &field.ty {
+            // padding only needs to be skipped
+            ty if skip => quote! {
+                let alignment = ::std::mem::align_of::<#ty>();
+                end = (end + alignment - 1) / alignment * alignment + ::std::mem::size_of::<#ty>();
+            },
+
+            Type::Slice(slice) => {
+                let elem = &slice.elem;
+                let array_alignment = match layout {
+                    Layout::Std140 => quote! {{
+                        let alignment = <#elem as ::glium::uniforms::StdLayout>::#alignment;
+                        if alignment > 16 { alignment } else { 16 }
+                    }},
+                    Layout::Std430 => quote!(<#elem as ::glium::uniforms::StdLayout>::#alignment),
+                };
+
+                quote! {
+                    let alignment = ::std::mem::align_of::<#elem>();
+                    let offset = (end + alignment - 1) / alignment * alignment;
+                    let alignment = #array_alignment;
+                    assert!(offset == (end + alignment - 1) / alignment * alignment, #offset_msg);
+                    let stride = <#elem as ::glium::uniforms::StdLayout>::#size;
+                    let stride = (stride + alignment - 1) / alignment * alignment;
+                    assert!(::std::mem::size_of::<#elem>() == stride, #size_msg);
+                }
+            },
+
+            ty => quote! {
+                let alignment = ::std::mem::align_of::<#ty>();
+                let offset = (end + alignment - 1) / alignment * alignment;
+                let alignment = <#ty as ::glium::uniforms::StdLayout>::#alignment;
+                assert!(offset == (end + alignment - 1) / alignment * alignment, #offset_msg);
+                assert!(::std::mem::size_of::<#ty>() == <#ty as ::glium::uniforms::StdLayout>::#size,
+                        #size_msg);
+                end = offset + ::std::mem::size_of::<#ty>();
+            },
+        }
+    }).collect()
+}
+
+/// Builds the implementation of `StdLayout` for a sized struct.
+fn std_layout_impl(input: &DeriveInput, fields: &[&Field], skipped: &[bool], generics: &Generics)
+                   -> TokenStream2
+{
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let alignments = |alignment: TokenStream2| {
+        fields.iter().zip(skipped.iter()).map(move |(field, &skip)| {
+            let ty = &field.ty;
+            if skip {
+                quote!(::std::mem::align_of::<#ty>())
+            } else {
+                quote!(<#ty as ::glium::uniforms::StdLayout>::#alignment)
+            }
+        }).collect::<Vec<_>>()
+    };
+
+    let sizes = |size: TokenStream2| {
+        fields.iter().zip(skipped.iter()).map(move |(field, &skip)| {
+            let ty = &field.ty;
+            if skip {
+                quote!(::std::mem::size_of::<#ty>())
+            } else {
+                quote!(<#ty as ::glium::uniforms::StdLayout>::#size)
+            }
+        }).collect::<Vec<_>>()
+    };
+
+    let std140_alignments = alignments(quote!(STD140_ALIGNMENT));
+    let std140_sizes = sizes(quote!(STD140_SIZE));
+    let std430_alignments = alignments(quote!(STD430_ALIGNMENT));
+    let std430_sizes = sizes(quote!(STD430_SIZE));
+
+    quote! {
+        unsafe impl #impl_generics ::glium::uniforms::StdLayout for #struct_name #ty_generics
+            #where_clause
+        {
+            // with std140, the alignment of structs is rounded up to the one of a vec4
+            const STD140_ALIGNMENT: usize = {
+                let mut max = 16usize;
+                #(
+                    if #std140_alignments > max { max = #std140_alignments; }
+                )*
+                max
+            };
+
+            const STD140_SIZE: usize = {
+                let mut end = 0usize;
+                #(
+                    end = (end + #std140_alignments - 1) / #std140_alignments * #std140_alignments
+                          + #std140_sizes;
+                )*
+                (end + Self::STD140_ALIGNMENT - 1) / Self::STD140_ALIGNMENT * Self::STD140_ALIGNMENT
+            };
+
+            const STD430_ALIGNMENT: usize = {
+                let mut max = 1usize;
+                #(
+                    if #std430_alignments > max { max = #std430_alignments; }
+                )*
+                max
+            };
+
+            const STD430_SIZE: usize = {
+                let mut end = 0usize;
+                #(
+                    end = (end + #std430_alignments - 1) / #std430_alignments * #std430_alignments
+                          + #std430_sizes;
+                )*
+                (end + Self::STD430_ALIGNMENT - 1) / Self::STD430_ALIGNMENT * Self::STD430_ALIGNMENT
+            };
+        }
+    }
+}
+
+fn uniforms(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    attributes::parse_struct(&input.attrs, &[])?;
+
+    let mut bounds = Vec::new();
+    let mut visits = Vec::new();
+
+    for field in named_fields(input, "Uniforms")? {
+        let options = attributes::parse_field(&field.attrs, &["name", "flatten", "skip"])?;
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        if options.skip {
+            continue;
+        }
+
+        if options.flatten {
+            if options.name.is_some() {
+                return Err(Error::new(field.span(), "`flatten` can't be used with other options"));
+            }
+
+            bounds.push(syn::parse_quote!(#ty: ::glium::uniforms::Uniforms));
+            visits.push(quote! {
+                ::glium::uniforms::Uniforms::visit_values(&self.#ident, &mut output);
+            });
+            continue;
+        }
+
+        let name = options.name.unwrap_or_else(|| ident.to_string());
+        bounds.push(syn::parse_quote!(#ty: ::glium::uniforms::AsUniformValue));
+        visits.push(quote! {
+            output(#name, ::glium::uniforms::AsUniformValue::as_uniform_value(&self.#ident));
+        });
+    }
+
+    let generics = with_bounds(&input.generics, bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics ::glium::uniforms::Uniforms for #struct_name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_mut, unused_variables)]
+            fn visit_values<'__glium_a, __GliumF>(&'__glium_a self, mut output: __GliumF)
+                where __GliumF: FnMut(&str, ::glium::uniforms::UniformValue<'__glium_a>)
+            {
+                #(#visits)*
+            }
+        }
+    })
+}
+
+/// Returns the fields of a struct with named fields, or an error for other kinds of types.
+fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<Vec<&'a Field>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter().collect()),
+            fields => Err(Error::new(fields.span(),
+                                     format!("`{}` can only be derived for structs with named \
+                                              fields", derive))),
+        },
+        _ => Err(Error::new(input.ident.span(),
+                            format!("`{}` can only be derived for structs", derive))),
+    }
+}
+
+/// Returns a copy of the generics with additional predicates in their `where` clause.
+fn with_bounds(generics: &Generics, bounds: Vec<WherePredicate>) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(bounds);
+    generics
+}
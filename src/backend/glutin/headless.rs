@@ -4,10 +4,15 @@ use crate::{Frame, IncompatibleOpenGl, SwapBuffersError};
 use crate::debug;
 use crate::context;
 use crate::backend::{self, Backend};
+#[cfg(all(feature = "osmesa", any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd",
+                                  target_os = "netbsd", target_os = "openbsd")))]
+use super::DisplayCreationError;
 use std::rc::Rc;
 use std::cell::{Ref, RefCell};
 use std::ops::Deref;
 use std::os::raw::c_void;
+#[cfg(feature = "trace")]
+use std::io::Write;
 use super::glutin;
 use super::glutin::{PossiblyCurrent as Pc, ContextCurrentState};
 use takeable_option::Takeable;
@@ -18,8 +23,11 @@ pub struct Headless {
     glutin: Rc<RefCell<Takeable<glutin::Context<Pc>>>>,
 }
 
+/// Dimensions reported for the default framebuffer of the contexts that aren't built by OSMesa.
+const DEFAULT_DIMENSIONS: (u32, u32) = (800, 600);      // FIXME: these are random
+
 /// An implementation of the `Backend` trait for a glutin headless context.
-pub struct GlutinBackend(Rc<RefCell<Takeable<glutin::Context<Pc>>>>);
+pub struct GlutinBackend(Rc<RefCell<Takeable<glutin::Context<Pc>>>>, (u32, u32));
 
 impl Deref for Headless {
     type Target = context::Context;
@@ -35,6 +43,20 @@ impl Deref for GlutinBackend {
     }
 }
 
+impl GlutinBackend {
+    /// Builds a backend over a glutin headless context, without building a glium context.
+    ///
+    /// This is useful to replay a trace with `trace::Replayer`.
+    ///
+    /// # Safety
+    ///
+    /// The glutin context is treated as current until `make_current` is called on the backend.
+    pub unsafe fn new<T: ContextCurrentState>(context: glutin::Context<T>) -> GlutinBackend {
+        let context = context.treat_as_current();
+        GlutinBackend(Rc::new(RefCell::new(Takeable::new(context))), DEFAULT_DIMENSIONS)
+    }
+}
+
 unsafe impl Backend for GlutinBackend {
     #[inline]
     fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
@@ -48,7 +70,7 @@ unsafe impl Backend for GlutinBackend {
 
     #[inline]
     fn get_framebuffer_dimensions(&self) -> (u32, u32) {
-        (800, 600)      // FIXME: these are random
+        self.1
     }
 
     #[inline]
@@ -93,7 +115,8 @@ impl Headless {
     pub fn with_debug<T: ContextCurrentState>(context: glutin::Context<T>, debug: debug::DebugCallbackBehavior)
         -> Result<Self, IncompatibleOpenGl>
     {
-        Self::new_inner(context, debug, true)
+        Self::new_inner(context, DEFAULT_DIMENSIONS,
+                        |backend| unsafe { context::Context::new(backend, true, debug) })
     }
 
     /// The same as the `unchecked` constructor, but allows for specifying debug callback behaviour.
@@ -102,21 +125,85 @@ impl Headless {
         debug: debug::DebugCallbackBehavior,
     ) -> Result<Self, IncompatibleOpenGl>
     {
-        Self::new_inner(context, debug, false)
+        Self::new_inner(context, DEFAULT_DIMENSIONS,
+                        |backend| context::Context::new(backend, false, debug))
     }
 
-    fn new_inner<T: ContextCurrentState>(
+    /// Create a new glium `Headless` context whose buffers and textures are shared with the
+    /// contexts of `share_group`.
+    ///
+    /// This is meant to be used on a worker thread in order to upload resources in the
+    /// background. The objects created with this context can be sent to another context of the
+    /// group with `into_transfer` and `from_transfer`.
+    ///
+    /// # Safety
+    ///
+    /// The glutin context must share its lists with the contexts of the group, for example by
+    /// being built with `with_shared_lists`.
+    pub unsafe fn new_shared<T: ContextCurrentState>(
         context: glutin::Context<T>,
+        share_group: &backend::ShareGroup,
         debug: debug::DebugCallbackBehavior,
-        checked: bool,
     ) -> Result<Self, IncompatibleOpenGl>
+    {
+        Self::new_inner(context, DEFAULT_DIMENSIONS,
+                        |backend| context::Context::new_shared(backend, share_group, true, debug))
+    }
+
+    /// The same as the `new` constructor, but records all the OpenGL commands to `trace`.
+    ///
+    /// See the `trace` module for how to replay them.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `trace` feature is enabled.
+    #[cfg(feature = "trace")]
+    pub fn with_trace<T: ContextCurrentState, W: Write + 'static>(
+        context: glutin::Context<T>,
+        debug: debug::DebugCallbackBehavior,
+        trace: W,
+    ) -> Result<Self, IncompatibleOpenGl>
+    {
+        Self::new_inner(context, DEFAULT_DIMENSIONS,
+                        |backend| unsafe { context::Context::new_with_trace(backend, true, debug, trace) })
+    }
+
+    /// Create a new glium `Headless` context that renders in software through OSMesa.
+    ///
+    /// No GPU and no windowing system are required, which makes this suitable for running
+    /// tests on build machines. The OSMesa library is loaded at runtime, and an error is returned
+    /// if it can't be found. Only desktop OpenGL can be requested from the context builder.
+    ///
+    /// The default framebuffer has the given dimensions.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `osmesa` feature is enabled, and only on Unix-like platforms.
+    #[cfg(all(feature = "osmesa", any(target_os = "linux", target_os = "dragonfly",
+                                      target_os = "freebsd", target_os = "netbsd",
+                                      target_os = "openbsd")))]
+    pub fn osmesa<T: ContextCurrentState>(cb: glutin::ContextBuilder<'_, T>, dimensions: (u32, u32))
+        -> Result<Self, DisplayCreationError>
+    {
+        use super::glutin::platform::unix::HeadlessContextExt;
+
+        let context = cb.build_osmesa(dimensions.into())?;
+        Self::new_inner(context, dimensions,
+                        |backend| unsafe { context::Context::new(backend, true, Default::default()) })
+            .map_err(From::from)
+    }
+
+    fn new_inner<T, F>(context: glutin::Context<T>, dimensions: (u32, u32), build_context: F)
+        -> Result<Self, IncompatibleOpenGl>
+        where T: ContextCurrentState,
+              F: FnOnce(GlutinBackend) -> Result<Rc<context::Context>, IncompatibleOpenGl>
     {
         let context = unsafe {
             context.treat_as_current()
         };
         let glutin_context = Rc::new(RefCell::new(Takeable::new(context)));
-        let glutin_backend = GlutinBackend(glutin_context.clone());
-        let context = unsafe { context::Context::new(glutin_backend, checked, debug) }?;
+        let glutin_backend = GlutinBackend(glutin_context.clone(), dimensions);
+        let context = build_context(glutin_backend)?;
         Ok(Headless { context, glutin: glutin_context })
     }
 
@@ -21,6 +21,8 @@ use crate::glutin::{ContextCurrentState, PossiblyCurrent as Pc};
 use std::cell::{Cell, Ref, RefCell};
 use std::error::Error;
 use std::fmt;
+#[cfg(feature = "trace")]
+use std::io::Write;
 use std::ops::Deref;
 use std::os::raw::c_void;
 use std::rc::Rc;
@@ -101,7 +103,7 @@ impl Display {
         gl_window: glutin::WindowedContext<T>,
         debug: debug::DebugCallbackBehavior,
     ) -> Result<Self, IncompatibleOpenGl> {
-        Self::new_inner(gl_window, debug, true)
+        Self::new_inner(gl_window, |backend| unsafe { context::Context::new(backend, true, debug) })
     }
 
     /// The same as the `unchecked` constructor, but allows for specifying debug callback behaviour.
@@ -109,19 +111,37 @@ impl Display {
         gl_window: glutin::WindowedContext<T>,
         debug: debug::DebugCallbackBehavior,
     ) -> Result<Self, IncompatibleOpenGl> {
-        Self::new_inner(gl_window, debug, false)
+        Self::new_inner(gl_window, |backend| context::Context::new(backend, false, debug))
     }
 
-    fn new_inner<T: ContextCurrentState>(
+    /// The same as the `new` constructor, but records all the OpenGL commands to `trace`.
+    ///
+    /// See the `trace` module for how to replay them.
+    ///
+    /// # Features
+    ///
+    /// Only available if the `trace` feature is enabled.
+    #[cfg(feature = "trace")]
+    pub fn with_trace<T: ContextCurrentState, W: Write + 'static>(
         gl_window: glutin::WindowedContext<T>,
         debug: debug::DebugCallbackBehavior,
-        checked: bool,
+        trace: W,
     ) -> Result<Self, IncompatibleOpenGl> {
+        Self::new_inner(gl_window, |backend| unsafe {
+            context::Context::new_with_trace(backend, true, debug, trace)
+        })
+    }
+
+    fn new_inner<T, F>(gl_window: glutin::WindowedContext<T>, build_context: F)
+        -> Result<Self, IncompatibleOpenGl>
+        where T: ContextCurrentState,
+              F: FnOnce(GlutinBackend) -> Result<Rc<context::Context>, IncompatibleOpenGl>
+    {
         let gl_window = unsafe { gl_window.treat_as_current() };
         let gl_window = Rc::new(RefCell::new(Takeable::new(gl_window)));
         let glutin_backend = GlutinBackend(gl_window.clone());
         let framebuffer_dimensions = glutin_backend.get_framebuffer_dimensions();
-        let context = unsafe { context::Context::new(glutin_backend, checked, debug) }?;
+        let context = build_context(glutin_backend)?;
         Ok(Display {
             gl_window,
             context,
@@ -132,15 +152,19 @@ impl Display {
     /// Rebuilds the Display's `WindowedContext` with the given window and context builders.
     ///
     /// This method ensures that the new `WindowedContext`'s `Context` will share the display lists of the
-    /// original `WindowedContext`'s `Context`.
+    /// original `WindowedContext`'s `Context`, unless the original one has been lost. In that case
+    /// the resources are recreated if the recovery mode is enabled. See
+    /// `Context::enable_resource_recovery`.
     pub fn rebuild<T: ContextCurrentState>(
         &self,
         wb: glutin::window::WindowBuilder,
         cb: glutin::ContextBuilder<'_, T>,
         events_loop: &glutin::event_loop::EventLoop<()>,
     ) -> Result<(), DisplayCreationError> {
-        // Share the display lists of the existing context.
-        let new_gl_window = {
+        // Share the display lists of the existing context, unless it is lost.
+        let new_gl_window = if self.context.is_context_lost() {
+            cb.build_windowed(wb, events_loop)?
+        } else {
             let gl_window = self.gl_window.borrow();
             let cb = cb.with_shared_lists(gl_window.context());
             cb.build_windowed(wb, events_loop)?
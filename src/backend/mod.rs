@@ -24,7 +24,13 @@ use crate::context::ExtensionsList;
 use crate::version::Version;
 
 pub use crate::context::Context;
+pub use crate::context::LostResource;
+pub use crate::context::RecoveryStatus;
 pub use crate::context::ReleaseBehavior;
+pub use crate::context::ResourceInfo;
+pub use crate::context::ResourceKind;
+pub use crate::context::ResourcesReport;
+pub use crate::context::ShareGroup;
 
 #[cfg(feature = "glutin")]
 pub mod glutin;
@@ -1,13 +1,18 @@
 use crate::backend::Facade;
 use crate::context::CommandContext;
 use crate::context::Context;
+use crate::context::{Resource, ResourceKind};
+use crate::context::ShareGroup;
+use crate::debug::{self, LabelNotSupportedError};
+use crate::sync::TransferFence;
 use crate::version::Version;
 use crate::CapabilitiesSource;
 use crate::ContextExt;
 use crate::gl;
 use std::os::raw;
 use std::error::Error;
-use std::{fmt, mem, ptr};
+use std::{fmt, mem, ptr, slice};
+use std::mem::ManuallyDrop;
 use std::cell::Cell;
 use std::rc::Rc;
 use std::ops::{Deref, DerefMut, Range};
@@ -112,7 +117,7 @@ impl Alloc {
             create_buffer(&mut ctxt, size, Some(data), ty, mode)
         }?;
 
-        Ok(Alloc {
+        let alloc = Alloc {
             context: facade.get_context().clone(),
             id,
             ty,
@@ -123,7 +128,13 @@ impl Alloc {
             creation_mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
-        })
+        };
+
+        alloc.register(Some(unsafe {
+            slice::from_raw_parts(data.to_void_ptr() as *const u8, size)
+        }));
+
+        Ok(alloc)
     }
 
     /// Builds a new empty buffer of the given size.
@@ -136,7 +147,7 @@ impl Alloc {
             create_buffer::<()>(&mut ctxt, size, None, ty, mode)
         }?;
 
-        Ok(Alloc {
+        let alloc = Alloc {
             context: facade.get_context().clone(),
             id,
             ty,
@@ -147,7 +158,10 @@ impl Alloc {
             creation_mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
-        })
+        };
+
+        alloc.register(None);
+        Ok(alloc)
     }
 
     /// Returns the context corresponding to this buffer.
@@ -156,12 +170,98 @@ impl Alloc {
         &self.context
     }
 
+    /// Prepares the buffer for being used by another context of the same share group.
+    ///
+    /// This context stops tracking the buffer, and a fence is inserted after the commands
+    /// that have been issued so far.
+    pub fn into_transfer(self) -> AllocTransfer {
+        let fence = unsafe {
+            let mut ctxt = self.context.make_current();
+            self.assert_unmapped(&mut ctxt);
+            self.assert_not_transform_feedback(&mut ctxt);
+            VertexAttributesSystem::purge_buffer(&mut ctxt, self.id);
+            purge_buffer_bindings(&mut ctxt, self.id);
+            TransferFence::new(&mut ctxt)
+        };
+
+        self.context.resource_registry().remove(ResourceKind::Buffer, self.id);
+
+        let transfer = AllocTransfer {
+            share_group: self.context.get_share_group().clone(),
+            id: self.id,
+            ty: self.ty,
+            size: self.size,
+            persistent_mapping: self.persistent_mapping,
+            immutable: self.immutable,
+            creation_mode: self.creation_mode,
+            created_with_buffer_storage: self.created_with_buffer_storage,
+            fence,
+        };
+
+        // the buffer now belongs to the transfer, so the destructor must not delete it
+        let this = ManuallyDrop::new(self);
+        drop(unsafe { ptr::read(&this.context) });
+
+        transfer
+    }
+
+    /// Takes ownership of a buffer that has been prepared with `into_transfer`.
+    ///
+    /// Returns the transfer if the context of `facade` doesn't belong to the same share group
+    /// as the context that has created it.
+    pub fn from_transfer<F: ?Sized>(facade: &F, transfer: AllocTransfer)
+                                    -> Result<Alloc, AllocTransfer> where F: Facade
+    {
+        if *facade.get_context().get_share_group() != transfer.share_group {
+            return Err(transfer);
+        }
+
+        let AllocTransfer { id, ty, size, persistent_mapping, immutable, creation_mode,
+                            created_with_buffer_storage, fence, .. } = transfer;
+
+        {
+            let mut ctxt = facade.get_context().make_current();
+            if persistent_mapping.is_some() {
+                unsafe { fence.client_wait_and_drop(&mut ctxt) };
+            } else {
+                unsafe { fence.wait_and_drop(&mut ctxt) };
+            }
+        }
+
+        let alloc = Alloc {
+            context: facade.get_context().clone(),
+            id,
+            ty,
+            size,
+            persistent_mapping,
+            immutable,
+            created_with_buffer_storage,
+            creation_mode,
+            mapped: Cell::new(false),
+            latest_shader_write: Cell::new(0),
+        };
+
+        // the content of the buffer is unknown
+        alloc.register(None);
+        Ok(alloc)
+    }
+
     /// Returns the total size in bytes of this buffer.
     #[inline]
     pub fn get_size(&self) -> usize {
         self.size
     }
 
+    /// Gives a label to the buffer, which is shown by debuggers and in the resources report of
+    /// the context.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.context.resource_registry().set_label(ResourceKind::Buffer, self.id,
+                                                   Some(label.to_owned()));
+
+        let mut ctxt = self.context.make_current();
+        unsafe { debug::set_object_label(&mut ctxt, gl::BUFFER, self.id, label) }
+    }
+
     /// Returns true if the buffer is persistently mapped in memory.
     #[inline]
     pub fn uses_persistent_mapping(&self) -> bool {
@@ -172,9 +272,62 @@ impl Alloc {
     pub fn set_type(mut self, ty: BufferType) -> Result<Alloc, Alloc> {
         // FIXME: return Err for GLES2
         self.ty = ty;
+
+        if let Some(&mut Resource { recipe: Some(ref mut recipe), .. }) =
+            self.context.resource_registry().buffers.get_mut(&self.id)
+        {
+            recipe.ty = ty;
+        }
+
         Ok(self)
     }
 
+    /// Adds the buffer to the registry of the resources of the context. `data` is the initial
+    /// content of the buffer, if it is known.
+    fn register(&self, data: Option<&[u8]>) {
+        let mut registry = self.context.resource_registry();
+
+        // the persistent mapping can't be recreated at the same address
+        let recipe = if registry.is_recovery_enabled() && self.persistent_mapping.is_none() {
+            // buffers that are meant to be modified often don't keep a copy of their content
+            let data = match self.creation_mode {
+                BufferMode::Default | BufferMode::Immutable => data.map(|d| d.to_vec()),
+                BufferMode::Dynamic | BufferMode::Persistent => None,
+            };
+
+            Some(BufferRecipe {
+                ty: self.ty,
+                mode: self.creation_mode,
+                size: self.size,
+                data,
+            })
+        } else {
+            None
+        };
+
+        registry.insert_buffer(self.id, self.size, recipe);
+    }
+
+    /// Must be called when the content of the buffer is modified with `data` at `offset`.
+    fn update_shadow_copy(&self, offset: usize, data: &[u8]) {
+        if let Some(&mut Resource {
+            recipe: Some(BufferRecipe { data: Some(ref mut copy), .. }), ..
+        }) = self.context.resource_registry().buffers.get_mut(&self.id)
+        {
+            copy[offset .. offset + data.len()].copy_from_slice(data);
+        }
+    }
+
+    /// Must be called before the content of the buffer is modified. The copy of the content
+    /// that is kept for the recovery after a context loss is no longer valid.
+    fn discard_shadow_copy(&self) {
+        if let Some(&mut Resource { recipe: Some(ref mut recipe), .. }) =
+            self.context.resource_registry().buffers.get_mut(&self.id)
+        {
+            recipe.data = None;
+        }
+    }
+
     /// Asserts that the buffer is not mapped and available for operations.
     /// No-op for persistent mapping.
     fn assert_unmapped(&self, ctxt: &mut CommandContext<'_>) {
@@ -237,6 +390,7 @@ impl Alloc {
     /// Makes sure that the buffer is bound to the `GL_PIXEL_PACK_BUFFER` and calls
     /// `glMemoryBarrier(GL_PIXEL_BUFFER_BARRIER_BIT)` if necessary.
     pub fn prepare_and_bind_for_pixel_pack(&self, ctxt: &mut CommandContext<'_>) {
+        self.discard_shadow_copy();
         self.assert_unmapped(ctxt);
         self.assert_not_transform_feedback(ctxt);
 
@@ -281,6 +435,7 @@ impl Alloc {
                 ctxt.extensions.gl_arb_query_buffer_object ||
                 ctxt.extensions.gl_amd_query_buffer_object);
 
+        self.discard_shadow_copy();
         self.assert_unmapped(ctxt);
         self.assert_not_transform_feedback(ctxt);
 
@@ -347,6 +502,7 @@ impl Alloc {
     pub fn prepare_and_bind_for_shared_storage(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint,
                                                range: Range<usize>)
     {
+        self.discard_shadow_copy();
         self.assert_unmapped(ctxt);
         self.assert_not_transform_feedback(ctxt);
 
@@ -365,6 +521,7 @@ impl Alloc {
     pub fn prepare_and_bind_for_atomic_counter(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint,
                                                range: Range<usize>)
     {
+        self.discard_shadow_copy();
         self.assert_unmapped(ctxt);
         self.assert_not_transform_feedback(ctxt);
 
@@ -384,6 +541,7 @@ impl Alloc {
     pub fn bind_to_transform_feedback(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint,
                                       range: Range<usize>)
     {
+        self.discard_shadow_copy();
         self.indexed_bind(ctxt, BufferType::TransformFeedbackBuffer, index, range);
     }
 
@@ -436,6 +594,9 @@ impl Alloc {
     {
         assert!(offset_bytes + mem::size_of_val(data) <= self.size);
 
+        self.update_shadow_copy(offset_bytes, slice::from_raw_parts(data.to_void_ptr() as *const u8,
+                                                                    mem::size_of_val(data)));
+
         if self.persistent_mapping.is_some() {
             let mapping = Mapping { mapping: self.map_shared(offset_bytes .. offset_bytes + mem::size_of_val(data), false, true) };
             ptr::copy_nonoverlapping(data.to_void_ptr() as *const u8, <D as Content>::to_void_ptr(&mapping) as *mut u8, mem::size_of_val(data));
@@ -510,6 +671,7 @@ impl Alloc {
     ///
     pub fn invalidate(&self, offset: usize, size: usize) {
         assert!(offset + size <= self.size);
+        self.discard_shadow_copy();
 
         let is_whole_buffer = offset == 0 && size == self.size;
 
@@ -555,6 +717,78 @@ impl Alloc {
         }
     }
 
+    /// Fills a range of the buffer with copies of `pattern`.
+    ///
+    /// `num_components` is the number of components of the pattern, which all have the same
+    /// size. It is used to pick the format passed to `glClearBufferSubData`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the range is out of the buffer or if its size is not a multiple of the size
+    /// of the pattern.
+    ///
+    /// # Unsafety
+    ///
+    /// If the buffer uses persistent mapping, the caller of this function must handle
+    /// synchronization.
+    ///
+    pub unsafe fn fill(&self, range: Range<usize>, pattern: &[u8], num_components: usize) {
+        assert!(range.end >= range.start);
+        assert!(range.end <= self.size);
+        assert!(!pattern.is_empty());
+        assert!((range.end - range.start) % pattern.len() == 0);
+
+        if range.start == range.end {
+            return;
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        let formats = clear_buffer_formats(pattern.len(), num_components);
+        let supported = ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                        ctxt.extensions.gl_arb_clear_buffer_object;
+
+        // the offset passed to `glClearBufferSubData` must be a multiple of the size of an
+        // element, otherwise we upload the data manually
+        match formats {
+            Some((internal_format, format, ty)) if supported &&
+                                                   range.start % pattern.len() == 0 =>
+            {
+                self.discard_shadow_copy();
+                self.barrier_for_buffer_update(&mut ctxt);
+                self.assert_unmapped(&mut ctxt);
+                self.assert_not_transform_feedback(&mut ctxt);
+
+                let offset = range.start as gl::types::GLintptr;
+                let size = (range.end - range.start) as gl::types::GLsizeiptr;
+                let data = pattern.as_ptr() as *const _;
+
+                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                   ctxt.extensions.gl_arb_direct_state_access
+                {
+                    ctxt.gl.ClearNamedBufferSubData(self.id, internal_format, offset, size,
+                                                    format, ty, data);
+
+                } else if ctxt.extensions.gl_ext_direct_state_access {
+                    ctxt.gl.ClearNamedBufferSubDataEXT(self.id, internal_format, offset, size,
+                                                       format, ty, data);
+
+                } else {
+                    let bind = bind_buffer(&mut ctxt, self.id, self.ty);
+                    ctxt.gl.ClearBufferSubData(bind, internal_format, offset, size, format, ty,
+                                               data);
+                }
+            },
+
+            _ => {
+                drop(ctxt);
+
+                let data = pattern.repeat((range.end - range.start) / pattern.len());
+                self.upload(range.start, &data[..]);
+            },
+        }
+    }
+
     /// Returns a mapping in memory of the content of the buffer.
     ///
     /// There are two possibilities:
@@ -587,6 +821,10 @@ impl Alloc {
     unsafe fn map_shared<D: ?Sized>(&self, bytes_range: Range<usize>, read: bool, write: bool)
                                     -> MappingImpl<'_, D> where D: Content
     {
+        if write {
+            self.discard_shadow_copy();
+        }
+
         if let Some(existing_mapping) = self.persistent_mapping {
             // TODO: optimize so that it's not always necessary to make the context current
             let mut ctxt = self.context.make_current();
@@ -675,6 +913,10 @@ impl Alloc {
     unsafe fn map_impl<D: ?Sized>(&mut self, bytes_range: Range<usize>, read: bool, write: bool)
                                   -> MappingImpl<'_, D> where D: Content
     {
+        if write {
+            self.discard_shadow_copy();
+        }
+
         if self.persistent_mapping.is_some() || self.immutable {
             self.map_shared(bytes_range, read, write)
 
@@ -852,6 +1094,8 @@ impl Alloc {
         assert!(range.end <= self.size);
         assert!(dest_offset + range.end - range.start <= target.size);
 
+        target.discard_shadow_copy();
+
         let mut ctxt = self.context.make_current();
 
         unsafe {
@@ -876,6 +1120,8 @@ impl Drop for Alloc {
             VertexAttributesSystem::purge_buffer(&mut ctxt, self.id);
             destroy_buffer(&mut ctxt, self.id);
         }
+
+        self.context.resource_registry().remove(ResourceKind::Buffer, self.id);
     }
 }
 
@@ -888,6 +1134,57 @@ impl GlObject for Alloc {
     }
 }
 
+/// A buffer that is being transferred to another context of the same share group.
+///
+/// If this object is destroyed without being passed to `Alloc::from_transfer`, the buffer is
+/// leaked.
+pub struct AllocTransfer {
+    share_group: ShareGroup,
+    id: gl::types::GLuint,
+    ty: BufferType,
+    size: usize,
+    persistent_mapping: Option<*mut raw::c_void>,
+    immutable: bool,
+    creation_mode: BufferMode,
+    created_with_buffer_storage: bool,
+    fence: TransferFence,
+}
+
+// the persistent mapping is valid in all the contexts of the share group
+unsafe impl Send for AllocTransfer {}
+
+impl AllocTransfer {
+    /// Returns the group of contexts that can receive this buffer.
+    #[inline]
+    pub fn get_share_group(&self) -> &ShareGroup {
+        &self.share_group
+    }
+
+    /// Returns the size in bytes of the buffer.
+    #[inline]
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+/// What is needed to create a buffer again after a context loss.
+pub struct BufferRecipe {
+    ty: BufferType,
+    mode: BufferMode,
+    size: usize,
+
+    /// Copy of the content of the buffer, if it is known.
+    data: Option<Vec<u8>>,
+}
+
+impl BufferRecipe {
+    /// Returns true if the buffer gets its content back when it is recreated.
+    #[inline]
+    pub fn has_content(&self) -> bool {
+        self.data.is_some()
+    }
+}
+
 /// A mapping of a buffer. Private object.
 enum MappingImpl<'b, D: ?Sized> {
     PersistentMapping {
@@ -1167,6 +1464,69 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext<'_>, size: usiz
         a => a
     };
 
+    let (immutable, created_with_buffer_storage) =
+        match init_buffer_storage(ctxt, id, size, data_ptr, ty, mode)
+    {
+        Ok(r) => r,
+        Err(err) => {
+            if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                ctxt.version >= &Version(Api::GlEs, 2, 0)
+            {
+                ctxt.gl.DeleteBuffers(1, [id].as_ptr());
+            } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+                ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
+            } else {
+                unreachable!();
+            }
+
+            return Err(err);
+        },
+    };
+
+    let persistent_mapping = if let BufferMode::Persistent = mode {
+        if immutable {
+            let ptr = if ctxt.version >= &Version(Api::Gl, 4, 5) {
+                ctxt.gl.MapNamedBufferRange(id, 0, size as gl::types::GLsizeiptr,
+                                            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
+                                            gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
+
+            } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                      ctxt.extensions.gl_arb_map_buffer_range
+            {
+                let bind = bind_buffer(&mut ctxt, id, ty);
+                ctxt.gl.MapBufferRange(bind, 0, size as gl::types::GLsizeiptr,
+                                       gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
+                                       gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
+            } else {
+                unreachable!();
+            };
+
+            if ptr.is_null() {
+                let error = crate::get_gl_error(ctxt);
+                panic!("glMapBufferRange returned null (error: {:?})", error);
+            }
+
+            Some(ptr)
+
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    Ok((id, immutable, created_with_buffer_storage, persistent_mapping))
+}
+
+/// Allocates the storage of a buffer whose name has already been created, and fills it with
+/// `data_ptr` if it is not null.
+///
+/// `size` must not be 0. Returns whether the buffer is immutable and whether it was created
+/// with `glBufferStorage`.
+unsafe fn init_buffer_storage(mut ctxt: &mut CommandContext<'_>, id: gl::types::GLuint, size: usize,
+                              data_ptr: *const (), ty: BufferType, mode: BufferMode)
+                              -> Result<(bool, bool), BufferCreationError>
+{
     // the flags to use in the case where only `glBufferData` is supported
     let mutable_storage_flags = match mode {
         BufferMode::Persistent | BufferMode::Dynamic => gl::DYNAMIC_DRAW,
@@ -1259,52 +1619,38 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext<'_>, size: usiz
     }
 
     if size != obtained_size as usize {
-        if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-            ctxt.version >= &Version(Api::GlEs, 2, 0)
-        {
-            ctxt.gl.DeleteBuffers(1, [id].as_ptr());
-        } else if ctxt.extensions.gl_arb_vertex_buffer_object {
-            ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
-        } else {
-            unreachable!();
-        }
-
         return Err(BufferCreationError::OutOfMemory);
     }
 
-    let persistent_mapping = if let BufferMode::Persistent = mode {
-        if immutable {
-            let ptr = if ctxt.version >= &Version(Api::Gl, 4, 5) {
-                ctxt.gl.MapNamedBufferRange(id, 0, size as gl::types::GLsizeiptr,
-                                            gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
-                                            gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
+    Ok((immutable, created_with_buffer_storage))
+}
 
-            } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
-                      ctxt.extensions.gl_arb_map_buffer_range
-            {
-                let bind = bind_buffer(&mut ctxt, id, ty);
-                ctxt.gl.MapBufferRange(bind, 0, size as gl::types::GLsizeiptr,
-                                       gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
-                                       gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
-            } else {
-                unreachable!();
-            };
+/// Creates the storage of a buffer again after a context loss. The name must have been
+/// generated in the current context.
+///
+/// Returns false if the buffer couldn't be recreated.
+pub unsafe fn recreate_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint,
+                              recipe: &BufferRecipe) -> bool
+{
+    if !is_buffer_type_supported(ctxt, recipe.ty) {
+        return false;
+    }
 
-            if ptr.is_null() {
-                let error = crate::get_gl_error(ctxt);
-                panic!("glMapBufferRange returned null (error: {:?})", error);
-            }
+    let data_ptr = match recipe.data {
+        Some(ref data) if !data.is_empty() => data.as_ptr() as *const (),
+        _ => ptr::null(),
+    };
 
-            Some(ptr)
+    // binding the buffer creates the object behind the name, which is required by the
+    // direct state access functions
+    bind_buffer(ctxt, id, recipe.ty);
 
-        } else {
-            None
-        }
-    } else {
-        None
+    let size = match recipe.size {
+        0 => 1,
+        a => a
     };
 
-    Ok((id, immutable, created_with_buffer_storage, persistent_mapping))
+    init_buffer_storage(ctxt, id, size, data_ptr, recipe.ty, recipe.mode).is_ok()
 }
 
 /// Returns true if a given buffer type is supported on a platform.
@@ -1533,6 +1879,43 @@ unsafe fn indexed_bind_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLui
     panic!();
 }
 
+/// Returns the internal format, format and type to pass to `glClearBufferSubData` in order to
+/// repeat a pattern of `size` bytes made of `num_components` components of the same size.
+///
+/// Integral formats are used so that the bits of the pattern are copied without conversion.
+/// Returns `None` if no format matches, for example for three components of one byte.
+fn clear_buffer_formats(size: usize, num_components: usize)
+                        -> Option<(gl::types::GLenum, gl::types::GLenum, gl::types::GLenum)>
+{
+    if num_components == 0 || size % num_components != 0 {
+        return None;
+    }
+
+    let format = match num_components {
+        1 => gl::RED_INTEGER,
+        2 => gl::RG_INTEGER,
+        3 => gl::RGB_INTEGER,
+        4 => gl::RGBA_INTEGER,
+        _ => return None,
+    };
+
+    let (internal_format, ty) = match (size / num_components, num_components) {
+        (1, 1) => (gl::R8UI, gl::UNSIGNED_BYTE),
+        (1, 2) => (gl::RG8UI, gl::UNSIGNED_BYTE),
+        (1, 4) => (gl::RGBA8UI, gl::UNSIGNED_BYTE),
+        (2, 1) => (gl::R16UI, gl::UNSIGNED_SHORT),
+        (2, 2) => (gl::RG16UI, gl::UNSIGNED_SHORT),
+        (2, 4) => (gl::RGBA16UI, gl::UNSIGNED_SHORT),
+        (4, 1) => (gl::R32UI, gl::UNSIGNED_INT),
+        (4, 2) => (gl::RG32UI, gl::UNSIGNED_INT),
+        (4, 3) => (gl::RGB32UI, gl::UNSIGNED_INT),
+        (4, 4) => (gl::RGBA32UI, gl::UNSIGNED_INT),
+        _ => return None,
+    };
+
+    Some((internal_format, format, ty))
+}
+
 /// Copies from a buffer to another.
 ///
 /// # Safety
@@ -1622,6 +2005,21 @@ unsafe fn destroy_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
     // FIXME: uncomment this and move it from Buffer's destructor
     //self.context.vertex_array_objects.purge_buffer(&mut ctxt, id);
 
+    purge_buffer_bindings(ctxt, id);
+
+    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+        ctxt.version >= &Version(Api::GlEs, 2, 0)
+    {
+        ctxt.gl.DeleteBuffers(1, [id].as_ptr());
+    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+        ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
+    } else {
+        unreachable!();
+    }
+}
+
+/// Removes a buffer from the bind points of the state cache.
+unsafe fn purge_buffer_bindings(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
     if ctxt.state.array_buffer_binding == id {
         ctxt.state.array_buffer_binding = 0;
     }
@@ -1694,16 +2092,6 @@ unsafe fn destroy_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
             point.buffer = 0;
         }
     }
-
-    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-        ctxt.version >= &Version(Api::GlEs, 2, 0)
-    {
-        ctxt.gl.DeleteBuffers(1, [id].as_ptr());
-    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
-        ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
-    } else {
-        unreachable!();
-    }
 }
 
 /// Flushes a range of a mapped buffer.
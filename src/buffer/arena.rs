@@ -0,0 +1,377 @@
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Range;
+
+use crate::backend::Facade;
+use crate::buffer::Buffer;
+use crate::buffer::BufferCreationError;
+use crate::buffer::BufferMode;
+use crate::buffer::BufferSlice;
+use crate::buffer::BufferType;
+use crate::buffer::CopyError;
+use crate::index::{Index, IndexBufferSlice, PrimitiveType};
+use crate::vertex::{Vertex, VertexBufferSlice, VertexFormat};
+
+/// Error that happens when a mesh doesn't fit in the free space of a `MeshArena`.
+///
+/// Calling `MeshArena::defragment` may make enough contiguous space available.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ArenaFullError;
+
+impl fmt::Display for ArenaFullError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Not enough contiguous free space in the arena to store the mesh")
+    }
+}
+
+impl Error for ArenaFullError {}
+
+/// Error that can happen when defragmenting a `MeshArena`.
+#[derive(Copy, Clone, Debug)]
+pub enum DefragmentError {
+    /// The new buffer couldn't be created.
+    BufferCreationError(BufferCreationError),
+
+    /// The meshes couldn't be copied to the new buffer.
+    CopyError(CopyError),
+}
+
+impl fmt::Display for DefragmentError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DefragmentError::BufferCreationError(ref err) => write!(fmt, "{}", err),
+            DefragmentError::CopyError(ref err) => write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl Error for DefragmentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            DefragmentError::BufferCreationError(ref err) => Some(err),
+            DefragmentError::CopyError(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<BufferCreationError> for DefragmentError {
+    #[inline]
+    fn from(err: BufferCreationError) -> DefragmentError {
+        DefragmentError::BufferCreationError(err)
+    }
+}
+
+impl From<CopyError> for DefragmentError {
+    #[inline]
+    fn from(err: CopyError) -> DefragmentError {
+        DefragmentError::CopyError(err)
+    }
+}
+
+/// Identifier of a mesh stored in a `MeshArena`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MeshId {
+    slot: usize,
+    generation: u32,
+}
+
+/// Stores many small meshes in a single buffer.
+///
+/// Each mesh is made of a range of vertices and a range of indices, which are sub-allocated from
+/// the same buffer. Since all the meshes share the same buffer, drawing them uses the same
+/// vertex array object if the backend supports the `glDraw*BaseVertex` functions (OpenGL 3.2,
+/// OpenGL ES 3.2, `GL_ARB_draw_elements_base_vertex` or `GL_OES_draw_elements_base_vertex`).
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {}
+/// # fn example(display: glium::Display, program: glium::Program) {
+/// # use glium::Surface;
+/// #[derive(Copy, Clone)]
+/// struct Vertex { position: [f32; 2] }
+/// implement_vertex!(Vertex, position);
+///
+/// let mut arena = glium::buffer::MeshArena::<Vertex, u16>::new(&display, 1024 * 1024).unwrap();
+///
+/// let triangle = arena.insert(&[
+///     Vertex { position: [-0.5, -0.5] },
+///     Vertex { position: [ 0.0,  0.5] },
+///     Vertex { position: [ 0.5, -0.5] },
+/// ], glium::index::PrimitiveType::TrianglesList, &[0, 1, 2]).unwrap();
+///
+/// let mut target = display.draw();
+/// target.draw(arena.vertices(triangle).unwrap(), &arena.indices(triangle).unwrap(), &program,
+///             &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct MeshArena<V, I> where V: Vertex, I: Index {
+    buffer: Buffer<[u8]>,
+    bindings: VertexFormat,
+
+    /// Free ranges of the buffer, in bytes. Sorted and never adjacent to each other.
+    free: Vec<Range<usize>>,
+
+    /// The meshes, indexed by `MeshId::slot`.
+    slots: Vec<Slot>,
+
+    /// Slots that don't contain a mesh.
+    free_slots: Vec<usize>,
+
+    marker: PhantomData<(V, I)>,
+}
+
+struct Slot {
+    generation: u32,
+    mesh: Option<MeshEntry>,
+}
+
+#[derive(Clone)]
+struct MeshEntry {
+    /// Range of the vertices in the buffer, in bytes.
+    vertices: Range<usize>,
+    /// Range of the indices in the buffer, in bytes.
+    indices: Range<usize>,
+    primitives: PrimitiveType,
+}
+
+impl<V, I> MeshArena<V, I> where V: Vertex, I: Index {
+    /// Builds a new empty arena whose buffer has a size of `size` bytes.
+    pub fn new<F: ?Sized>(facade: &F, size: usize) -> Result<MeshArena<V, I>, BufferCreationError>
+                          where F: Facade
+    {
+        let buffer = Buffer::empty_unsized(facade, BufferType::ArrayBuffer, size,
+                                           BufferMode::Default)?;
+
+        Ok(MeshArena {
+            buffer,
+            bindings: V::build_bindings(),
+            free: if size == 0 { Vec::new() } else { vec![0 .. size] },
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the size in bytes of the buffer.
+    #[inline]
+    pub fn get_size(&self) -> usize {
+        self.buffer.get_size()
+    }
+
+    /// Returns the number of bytes that are not used by a mesh. Because of fragmentation, a mesh
+    /// of this size doesn't necessarily fit in the arena.
+    #[inline]
+    pub fn get_free_space(&self) -> usize {
+        self.free.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// Returns the number of meshes in the arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    /// Returns true if the arena doesn't contain any mesh.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies a mesh in the arena and returns its identifier.
+    ///
+    /// The indices are relative to the first vertex of the mesh.
+    pub fn insert(&mut self, vertices: &[V], primitives: PrimitiveType, indices: &[I])
+                  -> Result<MeshId, ArenaFullError>
+    {
+        // the vertices are aligned to their size, so that the same VAO can be used with a
+        // base vertex for all the meshes
+        let vertices_range = self.allocate(mem::size_of_val(vertices), mem::size_of::<V>())
+                                 .ok_or(ArenaFullError)?;
+        let indices_range = match self.allocate(mem::size_of_val(indices), mem::size_of::<I>()) {
+            Some(r) => r,
+            None => {
+                self.deallocate(vertices_range);
+                return Err(ArenaFullError);
+            },
+        };
+
+        let entry = MeshEntry { vertices: vertices_range, indices: indices_range, primitives };
+
+        if !vertices.is_empty() {
+            self.vertices_slice(&entry).write(vertices);
+        }
+        if !indices.is_empty() {
+            self.indices_slice(&entry).write(indices);
+        }
+
+        let slot = match self.free_slots.pop() {
+            Some(s) => s,
+            None => {
+                self.slots.push(Slot { generation: 0, mesh: None });
+                self.slots.len() - 1
+            },
+        };
+
+        self.slots[slot].mesh = Some(entry);
+        Ok(MeshId { slot, generation: self.slots[slot].generation })
+    }
+
+    /// Removes a mesh from the arena and makes its space available. Returns false if the mesh
+    /// had already been removed.
+    pub fn remove(&mut self, id: MeshId) -> bool {
+        let entry = match self.slots.get_mut(id.slot) {
+            Some(slot) if slot.generation == id.generation => match slot.mesh.take() {
+                Some(e) => e,
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        self.slots[id.slot].generation = self.slots[id.slot].generation.wrapping_add(1);
+        self.free_slots.push(id.slot);
+
+        self.deallocate(entry.vertices);
+        self.deallocate(entry.indices);
+        true
+    }
+
+    /// Returns the vertices of a mesh, or `None` if it has been removed.
+    pub fn vertices(&self, id: MeshId) -> Option<VertexBufferSlice<'_, V>> {
+        let entry = self.get(id)?;
+        Some(VertexBufferSlice::from_buffer_slice(self.vertices_slice(entry), &self.bindings))
+    }
+
+    /// Returns the indices of a mesh, or `None` if it has been removed.
+    pub fn indices(&self, id: MeshId) -> Option<IndexBufferSlice<'_, I>> {
+        let entry = self.get(id)?;
+        Some(IndexBufferSlice::from_buffer_slice(self.indices_slice(entry), entry.primitives))
+    }
+
+    /// Moves all the meshes to the beginning of a new buffer, so that the free space becomes
+    /// contiguous. The identifiers of the meshes stay valid.
+    ///
+    /// # Implementation
+    ///
+    /// Creates a new buffer of the same size and copies the meshes to it with
+    /// `glCopyBufferSubData`. The old buffer is destroyed. If an error happens, the arena is
+    /// left unchanged.
+    pub fn defragment(&mut self) -> Result<(), DefragmentError> {
+        let size = self.buffer.get_size();
+        let new_buffer = Buffer::<[u8]>::empty_unsized(self.buffer.get_context(),
+                                                       BufferType::ArrayBuffer, size,
+                                                       BufferMode::Default)?;
+
+        let mut new_entries = Vec::with_capacity(self.slots.len());
+        let mut cursor = 0;
+
+        for slot in self.slots.iter() {
+            let entry = match slot.mesh {
+                Some(ref e) => e,
+                None => { new_entries.push(None); continue; },
+            };
+
+            let mut copy = |range: &Range<usize>, align: usize| -> Result<Range<usize>, CopyError> {
+                let len = range.end - range.start;
+                if len == 0 {
+                    return Ok(0 .. 0);
+                }
+
+                let start = (cursor + align - 1) / align * align;
+                cursor = start + len;
+
+                let source = self.buffer.slice(range.clone()).unwrap();
+                source.copy_to(new_buffer.slice(start .. start + len).unwrap())?;
+                Ok(start .. start + len)
+            };
+
+            let vertices = copy(&entry.vertices, mem::size_of::<V>())?;
+            let indices = copy(&entry.indices, mem::size_of::<I>())?;
+
+            new_entries.push(Some(MeshEntry { vertices, indices, primitives: entry.primitives }));
+        }
+
+        for (slot, entry) in self.slots.iter_mut().zip(new_entries.into_iter()) {
+            slot.mesh = entry;
+        }
+
+        self.buffer = new_buffer;
+        self.free = if cursor < size { vec![cursor .. size] } else { Vec::new() };
+        Ok(())
+    }
+
+    fn get(&self, id: MeshId) -> Option<&MeshEntry> {
+        match self.slots.get(id.slot) {
+            Some(slot) if slot.generation == id.generation => slot.mesh.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn vertices_slice(&self, entry: &MeshEntry) -> BufferSlice<'_, [V]> {
+        let slice = self.buffer.slice(entry.vertices.clone()).unwrap();
+        unsafe { slice.reinterpret::<[V]>() }
+    }
+
+    fn indices_slice(&self, entry: &MeshEntry) -> BufferSlice<'_, [I]> {
+        let slice = self.buffer.slice(entry.indices.clone()).unwrap();
+        unsafe { slice.reinterpret::<[I]>() }
+    }
+
+    /// Finds a free range of `size` bytes whose start is a multiple of `align`.
+    fn allocate(&mut self, size: usize, align: usize) -> Option<Range<usize>> {
+        if size == 0 {
+            return Some(0 .. 0);
+        }
+
+        for i in 0 .. self.free.len() {
+            let free = self.free[i].clone();
+            let start = (free.start + align - 1) / align * align;
+            if start + size > free.end {
+                continue;
+            }
+
+            let mut remaining = Vec::with_capacity(2);
+            if free.start < start {
+                remaining.push(free.start .. start);
+            }
+            if start + size < free.end {
+                remaining.push(start + size .. free.end);
+            }
+
+            self.free.splice(i .. i + 1, remaining);
+            return Some(start .. start + size);
+        }
+
+        None
+    }
+
+    /// Marks a range as free and merges it with its neighbours.
+    fn deallocate(&mut self, range: Range<usize>) {
+        if range.start == range.end {
+            return;
+        }
+
+        let pos = self.free.iter().position(|r| r.start > range.start).unwrap_or(self.free.len());
+        self.free.insert(pos, range);
+
+        if pos + 1 < self.free.len() && self.free[pos].end == self.free[pos + 1].start {
+            self.free[pos].end = self.free[pos + 1].end;
+            self.free.remove(pos + 1);
+        }
+
+        if pos >= 1 && self.free[pos - 1].end == self.free[pos].start {
+            self.free[pos - 1].end = self.free[pos].end;
+            self.free.remove(pos);
+        }
+    }
+}
+
+impl<V, I> fmt::Debug for MeshArena<V, I> where V: Vertex, I: Index {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "MeshArena {{ buffer: {:?}, meshes: {} }}", self.buffer, self.len())
+    }
+}
@@ -38,9 +38,7 @@ impl Fences {
         let mut new_fences = SmallVec::new();
 
         for existing in existing_fences.drain(..) {
-            if (existing.0.start >= range.start && existing.0.start < range.end) ||
-               (existing.0.end > range.start && existing.0.end < range.end)
-            {
+            if overlaps(&existing.0, &range) {
                 unsafe { sync::wait_linear_sync_fence_and_drop(existing.1, ctxt) };
             } else {
                 new_fences.push(existing);
@@ -59,6 +57,12 @@ impl Fences {
     }
 }
 
+/// Returns true if the two ranges have at least one element in common.
+#[inline]
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
 /// Allows inserting a fence in the list.
 pub struct Inserter<'a> {
     fences: &'a Fences,
@@ -129,3 +133,29 @@ impl<'a> Inserter<'a> {
         *existing_fences = new_fences;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::overlaps;
+
+    #[test]
+    fn overlapping_ranges() {
+        assert!(overlaps(&(0 .. 10), &(5 .. 15)));
+        assert!(overlaps(&(5 .. 15), &(0 .. 10)));
+        assert!(overlaps(&(0 .. 10), &(0 .. 10)));
+
+        // one of the ranges contains the other
+        assert!(overlaps(&(0 .. 20), &(5 .. 10)));
+        assert!(overlaps(&(5 .. 10), &(0 .. 20)));
+
+        // same end
+        assert!(overlaps(&(0 .. 10), &(5 .. 10)));
+    }
+
+    #[test]
+    fn disjoint_ranges() {
+        assert!(!overlaps(&(0 .. 10), &(10 .. 20)));
+        assert!(!overlaps(&(10 .. 20), &(0 .. 10)));
+        assert!(!overlaps(&(0 .. 5), &(15 .. 20)));
+    }
+}
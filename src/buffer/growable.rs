@@ -0,0 +1,182 @@
+use std::cmp;
+use std::fmt;
+
+use crate::backend::Facade;
+use crate::buffer::Buffer;
+use crate::buffer::BufferCreationError;
+use crate::buffer::BufferMode;
+use crate::buffer::BufferSlice;
+use crate::buffer::BufferType;
+use crate::utils::range::RangeArgument;
+
+/// A buffer of elements whose length can change, similar to a `Vec`.
+///
+/// The buffer has a capacity, which is the number of elements that it can hold without
+/// reallocating. When the length exceeds the capacity, a new buffer of twice the capacity is
+/// created and the content of the old one is copied to it by the GPU.
+///
+/// See also `GrowableVertexBuffer` and `GrowableIndexBuffer`.
+pub struct GrowableBuffer<T> where T: Copy {
+    buffer: Buffer<[T]>,
+    len: usize,
+    ty: BufferType,
+    mode: BufferMode,
+}
+
+impl<T> GrowableBuffer<T> where T: Copy {
+    /// Builds a new empty buffer that can hold `capacity` elements without reallocating.
+    pub fn with_capacity<F: ?Sized>(facade: &F, ty: BufferType, capacity: usize, mode: BufferMode)
+                                    -> Result<GrowableBuffer<T>, BufferCreationError>
+                                    where F: Facade
+    {
+        Ok(GrowableBuffer {
+            buffer: Buffer::empty_array(facade, ty, capacity, mode)?,
+            len: 0,
+            ty,
+            mode,
+        })
+    }
+
+    /// Returns the number of elements in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the buffer doesn't contain any element.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements that the buffer can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the underlying buffer. Its length is the capacity of the `GrowableBuffer`.
+    #[inline]
+    pub fn get_buffer(&self) -> &Buffer<[T]> {
+        &self.buffer
+    }
+
+    /// Returns a slice containing all the elements of the buffer.
+    #[inline]
+    pub fn as_slice(&self) -> BufferSlice<'_, [T]> {
+        self.buffer.slice(0 .. self.len).unwrap()
+    }
+
+    /// Returns a slice containing some elements of the buffer, or `None` if out of range.
+    #[inline]
+    pub fn slice<R: RangeArgument<usize>>(&self, range: R) -> Option<BufferSlice<'_, [T]>> {
+        self.as_slice().slice(range)
+    }
+
+    /// Appends an element at the end of the buffer.
+    ///
+    /// Each call uploads the element separately. Prefer `extend_from_slice` to add multiple
+    /// elements at once.
+    ///
+    /// # Panic
+    ///
+    /// Same as `reserve`.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.extend_from_slice(&[value]);
+    }
+
+    /// Appends elements at the end of the buffer.
+    ///
+    /// # Panic
+    ///
+    /// Same as `reserve`.
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        if values.is_empty() {
+            return;
+        }
+
+        self.reserve(values.len());
+        self.buffer.slice(self.len .. self.len + values.len()).unwrap().write(values);
+        self.len += values.len();
+    }
+
+    /// Shortens the buffer to `len` elements. Does nothing if the buffer is already shorter.
+    ///
+    /// The capacity is unchanged.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.len = len;
+        }
+    }
+
+    /// Removes all the elements. The capacity is unchanged.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Makes sure that at least `additional` more elements can be added without reallocating.
+    ///
+    /// The new capacity is at least twice the old one, so that adding elements one by one
+    /// reallocates a logarithmic number of times.
+    ///
+    /// # Implementation
+    ///
+    /// Creates a new buffer and copies the elements to it with `glCopyBufferSubData`. If this is
+    /// not supported, reads the elements then uploads them to the new buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the new buffer can't be created, or if the backend supports neither copying
+    /// between buffers nor reading from buffers.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("Capacity overflow");
+        if required <= self.capacity() {
+            return;
+        }
+
+        let capacity = cmp::max(cmp::max(self.capacity() * 2, required), 4);
+        self.reallocate(capacity);
+    }
+
+    /// Reduces the capacity to the length of the buffer.
+    ///
+    /// # Panic
+    ///
+    /// Same as `reserve`.
+    pub fn shrink_to_fit(&mut self) {
+        if self.len != self.capacity() {
+            let len = self.len;
+            self.reallocate(len);
+        }
+    }
+
+    /// Replaces the buffer with a new one of the given capacity and copies the elements to it.
+    fn reallocate(&mut self, capacity: usize) {
+        debug_assert!(capacity >= self.len);
+
+        let new_buffer = Buffer::<[T]>::empty_array(self.buffer.get_context(), self.ty, capacity,
+                                                    self.mode).unwrap();
+
+        if self.len != 0 {
+            let source = self.buffer.slice(0 .. self.len).unwrap();
+            let target = new_buffer.slice(0 .. self.len).unwrap();
+
+            if source.copy_to(target).is_err() {
+                let data = source.read().expect("The backend supports neither copying between \
+                                                 buffers nor reading from buffers");
+                new_buffer.slice(0 .. self.len).unwrap().write(&data);
+            }
+        }
+
+        self.buffer = new_buffer;
+    }
+}
+
+impl<T> fmt::Debug for GrowableBuffer<T> where T: Copy {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "GrowableBuffer {{ buffer: {:?}, len: {} }}", self.buffer, self.len)
+    }
+}
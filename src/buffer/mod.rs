@@ -44,11 +44,16 @@
 //! # }
 //! ```
 //!
-pub use self::view::{Buffer, BufferAny, BufferMutSlice};
+pub use self::view::{Buffer, BufferAny, BufferMutSlice, BufferTransfer};
 pub use self::view::{BufferSlice, BufferAnySlice};
 pub use self::alloc::{Mapping, WriteMapping, ReadMapping, ReadError, CopyError};
 pub use self::alloc::{is_buffer_read_supported};
+pub(crate) use self::alloc::{BufferRecipe, recreate_buffer};
 pub use self::fences::Inserter;
+pub use self::growable::GrowableBuffer;
+pub use self::arena::{ArenaFullError, DefragmentError, MeshArena, MeshId};
+pub use self::readback::PendingRead;
+pub use self::streaming::{StreamingBuffer, StreamingBufferFullError};
 
 /// DEPRECATED. Only here for backwards compatibility.
 #[deprecated(note = "Only here for backwards compatibility")]
@@ -73,7 +78,11 @@ use std::mem;
 use std::slice;
 
 mod alloc;
+mod arena;
 mod fences;
+mod growable;
+mod readback;
+mod streaming;
 mod view;
 
 /// Trait for types of data that can be put inside buffers.
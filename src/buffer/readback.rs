@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::buffer::Buffer;
+use crate::buffer::Content;
+use crate::buffer::ReadError;
+use crate::sync::SyncFence;
+
+/// Content of a buffer that is being transferred to the CPU.
+///
+/// This object is returned by `read_async`. The data is copied to a staging buffer by the GPU
+/// and can be retrieved once the copy is over, without stalling the pipeline.
+///
+/// ## Example
+///
+/// ```no_run
+/// # fn example(display: glium::Display, buffer: glium::buffer::Buffer<[f32]>) {
+/// let mut pending = Some(buffer.read_async().unwrap());
+///
+/// loop {
+///     // ... draw the next frame ...
+///
+///     if let Some(p) = pending.take() {
+///         match p.try_get() {
+///             Ok(data) => { let _data: Vec<f32> = data.unwrap(); break; },
+///             Err(p) => pending = Some(p),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct PendingRead<T: ?Sized> where T: Content {
+    state: PendingReadState<T>,
+}
+
+// both variants are boxed, as the staging buffer and the data can be large
+enum PendingReadState<T: ?Sized> where T: Content {
+    /// The GPU is copying the data to the staging buffer. The fence is signaled once it's done.
+    Copying(Box<Buffer<T>>, SyncFence),
+
+    /// The data has already been read, because the backend doesn't support copying between
+    /// buffers or fences.
+    Done(Box<T::Owned>),
+}
+
+impl<T: ?Sized> PendingRead<T> where T: Content {
+    #[inline]
+    pub(crate) fn copying(staging: Buffer<T>, fence: SyncFence) -> PendingRead<T> {
+        PendingRead { state: PendingReadState::Copying(Box::new(staging), fence) }
+    }
+
+    #[inline]
+    pub(crate) fn done(data: T::Owned) -> PendingRead<T> {
+        PendingRead { state: PendingReadState::Done(Box::new(data)) }
+    }
+
+    /// Returns true if the data can be retrieved without blocking.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        match self.state {
+            PendingReadState::Copying(_, ref fence) => fence.is_signaled(),
+            PendingReadState::Done(_) => true,
+        }
+    }
+
+    /// Returns the data if it is ready, or gives back the `PendingRead` otherwise.
+    pub fn try_get(self) -> Result<Result<T::Owned, ReadError>, PendingRead<T>> {
+        if self.is_ready() {
+            Ok(self.wait())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Blocks until the data is ready and returns it.
+    pub fn wait(self) -> Result<T::Owned, ReadError> {
+        match self.state {
+            PendingReadState::Copying(staging, fence) => {
+                fence.wait();
+                staging.read()
+            },
+            PendingReadState::Done(data) => Ok(*data),
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for PendingRead<T> where T: Content {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.state {
+            PendingReadState::Copying(ref staging, _) => {
+                write!(fmt, "PendingRead {{ staging: {:?} }}", staging)
+            },
+            PendingReadState::Done(_) => write!(fmt, "PendingRead {{ done }}"),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::any::TypeId;
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+
+use crate::backend::Facade;
+use crate::buffer::Buffer;
+use crate::buffer::BufferCreationError;
+use crate::buffer::BufferMode;
+use crate::buffer::BufferSlice;
+use crate::buffer::BufferType;
+use crate::buffer::Content;
+use crate::index::{Index, IndexBufferSlice, PrimitiveType};
+use crate::vertex::{Vertex, VertexBufferSlice, VertexFormat};
+use crate::BufferSliceExt;
+use crate::CapabilitiesSource;
+use crate::ContextExt;
+
+/// Error that happens when the free space of a `StreamingBuffer` is used by the slices written
+/// during the current frame.
+///
+/// Calling `StreamingBuffer::end_frame` makes the space of the previous frames available again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StreamingBufferFullError;
+
+impl fmt::Display for StreamingBufferFullError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Not enough free space in the streaming buffer for the current frame")
+    }
+}
+
+impl Error for StreamingBufferFullError {}
+
+/// A buffer that holds data that changes every frame, for example per-frame vertices or
+/// uniforms.
+///
+/// The buffer is used as a ring. Each call to `write` sub-allocates a range after the one of
+/// the previous call and wraps around when the end of the buffer is reached. The slices written
+/// between two calls to `end_frame` belong to the same frame and are never overwritten by each
+/// other; if the data of a frame doesn't fit in the buffer, `write` returns an error. The
+/// regions of the previous frames are only overwritten after the GPU has finished the commands
+/// that use them.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {}
+/// # fn example(display: glium::Display, program: glium::Program) {
+/// # use glium::Surface;
+/// #[derive(Copy, Clone)]
+/// struct Vertex { position: [f32; 2] }
+/// implement_vertex!(Vertex, position);
+///
+/// let stream = glium::buffer::StreamingBuffer::new(&display, 1024 * 1024).unwrap();
+///
+/// loop {
+///     let vertices = stream.write_vertices(&[
+///         Vertex { position: [-0.5, -0.5] },
+///         Vertex { position: [ 0.0,  0.5] },
+///         Vertex { position: [ 0.5, -0.5] },
+///     ]).unwrap();
+///     let indices = stream.write_indices(glium::index::PrimitiveType::TrianglesList,
+///                                        &[0u16, 1, 2]).unwrap();
+///
+///     let mut target = display.draw();
+///     target.draw(vertices, &indices, &program, &glium::uniforms::EmptyUniforms,
+///                 &Default::default()).unwrap();
+///     target.finish().unwrap();
+///
+///     stream.end_frame();
+/// }
+/// # }
+/// ```
+///
+/// # Implementation
+///
+/// The buffer is created with `BufferMode::Persistent`. Draw commands and `end_frame` insert a
+/// fence for the ranges that they use, and writing to a range waits for the fences that cover
+/// it. If persistent mapping is not supported, the ranges are written with `glBufferSubData`.
+pub struct StreamingBuffer {
+    buffer: Buffer<[u8]>,
+
+    /// Alignment in bytes of the ranges returned by `write`.
+    alignment: usize,
+
+    /// Offset in bytes where the next allocation starts looking for space.
+    head: Cell<usize>,
+
+    /// Offset in bytes of the first slice of the current frame, or `None` if nothing has been
+    /// written since the last call to `end_frame`.
+    frame_start: Cell<Option<usize>>,
+
+    /// True if the slices of the current frame wrap around the end of the buffer.
+    frame_wrapped: Cell<bool>,
+
+    /// Layouts of the vertex types that have been written, so that the slices returned by
+    /// `write_vertices` can borrow them. Entries are never removed.
+    formats: RefCell<Vec<(TypeId, Box<VertexFormat>)>>,
+}
+
+impl StreamingBuffer {
+    /// Builds a new streaming buffer with a capacity of `size` bytes.
+    pub fn new<F: ?Sized>(facade: &F, size: usize) -> Result<StreamingBuffer, BufferCreationError>
+                          where F: Facade
+    {
+        // the alignment of uniform buffers is the strictest requirement, and 16 is enough for
+        // vertices, indices and draw commands
+        let alignment = {
+            let caps = facade.get_context().get_capabilities();
+            cmp::max(caps.uniform_buffer_offset_alignment as usize, 16)
+        };
+
+        let buffer = Buffer::empty_unsized(facade, BufferType::ArrayBuffer, size,
+                                           BufferMode::Persistent)?;
+
+        Ok(StreamingBuffer {
+            buffer,
+            alignment,
+            head: Cell::new(0),
+            frame_start: Cell::new(None),
+            frame_wrapped: Cell::new(false),
+            formats: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Returns the capacity in bytes of the buffer.
+    #[inline]
+    pub fn get_size(&self) -> usize {
+        self.buffer.get_size()
+    }
+
+    /// Returns the alignment in bytes of the slices returned by `write`.
+    #[inline]
+    pub fn get_alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Returns true if the buffer uses persistent mapping.
+    #[inline]
+    pub fn is_persistent(&self) -> bool {
+        self.buffer.is_persistent()
+    }
+
+    /// Copies some data in the next free range of the buffer and returns the slice that holds
+    /// it.
+    ///
+    /// The slice can be used as a uniform block or as a source of draw commands. If the range is
+    /// still used by the GPU, blocks until it is available.
+    ///
+    /// Returns an error if the free space of the buffer is used by the slices written since the
+    /// last call to `end_frame`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the data is larger than the capacity of the buffer.
+    pub fn write<T: ?Sized>(&self, data: &T) -> Result<BufferSlice<'_, T>, StreamingBufferFullError>
+                            where T: Content
+    {
+        let size = mem::size_of_val(data);
+        let range = self.allocate(size)?;
+
+        let slice = unsafe { self.buffer.slice(range).unwrap().reinterpret::<T>() };
+        slice.write(data);
+        Ok(slice)
+    }
+
+    /// Same as `write`, but returns a slice that can be used as a source of vertices.
+    pub fn write_vertices<T>(&self, data: &[T])
+                             -> Result<VertexBufferSlice<'_, T>, StreamingBufferFullError>
+                             where T: Vertex + 'static
+    {
+        let bindings = self.get_format::<T>();
+        Ok(VertexBufferSlice::from_buffer_slice(self.write(data)?, bindings))
+    }
+
+    /// Same as `write`, but returns a slice that can be used as a source of indices.
+    pub fn write_indices<T>(&self, primitives: PrimitiveType, data: &[T])
+                            -> Result<IndexBufferSlice<'_, T>, StreamingBufferFullError>
+                            where T: Index
+    {
+        Ok(IndexBufferSlice::from_buffer_slice(self.write(data)?, primitives))
+    }
+
+    /// Ends the current frame. The space used by the slices written since the previous call
+    /// becomes available again once the GPU has finished the commands submitted so far.
+    ///
+    /// Call this function once per frame, after the commands that use the slices of the frame.
+    pub fn end_frame(&self) {
+        let frame_start = match self.frame_start.take() {
+            Some(start) => start,
+            None => return,
+        };
+
+        let head = self.head.get();
+        let ranges = if self.frame_wrapped.replace(false) {
+            [frame_start .. self.buffer.get_size(), 0 .. head]
+        } else {
+            [frame_start .. head, 0 .. 0]
+        };
+
+        let mut ctxt = self.buffer.get_context().make_current();
+        for range in ranges.iter().filter(|r| r.start != r.end) {
+            if let Some(inserter) = self.buffer.slice(range.clone()).unwrap().add_fence() {
+                inserter.insert(&mut ctxt);
+            }
+        }
+    }
+
+    /// Finds the range of the next allocation of `size` bytes, without overlapping the slices
+    /// of the current frame.
+    fn allocate(&self, size: usize) -> Result<Range<usize>, StreamingBufferFullError> {
+        let capacity = self.buffer.get_size();
+        assert!(size <= capacity, "The data ({} bytes) doesn't fit in the streaming buffer \
+                                   ({} bytes)", size, capacity);
+
+        let mut start = (self.head.get() + self.alignment - 1) / self.alignment * self.alignment;
+        let mut wrapped = self.frame_wrapped.get();
+
+        if !wrapped && start + size > capacity {
+            start = 0;
+            wrapped = self.frame_start.get().is_some();
+        }
+
+        match self.frame_start.get() {
+            Some(frame_start) if wrapped && start + size > frame_start => {
+                return Err(StreamingBufferFullError);
+            },
+            Some(_) => (),
+            None => self.frame_start.set(Some(start)),
+        }
+
+        self.frame_wrapped.set(wrapped);
+        self.head.set(start + size);
+        Ok(start .. start + size)
+    }
+
+    /// Returns the layout of the vertex type `T`.
+    fn get_format<T>(&self) -> &VertexFormat where T: Vertex + 'static {
+        let mut formats = self.formats.borrow_mut();
+
+        let format: *const VertexFormat = match formats.iter().find(|f| f.0 == TypeId::of::<T>()) {
+            Some(&(_, ref format)) => &**format,
+            None => {
+                formats.push((TypeId::of::<T>(), Box::new(T::build_bindings())));
+                &*formats.last().unwrap().1
+            },
+        };
+
+        // the formats are boxed and never removed, so they live as long as `self`
+        unsafe { &*format }
+    }
+}
+
+impl fmt::Debug for StreamingBuffer {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "StreamingBuffer {{ buffer: {:?}, head: {}, frame_start: {:?} }}",
+               self.buffer, self.head.get(), self.frame_start.get())
+    }
+}
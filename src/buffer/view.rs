@@ -1,5 +1,6 @@
 use std::fmt;
 use std::mem;
+use std::slice;
 use std::borrow::Cow;
 use crate::utils::range::RangeArgument;
 use std::marker::PhantomData;
@@ -14,8 +15,11 @@ use crate::GlObject;
 
 use crate::context::Context;
 use crate::context::CommandContext;
+use crate::context::ShareGroup;
 use std::rc::Rc;
 use crate::ContextExt;
+use crate::debug::LabelNotSupportedError;
+use crate::sync::SyncFence;
 
 use crate::buffer::BufferType;
 use crate::buffer::BufferMode;
@@ -24,11 +28,13 @@ use crate::buffer::Content;
 use crate::buffer::fences::Fences;
 use crate::buffer::fences::Inserter;
 use crate::buffer::alloc::Alloc;
+use crate::buffer::alloc::AllocTransfer;
 use crate::buffer::alloc::Mapping;
 use crate::buffer::alloc::ReadMapping;
 use crate::buffer::alloc::WriteMapping;
 use crate::buffer::alloc::ReadError;
 use crate::buffer::alloc::CopyError;
+use crate::buffer::readback::PendingRead;
 use crate::field::Field;
 
 /// Represents a view of a buffer.
@@ -94,12 +100,69 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         self.alloc.as_ref().unwrap().get_size()
     }
 
+    /// Prepares the buffer for being sent to another context of the same share group, for
+    /// example in order to upload data on a worker thread and use it on the main thread.
+    ///
+    /// The returned object can be sent to another thread, and must be turned back into a buffer
+    /// with `Buffer::from_transfer`. This context no longer tracks the buffer.
+    ///
+    /// # Implementation
+    ///
+    /// Inserts a fence in the commands queue then calls `glFlush`. If fences are not supported,
+    /// calls `glFinish` instead.
+    pub fn into_transfer(mut self) -> BufferTransfer<T> {
+        let alloc = self.alloc.take().unwrap();
+        let mut fence = self.fence.take().unwrap();
+        fence.clean(&mut alloc.get_context().make_current());
+
+        BufferTransfer {
+            alloc: alloc.into_transfer(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Takes ownership of a buffer that has been sent by another context with `into_transfer`.
+    ///
+    /// Returns the transfer if the context of `facade` doesn't belong to the same share group
+    /// as the context that has sent it.
+    ///
+    /// # Implementation
+    ///
+    /// Calls `glWaitSync` on the fence of the transfer, so that the commands that use the buffer
+    /// are executed after the ones that have been issued by the other context. For persistent-
+    /// mapped buffers, blocks until the fence is signaled.
+    pub fn from_transfer<F: ?Sized>(facade: &F, transfer: BufferTransfer<T>)
+                                    -> Result<Buffer<T>, BufferTransfer<T>> where F: Facade
+    {
+        match Alloc::from_transfer(facade, transfer.alloc) {
+            Ok(alloc) => Ok(Buffer {
+                alloc: Some(alloc),
+                fence: Some(Fences::new()),
+                marker: PhantomData,
+            }),
+            Err(alloc) => Err(BufferTransfer {
+                alloc,
+                marker: PhantomData,
+            }),
+        }
+    }
+
     /// Returns true if this buffer uses persistent mapping.
     #[inline]
     pub fn is_persistent(&self) -> bool {
         self.alloc.as_ref().unwrap().uses_persistent_mapping()
     }
 
+    /// Gives a label to the buffer, which is shown by debuggers such as RenderDoc and in
+    /// `Context::get_resources_report`.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`, in which case the label
+    /// is only shown in the resources report.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.alloc.as_ref().unwrap().set_label(label)
+    }
+
     /// Uploads some data in this buffer.
     ///
     /// # Implementation
@@ -140,6 +203,14 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         self.alloc.as_ref().unwrap().invalidate(0, self.get_size());
     }
 
+    /// Sets all the bytes of the buffer to zero.
+    ///
+    /// See `BufferSlice::clear`.
+    #[inline]
+    pub fn clear(&self) {
+        self.as_slice().clear()
+    }
+
     /// Reads the content of the buffer.
     pub fn read(&self) -> Result<T::Owned, ReadError> {
         self.fence.as_ref().unwrap().wait(&mut self.alloc.as_ref().unwrap().get_context().make_current(),
@@ -150,6 +221,14 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         }
     }
 
+    /// Starts reading the content of the buffer without blocking.
+    ///
+    /// See `BufferSlice::read_async`.
+    #[inline]
+    pub fn read_async(&self) -> Result<PendingRead<T>, ReadError> {
+        self.as_slice().read_async()
+    }
+
     /// Maps the buffer in memory for both reading and writing.
     ///
     /// # Implementation
@@ -380,6 +459,14 @@ impl<T> Buffer<[T]> where T: PixelValue {
         let data = self.read()?;
         Ok(S::from_raw(Cow::Owned(data), self.len() as u32))
     }
+
+    /// Sets all the elements of the buffer to `value`.
+    ///
+    /// See `BufferSlice::fill`.
+    #[inline]
+    pub fn fill(&self, value: T) {
+        self.as_slice().fill(value)
+    }
 }
 
 impl<T: ?Sized> fmt::Debug for Buffer<T> where T: Content {
@@ -389,6 +476,35 @@ impl<T: ?Sized> fmt::Debug for Buffer<T> where T: Content {
     }
 }
 
+/// A buffer that is being sent to another context of the same share group.
+///
+/// Obtained with `Buffer::into_transfer`. If this object is destroyed without being passed to
+/// `Buffer::from_transfer`, the buffer is leaked.
+pub struct BufferTransfer<T: ?Sized> where T: Content {
+    alloc: AllocTransfer,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized> BufferTransfer<T> where T: Content {
+    /// Returns the group of contexts that can receive this buffer.
+    #[inline]
+    pub fn get_share_group(&self) -> &ShareGroup {
+        self.alloc.get_share_group()
+    }
+
+    /// Returns the size in bytes of the buffer.
+    #[inline]
+    pub fn get_size(&self) -> usize {
+        self.alloc.get_size()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for BufferTransfer<T> where T: Content {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(fmt, "BufferTransfer {{ size: {} }}", self.get_size())
+    }
+}
+
 impl<T: ?Sized> Drop for Buffer<T> where T: Content {
     #[inline]
     fn drop(&mut self) {
@@ -550,6 +666,32 @@ impl<'a, T: ?Sized> BufferSlice<'a, T> where T: Content + 'a {
         self.alloc.invalidate(self.bytes_start, self.get_size());
     }
 
+    /// Sets all the bytes of the slice to zero.
+    ///
+    /// # Implementation
+    ///
+    /// Calls `glClearBufferSubData` if supported. Otherwise, uploads zeroes with
+    /// `glBufferSubData` or through the persistent mapping.
+    ///
+    #[inline]
+    pub fn clear(&self) {
+        self.fill_bytes(&[0], 1);
+    }
+
+    /// Fills the slice with copies of `pattern`, which is made of `num_components` components
+    /// of the same size.
+    fn fill_bytes(&self, pattern: &[u8], num_components: usize) {
+        self.fence.wait(&mut self.alloc.get_context().make_current(),
+                        self.bytes_start .. self.bytes_end);
+        unsafe { self.alloc.fill(self.bytes_start .. self.bytes_end, pattern, num_components); }
+
+        // the CPU must not access the slice before the GPU has cleared it
+        if let Some(inserter) = self.add_fence() {
+            let mut ctxt = self.alloc.get_context().make_current();
+            inserter.insert(&mut ctxt);
+        }
+    }
+
     /// Reads the content of the buffer.
     pub fn read(&self) -> Result<T::Owned, ReadError> {
         self.fence.wait(&mut self.alloc.get_context().make_current(),
@@ -560,6 +702,59 @@ impl<'a, T: ?Sized> BufferSlice<'a, T> where T: Content + 'a {
         }
     }
 
+    /// Builds a slice of another type that covers the same bytes.
+    ///
+    /// *Safety*: the content of the slice must be a valid `R`.
+    #[inline]
+    pub(crate) unsafe fn reinterpret<R: ?Sized>(self) -> BufferSlice<'a, R> where R: Content {
+        assert!(<R as Content>::is_size_suitable(self.get_size()));
+
+        BufferSlice {
+            alloc: self.alloc,
+            bytes_start: self.bytes_start,
+            bytes_end: self.bytes_end,
+            fence: self.fence,
+            marker: PhantomData,
+        }
+    }
+
+    /// Starts reading the content of the slice without blocking.
+    ///
+    /// Contrary to `read`, this function doesn't wait for the GPU to finish the operations that
+    /// write to the buffer. The returned object can be polled in a later frame.
+    ///
+    /// # Implementation
+    ///
+    /// Copies the slice to a staging buffer with `glCopyBufferSubData` then inserts a fence. If
+    /// copying between buffers or fences are not supported, reads the content immediately.
+    ///
+    pub fn read_async(&self) -> Result<PendingRead<T>, ReadError> {
+        let context = self.alloc.get_context();
+
+        let staging = Buffer::<T>::empty_unsized(context, BufferType::CopyWriteBuffer,
+                                                 self.get_size(), BufferMode::Dynamic);
+        let staging = match staging {
+            Ok(b) => b,
+            Err(_) => return self.read().map(PendingRead::done),
+        };
+
+        if self.alloc.copy_to(self.bytes_start .. self.bytes_end,
+                              staging.alloc.as_ref().unwrap(), 0).is_err()
+        {
+            return self.read().map(PendingRead::done);
+        }
+
+        if let Some(inserter) = self.add_fence() {
+            let mut ctxt = context.make_current();
+            inserter.insert(&mut ctxt);
+        }
+
+        match SyncFence::new(context) {
+            Ok(fence) => Ok(PendingRead::copying(staging, fence)),
+            Err(_) => staging.read().map(PendingRead::done),
+        }
+    }
+
     /// Copies the content of this slice to another slice.
     ///
     /// # Panic
@@ -674,6 +869,40 @@ impl<'a, T> BufferSlice<'a, [T]> where T: PixelValue + 'a {
         let data = self.read()?;
         Ok(S::from_raw(Cow::Owned(data), self.len() as u32))
     }
+
+    /// Sets all the elements of the slice to `value`.
+    ///
+    /// The format of `value` is given by its `ClientFormat`. For example a `u32` or a `f32` is
+    /// repeated every 4 bytes, and a `(f32, f32, f32, f32)` every 16 bytes. The bits of `value`
+    /// are copied as they are, without any conversion.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # fn example(display: glium::Display) {
+    /// let buffer = glium::buffer::Buffer::<[(f32, f32, f32, f32)]>::empty_array(&display,
+    ///                 glium::buffer::BufferType::ArrayBuffer, 64,
+    ///                 glium::buffer::BufferMode::Default).unwrap();
+    ///
+    /// buffer.slice(16 .. 32).unwrap().fill((1.0, 0.0, 0.0, 1.0));
+    /// # }
+    /// ```
+    ///
+    /// # Implementation
+    ///
+    /// Calls `glClearBufferSubData` with an integral format of the same size as `value` if
+    /// supported. Otherwise, or if no such format exists (for example for three components of
+    /// one byte), uploads the repeated value with `glBufferSubData` or through the persistent
+    /// mapping.
+    ///
+    pub fn fill(&self, value: T) {
+        let num_components = T::get_format().get_num_components() as usize;
+        let pattern = unsafe {
+            slice::from_raw_parts(&value as *const T as *const u8, mem::size_of::<T>())
+        };
+
+        self.fill_bytes(pattern, num_components);
+    }
 }
 
 impl<'a, T: ?Sized> fmt::Debug for BufferSlice<'a, T> where T: Content {
@@ -784,7 +1013,7 @@ impl<'a, T: ?Sized> BufferExt for BufferSlice<'a, T> where T: Content {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
@@ -1129,6 +1358,12 @@ impl BufferAny {
         self.size
     }
 
+    /// Gives a label to the buffer. See `Buffer::set_label`.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.alloc.set_label(label)
+    }
+
     /// Invalidates the content of the buffer. The data becomes undefined.
     ///
     /// This operation is a no-op if the backend doesn't support it and for persistent-mapped
@@ -1402,7 +1637,7 @@ impl<'a> BufferExt for BufferAnySlice<'a> {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
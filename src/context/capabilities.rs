@@ -104,6 +104,10 @@ pub struct Capabilities {
     /// Maximum width and height of `glViewport`.
     pub max_viewport_dims: (gl::types::GLint, gl::types::GLint),
 
+    /// Maximum number of viewports that can be passed with `glViewportArrayv`. `None` if
+    /// viewport arrays are not supported.
+    pub max_viewports: Option<gl::types::GLint>,
+
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
@@ -122,6 +126,10 @@ pub struct Capabilities {
     /// Number of available buffer bind points for `GL_UNIFORM_BUFFER`.
     pub max_indexed_uniform_buffer: gl::types::GLint,
 
+    /// Required alignment in bytes of the offset of a buffer bound to `GL_UNIFORM_BUFFER`.
+    /// Zero if uniform buffers are not supported.
+    pub uniform_buffer_offset_alignment: gl::types::GLint,
+
     /// Number of work groups for compute shaders.
     pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
 
@@ -139,6 +147,10 @@ pub struct Capabilities {
 
     /// Maximum samples of an empty framebuffer. `None` if not supported.
     pub max_framebuffer_samples: Option<gl::types::GLint>,
+
+    /// Maximum length of the labels of objects and of the names of debug groups. `None` if
+    /// `GL_KHR_debug` is not supported.
+    pub max_label_length: Option<gl::types::GLint>,
 }
 
 /// Information about an internal format.
@@ -447,6 +459,14 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             (val[0], val[1])
         },
 
+        max_viewports: if version >= &Version(Api::Gl, 4, 1) || extensions.gl_arb_viewport_array {
+            let mut val = 1;
+            gl.GetIntegerv(gl::MAX_VIEWPORTS, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
         max_draw_buffers: {
             if version >= &Version(Api::Gl, 2, 0) ||
                 version >= &Version(Api::GlEs, 3, 0) ||
@@ -515,6 +535,18 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        uniform_buffer_offset_alignment: {
+            if version >= &Version(Api::Gl, 3, 1) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_arb_uniform_buffer_object
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut val);
+                val
+            } else {
+                0
+            }
+        },
+
         max_compute_work_group_count: if version >= &Version(Api::Gl, 4, 3) ||
                                          version >= &Version(Api::GlEs, 3, 1) ||
                                          extensions.gl_arb_compute_shader
@@ -599,6 +631,19 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_label_length: {
+            if version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 2) ||
+               extensions.gl_khr_debug
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_LABEL_LENGTH, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
         renderer,
     }
 }
@@ -56,8 +56,11 @@ extensions! {
     "GL_APPLE_vertex_array_object" => gl_apple_vertex_array_object,
     "GL_ARB_bindless_texture" => gl_arb_bindless_texture,
     "GL_ARB_buffer_storage" => gl_arb_buffer_storage,
+    "GL_ARB_clear_buffer_object" => gl_arb_clear_buffer_object,
+    "GL_ARB_clip_control" => gl_arb_clip_control,
     "GL_ARB_compute_shader" => gl_arb_compute_shader,
     "GL_ARB_copy_buffer" => gl_arb_copy_buffer,
+    "GL_ARB_copy_image" => gl_arb_copy_image,
     "GL_ARB_debug_output" => gl_arb_debug_output,
     "GL_ARB_depth_clamp" => gl_arb_depth_clamp,
     "GL_ARB_depth_texture" => gl_arb_depth_texture,
@@ -83,6 +86,7 @@ extensions! {
     "GL_ARB_invalidate_subdata" => gl_arb_invalidate_subdata,
     "GL_ARB_occlusion_query" => gl_arb_occlusion_query,
     "GL_ARB_occlusion_query2" => gl_arb_occlusion_query2,
+    "GL_ARB_pipeline_statistics_query" => gl_arb_pipeline_statistics_query,
     "GL_ARB_pixel_buffer_object" => gl_arb_pixel_buffer_object,
     "GL_ARB_program_interface_query" => gl_arb_program_interface_query,
     "GL_ARB_query_buffer_object" => gl_arb_query_buffer_object,
@@ -112,6 +116,8 @@ extensions! {
     "GL_ARB_texture_rgb10_a2ui" => gl_arb_texture_rgb10_a2ui,
     "GL_ARB_texture_stencil8" => gl_arb_texture_stencil8,
     "GL_ARB_texture_storage" => gl_arb_texture_storage,
+    "GL_ARB_texture_swizzle" => gl_arb_texture_swizzle,
+    "GL_ARB_texture_view" => gl_arb_texture_view,
     "GL_ARB_timer_query" => gl_arb_timer_query,
     "GL_ARB_transform_feedback3" => gl_arb_transform_feedback3,
     "GL_ARB_uniform_buffer_object" => gl_arb_uniform_buffer_object,
@@ -121,6 +127,7 @@ extensions! {
     "GL_ARB_vertex_shader" => gl_arb_vertex_shader,
     "GL_ARB_vertex_type_10f_11f_11f_rev" => gl_arb_vertex_type_10f_11f_11f_rev,
     "GL_ARB_vertex_type_2_10_10_10_rev" => gl_arb_vertex_type_2_10_10_10_rev,
+    "GL_ARB_viewport_array" => gl_arb_viewport_array,
     "GL_ARM_rgba8" => gl_arm_rgba8,
     "GL_ATI_meminfo" => gl_ati_meminfo,
     "GL_ATI_draw_buffers" => gl_ati_draw_buffers,
@@ -128,6 +135,7 @@ extensions! {
     "GL_EXT_blend_minmax" => gl_ext_blend_minmax,
     "GL_EXT_buffer_storage" => gl_ext_buffer_storage,
     "GL_EXT_debug_marker" => gl_ext_debug_marker,
+    "GL_EXT_depth_bounds_test" => gl_ext_depth_bounds_test,
     "GL_EXT_direct_state_access" => gl_ext_direct_state_access,
     "GL_EXT_memory_object" => gl_ext_memory_object,
     "GL_EXT_memory_object_fd" => gl_ext_memory_object_fd,
@@ -151,7 +159,9 @@ extensions! {
     "GL_EXT_texture3D" => gl_ext_texture3d,
     "GL_EXT_semaphore" => gl_ext_semaphore,
     "GL_EXT_semaphore_fd" => gl_ext_semaphore_fd,
+    "GL_EXT_shadow_samplers" => gl_ext_shadow_samplers,
     "GL_EXT_texture_array" => gl_ext_texture_array,
+    "GL_EXT_texture_border_clamp" => gl_ext_texture_border_clamp,
     "GL_EXT_texture_buffer" => gl_ext_texture_buffer,
     "GL_EXT_texture_buffer_object" => gl_ext_texture_buffer_object,
     "GL_EXT_texture_compression_s3tc" => gl_ext_texture_compression_s3tc,
@@ -162,12 +172,14 @@ extensions! {
     "GL_EXT_texture_shared_exponent" => gl_ext_texture_shared_exponent,
     "GL_EXT_texture_snorm" => gl_ext_texture_snorm,
     "GL_EXT_texture_sRGB" => gl_ext_texture_srgb,
+    "GL_EXT_texture_swizzle" => gl_ext_texture_swizzle,
     "GL_EXT_transform_feedback" => gl_ext_transform_feedback,
     "GL_GREMEDY_string_marker" => gl_gremedy_string_marker,
     "GL_KHR_debug" => gl_khr_debug,
     "GL_KHR_context_flush_control" => gl_khr_context_flush_control,
     "GL_KHR_robustness" => gl_khr_robustness,
     "GL_KHR_robust_buffer_access_behavior" => gl_khr_robust_buffer_access_behavior,
+    "GL_KHR_texture_compression_astc_ldr" => gl_khr_texture_compression_astc_ldr,
     "GL_NV_fbo_color_attachments" => gl_nv_fbo_color_attachments,
     "GL_NV_conditional_render" => gl_nv_conditional_render,
     "GL_NV_copy_buffer" => gl_nv_copy_buffer,
@@ -197,6 +209,7 @@ extensions! {
     "GL_OES_stencil4" => gl_oes_stencil4,
     "GL_OES_tessellation_shader" => gl_oes_tessellation_shader,
     "GL_OES_texture_3D" => gl_oes_texture_3d,
+    "GL_OES_texture_border_clamp" => gl_oes_texture_border_clamp,
     "GL_OES_texture_buffer" => gl_oes_texture_buffer,
     "GL_OES_texture_cube_map_array" => gl_oes_texture_cube_map_array,
     "GL_OES_texture_stencil8" => gl_oes_texture_stencil8,
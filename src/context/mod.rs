@@ -11,8 +11,12 @@ use std::cell::{Cell, RefCell, RefMut};
 use std::marker::PhantomData;
 use std::ffi::CStr;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::fmt;
 use std::os::raw;
 use std::hash::BuildHasherDefault;
+#[cfg(feature = "trace")]
+use std::io::{self, Write};
 
 use fnv::FnvHasher;
 
@@ -35,11 +39,16 @@ use crate::vertex_array_object;
 
 pub use self::capabilities::{ReleaseBehavior, Capabilities, Profile};
 pub use self::extensions::ExtensionsList;
-pub use self::state::GlState;
+pub use self::recovery::{LostResource, RecoveryStatus, ResourceKind};
+pub(crate) use self::recovery::Resource;
+pub use self::resources::{ResourceInfo, ResourcesReport};
+pub use self::state::{GlState, IndexedBlendState};
 pub use self::uuid::UuidError;
 
 mod capabilities;
 mod extensions;
+mod recovery;
+mod resources;
 mod state;
 mod uuid;
 
@@ -97,6 +106,52 @@ pub struct Context {
     /// List of images handles that are resident. We need to call `MakeImageHandleResidentARB`
     /// when rebuilding the context.
     resident_image_handles: RefCell<Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
+
+    /// Group of contexts whose buffers and textures are shared with this one.
+    share_group: ShareGroup,
+
+    /// The resources that are alive in this context, with their size and what is needed to
+    /// recreate them after a context loss.
+    resources: RefCell<recovery::ResourceRegistry>,
+
+    /// Number of times the resources have been recreated after a context loss.
+    recoveries: Cell<u64>,
+}
+
+/// Identifies a group of contexts that share their buffers and textures.
+///
+/// Every context created with `Context::new` starts a new group. A context created with
+/// `Context::new_shared` joins an existing group, and objects such as textures and buffers
+/// can then be transferred between the contexts of that group.
+///
+/// This object can be sent to other threads, for example in order to create a worker context
+/// that uploads resources in the background.
+#[derive(Clone)]
+pub struct ShareGroup {
+    id: Arc<()>,
+}
+
+impl ShareGroup {
+    /// Builds a new group that contains no context yet.
+    #[inline]
+    fn new() -> ShareGroup {
+        ShareGroup { id: Arc::new(()) }
+    }
+}
+
+impl PartialEq for ShareGroup {
+    #[inline]
+    fn eq(&self, other: &ShareGroup) -> bool {
+        Arc::ptr_eq(&self.id, &other.id)
+    }
+}
+
+impl Eq for ShareGroup {}
+
+impl fmt::Debug for ShareGroup {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "ShareGroup({:p})", &*self.id)
+    }
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -161,10 +216,65 @@ impl Context {
         callback_behavior: DebugCallbackBehavior,
     ) -> Result<Rc<Context>, IncompatibleOpenGl>
         where B: Backend + 'static
+    {
+        Context::new_inner(backend, ShareGroup::new(), check_current_context, callback_behavior,
+                           |_| ())
+    }
+
+    /// Builds a new context whose buffers and textures are shared with the contexts of
+    /// `share_group`.
+    ///
+    /// This is the same as `new`, except that the new context joins an existing group instead
+    /// of starting a new one. Buffers and textures created by one context of the group can be
+    /// transferred to another one with `into_transfer` and `from_transfer`. This is typically
+    /// used to create a worker context that uploads resources on another thread.
+    ///
+    /// The OpenGL context of the backend **must** share its lists with the contexts of the group,
+    /// for example by being created with glutin's `with_shared_lists`. glium can't verify this.
+    pub unsafe fn new_shared<B>(
+        backend: B,
+        share_group: &ShareGroup,
+        check_current_context: bool,
+        callback_behavior: DebugCallbackBehavior,
+    ) -> Result<Rc<Context>, IncompatibleOpenGl>
+        where B: Backend + 'static
+    {
+        Context::new_inner(backend, share_group.clone(), check_current_context,
+                           callback_behavior, |_| ())
+    }
+
+    /// Builds a new context that records all the OpenGL commands it executes to `trace`.
+    ///
+    /// This is the same as `new`, except that the commands can later be replayed with a
+    /// `trace::Replayer`. The end of each frame is marked in the trace when the buffers are
+    /// swapped. Call `flush_trace` to make sure that everything has been written.
+    #[cfg(feature = "trace")]
+    pub unsafe fn new_with_trace<B, W>(
+        backend: B,
+        check_current_context: bool,
+        callback_behavior: DebugCallbackBehavior,
+        trace: W,
+    ) -> Result<Rc<Context>, IncompatibleOpenGl>
+        where B: Backend + 'static, W: Write + 'static
+    {
+        let recorder = crate::trace::Recorder::new(trace);
+        Context::new_inner(backend, ShareGroup::new(), check_current_context, callback_behavior,
+                           move |gl| gl.set_recorder(recorder))
+    }
+
+    unsafe fn new_inner<B, F>(
+        backend: B,
+        share_group: ShareGroup,
+        check_current_context: bool,
+        callback_behavior: DebugCallbackBehavior,
+        setup_gl: F,
+    ) -> Result<Rc<Context>, IncompatibleOpenGl>
+        where B: Backend + 'static, F: FnOnce(&mut gl::Gl)
     {
         backend.make_current();
 
-        let gl = gl::Gl::load_with(|symbol| backend.get_proc_address(symbol) as *const _);
+        let mut gl = gl::Gl::load_with(|symbol| backend.get_proc_address(symbol) as *const _);
+        setup_gl(&mut gl);
         let gl_state: RefCell<GlState> = RefCell::new(Default::default());
 
         let version = version::get_gl_version(&gl);
@@ -212,6 +322,9 @@ impl Context {
             samplers,
             resident_texture_handles,
             resident_image_handles,
+            share_group,
+            resources: RefCell::new(recovery::ResourceRegistry::new()),
+            recoveries: Cell::new(0),
         });
 
         if context.debug_callback.is_some() {
@@ -245,15 +358,33 @@ impl Context {
     /// Changes the OpenGL context associated with this context.
     ///
     /// The new context **must** have lists shared with the old one.
+    ///
+    /// If the old context has been lost and the recovery mode is enabled, the new context doesn't
+    /// need to share its lists with the old one. The resources are recreated in the new context
+    /// instead. See `enable_resource_recovery`.
     pub unsafe fn rebuild<B>(&self, new_backend: B) -> Result<(), IncompatibleOpenGl>
         where B: Backend + 'static
     {
+        let lost = self.is_context_lost();
+
         // framebuffer objects and vertex array objects aren't shared,
         // so we have to destroy them
         {
             let mut ctxt = self.make_current();
             fbo::FramebuffersContainer::purge_all(&mut ctxt);
             vertex_array_object::VertexAttributesSystem::purge_all(&mut ctxt);
+
+            // samplers and bindless handles don't survive a context loss
+            if lost {
+                for (_, s) in mem::replace(&mut *ctxt.samplers,
+                                           HashMap::with_hasher(Default::default()))
+                {
+                    s.destroy(&mut ctxt);
+                }
+
+                ctxt.resident_texture_handles.clear();
+                ctxt.resident_image_handles.clear();
+            }
         }
 
         new_backend.make_current();
@@ -263,20 +394,99 @@ impl Context {
         *self.backend.borrow_mut() = Box::new(new_backend);
 
         // making textures resident
-        let textures = self.resident_texture_handles.borrow();
-        for &texture in textures.iter() {
+        for &texture in self.resident_texture_handles.borrow().iter() {
             self.gl.MakeTextureHandleResidentARB(texture);
         }
 
         // making images resident
-        let images = self.resident_image_handles.borrow();
-        for &(image, access) in images.iter() {
+        for &(image, access) in self.resident_image_handles.borrow().iter() {
             self.gl.MakeImageHandleResidentARB(image, access);
         }
 
+        if lost {
+            self.recover_resources();
+        }
+
         Ok(())
     }
 
+    /// Enables the recovery of the resources after a context loss.
+    ///
+    /// Once enabled, glium keeps for each buffer, texture and program what is needed to create
+    /// it again. When the context is lost (see `is_context_lost`), build a new OpenGL context
+    /// and pass it to `rebuild`. The resources are then recreated in the new context, and the
+    /// objects that you hold stay valid.
+    ///
+    /// Only the resources created after this function has been called are recreated. The other
+    /// resources must be destroyed before calling `rebuild`, otherwise their destructor could
+    /// destroy a resource that has been recreated.
+    ///
+    /// After the resources have been recreated, `callback` is called with the list of the
+    /// resources that need your attention:
+    ///
+    /// - Buffers whose content is known by glium get their content back. Only the buffers created
+    ///   with `BufferMode::Default` or `BufferMode::Immutable` whose content has not been modified
+    ///   by the GPU or by a mapping are in this situation. The other buffers are reported with
+    ///   `RecoveryStatus::ContentLost`.
+    /// - Textures are recreated with their format, dimensions and mipmaps, but always with
+    ///   `RecoveryStatus::ContentLost`.
+    /// - Programs are compiled and linked again. Programs created from SPIR-V aren't recreated.
+    /// - Persistent-mapped buffers, texture views, imported textures, render buffers and any
+    ///   object whose name can't be obtained again in the new context are reported with
+    ///   `RecoveryStatus::Destroyed`. They must be dropped and built again. Drop them before
+    ///   creating other resources, as their name can be given to a new resource.
+    ///
+    /// Queries, sync fences and bindless handles are never recreated.
+    ///
+    /// Keeping the content of the buffers uses memory on the CPU side. Calling this function
+    /// again replaces the callback, but keeps the resources that are already tracked.
+    pub fn enable_resource_recovery<F>(&self, callback: F)
+        where F: FnMut(&[LostResource]) + 'static
+    {
+        self.resources.borrow_mut().callback = Some(Box::new(callback));
+    }
+
+    /// Returns the registry of the resources of this context.
+    #[inline]
+    pub(crate) fn resource_registry(&self) -> RefMut<'_, recovery::ResourceRegistry> {
+        self.resources.borrow_mut()
+    }
+
+    /// Returns the number of times the resources have been recreated after a context loss.
+    ///
+    /// Objects that cache the state of their OpenGL object can compare this value in order to
+    /// know whether their cache is still valid.
+    #[inline]
+    pub(crate) fn get_recovery_count(&self) -> u64 {
+        self.recoveries.get()
+    }
+
+    /// Recreates the resources in the current context after a context loss, then calls the
+    /// callback of the recovery mode.
+    unsafe fn recover_resources(&self) {
+        let lost = {
+            let mut registry = self.resource_registry();
+            if !registry.is_recovery_enabled() {
+                return;
+            }
+
+            let mut ctxt = self.make_current();
+            registry.recreate(&mut ctxt)
+        };
+
+        self.recoveries.set(self.recoveries.get() + 1);
+
+        // the callback is taken out of the registry so that it can create or destroy resources
+        let mut callback = mem::replace(&mut self.resource_registry().callback,
+                                        Some(Box::new(|_| ())));
+
+        if let Some(ref mut callback) = callback {
+            callback(&lost);
+        }
+
+        self.resource_registry().callback = callback;
+    }
+
     /// Swaps the buffers in the backend.
     pub fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
         if self.state.borrow().lost_context {
@@ -314,6 +524,13 @@ impl Context {
             unsafe { backend.make_current() };
         }
 
+        #[cfg(feature = "trace")]
+        {
+            if let Some(recorder) = self.gl.recorder() {
+                recorder.end_frame();
+            }
+        }
+
         // swapping
         let err = backend.swap_buffers();
         if let Err(SwapBuffersError::ContextLost) = err {
@@ -322,6 +539,18 @@ impl Context {
         err
     }
 
+    /// Writes the pending commands to the trace that this context records to.
+    ///
+    /// Returns the first error that happened while writing the trace, if any. Does nothing if
+    /// the context doesn't record a trace.
+    #[cfg(feature = "trace")]
+    pub fn flush_trace(&self) -> io::Result<()> {
+        match self.gl.recorder() {
+            Some(recorder) => recorder.flush(),
+            None => Ok(()),
+        }
+    }
+
     /// Returns the OpenGL version
     #[inline]
     #[deprecated(note = "use `get_opengl_version` instead.")]
@@ -329,6 +558,15 @@ impl Context {
         &self.version
     }
 
+    /// Returns the group of contexts whose buffers and textures are shared with this one.
+    ///
+    /// Pass it to `Context::new_shared` in order to create a context that belongs to the
+    /// same group.
+    #[inline]
+    pub fn get_share_group(&self) -> &ShareGroup {
+        &self.share_group
+    }
+
     /// Returns the OpenGL version detected by this context.
     #[inline]
     pub fn get_opengl_version(&self) -> &Version {
@@ -438,6 +676,17 @@ impl Context {
         lost
     }
 
+    /// Marks the OpenGL context as lost.
+    ///
+    /// Use this when the windowing system destroys the context without OpenGL reporting a reset,
+    /// for example when an Android application is paused. `is_context_lost` then returns `true`
+    /// until `rebuild` is called, and `rebuild` recreates the resources if the recovery mode is
+    /// enabled.
+    #[inline]
+    pub fn mark_context_lost(&self) {
+        self.state.borrow_mut().lost_context = true;
+    }
+
     /// Returns the behavior when the current OpenGL context is changed.
     ///
     /// The most common value is `Flush`. In order to get `None` you must explicitly request it
@@ -501,6 +750,27 @@ impl Context {
         }
     }
 
+    /// Returns the list of the buffers, textures, render buffers and programs that are alive in
+    /// this context, with an estimate of their size.
+    ///
+    /// Only the resources created by glium are listed. Textures created with `from_id` without
+    /// taking ownership are not included.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # fn example(display: glium::Display) {
+    /// let report = display.get_resources_report();
+    /// println!("{} bytes used by {} resources", report.get_total_size(), report.resources.len());
+    /// # }
+    /// ```
+    pub fn get_resources_report(&self) -> ResourcesReport {
+        ResourcesReport {
+            resources: self.resources.borrow().snapshot(),
+            free_video_memory: self.get_free_video_memory(),
+        }
+    }
+
     /// Reads the content of the front buffer.
     ///
     /// You will only see the data that has finished being drawn.
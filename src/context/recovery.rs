@@ -0,0 +1,398 @@
+//! Registry of the resources of a context, and their recreation after a context loss.
+//!
+//! The registry knows the size and the label of each buffer, texture, render buffer and program,
+//! which are shown by `Context::get_resources_report`.
+//!
+//! When the recovery mode is enabled, glium also keeps for each resource what is needed to create
+//! it again. After the context has been lost and rebuilt, the resources are recreated with the
+//! same OpenGL names, so that the objects held by the user stay valid.
+//!
+//! The names of a newly-created context are all unused, and drivers usually hand them out in
+//! increasing order. We generate names until we obtain the ones that the resources had before the
+//! loss, recreate the resources, then delete the names that we didn't need. The number of names
+//! that are generated is bounded, and the resources whose name couldn't be obtained are reported
+//! as destroyed.
+
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasherDefault;
+
+use fnv::FnvHasher;
+
+use crate::gl;
+use crate::context::CommandContext;
+use crate::context::ResourceInfo;
+use crate::version::Api;
+use crate::version::Version;
+
+use crate::buffer::BufferRecipe;
+use crate::program::ProgramRecipe;
+use crate::texture::TextureRecipe;
+
+/// Type of a resource of a context.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    /// A buffer, for example a `VertexBuffer`.
+    Buffer,
+    /// A texture.
+    Texture,
+    /// A render buffer.
+    RenderBuffer,
+    /// A `Program` or a `ComputeShader`.
+    Program,
+}
+
+/// What glium could do with a resource after a context loss.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RecoveryStatus {
+    /// The resource has been recreated, but its content is undefined and must be uploaded again.
+    ContentLost,
+
+    /// The resource couldn't be recreated. It must be destroyed and built again.
+    Destroyed,
+}
+
+/// A resource that needs the attention of the application after a context loss.
+///
+/// See `Context::enable_resource_recovery`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LostResource {
+    /// Type of the resource.
+    pub kind: ResourceKind,
+
+    /// OpenGL name of the resource, as returned by `GlObject::get_id`.
+    pub id: gl::types::GLuint,
+
+    /// What happened to the resource.
+    pub status: RecoveryStatus,
+}
+
+/// Callback called after the resources have been recreated.
+pub type RecoveryCallback = Box<dyn FnMut(&[LostResource])>;
+
+/// A resource that is alive in a context.
+pub struct Resource<R> {
+    /// What is needed to recreate the resource. `None` if the recovery mode wasn't enabled when
+    /// the resource was created, or if the resource can't be recreated, for example because its
+    /// storage belongs to another texture or because it has been imported.
+    pub recipe: Option<R>,
+
+    /// Estimate of the amount of video memory used by the resource, in bytes.
+    pub size: usize,
+
+    /// The label of the resource, if any.
+    pub label: Option<String>,
+}
+
+type Resources<R> = HashMap<gl::types::GLuint, Resource<R>, BuildHasherDefault<FnvHasher>>;
+
+/// Resources of a context, with their size, their label and what is needed to recreate them.
+pub struct ResourceRegistry {
+    /// Called after the resources have been recreated. `None` if the recovery mode isn't
+    /// enabled.
+    pub callback: Option<RecoveryCallback>,
+
+    /// The buffers of the context.
+    pub buffers: Resources<BufferRecipe>,
+
+    /// The textures of the context.
+    pub textures: Resources<TextureRecipe>,
+
+    /// The render buffers of the context. They are never recreated.
+    pub render_buffers: Resources<()>,
+
+    /// The programs of the context.
+    pub programs: Resources<ProgramRecipe>,
+}
+
+impl ResourceRegistry {
+    /// Builds a new empty registry, with the recovery mode disabled.
+    pub fn new() -> ResourceRegistry {
+        ResourceRegistry {
+            callback: None,
+            buffers: HashMap::with_hasher(Default::default()),
+            textures: HashMap::with_hasher(Default::default()),
+            render_buffers: HashMap::with_hasher(Default::default()),
+            programs: HashMap::with_hasher(Default::default()),
+        }
+    }
+
+    /// Returns true if the recovery mode is enabled, in which case the recipes of the new
+    /// resources are kept.
+    #[inline]
+    pub fn is_recovery_enabled(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// Adds a buffer. The recipe is ignored if the recovery mode isn't enabled.
+    #[inline]
+    pub fn insert_buffer(&mut self, id: gl::types::GLuint, size: usize,
+                         recipe: Option<BufferRecipe>)
+    {
+        let resource = self.new_resource(size, recipe);
+        self.buffers.insert(id, resource);
+    }
+
+    /// Adds a texture. The recipe is ignored if the recovery mode isn't enabled.
+    #[inline]
+    pub fn insert_texture(&mut self, id: gl::types::GLuint, size: usize,
+                          recipe: Option<TextureRecipe>)
+    {
+        let resource = self.new_resource(size, recipe);
+        self.textures.insert(id, resource);
+    }
+
+    /// Adds a render buffer.
+    #[inline]
+    pub fn insert_render_buffer(&mut self, id: gl::types::GLuint, size: usize) {
+        let resource = self.new_resource(size, None);
+        self.render_buffers.insert(id, resource);
+    }
+
+    /// Adds a program. The recipe is ignored if the recovery mode isn't enabled.
+    #[inline]
+    pub fn insert_program(&mut self, id: gl::types::GLuint, size: usize,
+                          recipe: Option<ProgramRecipe>)
+    {
+        let resource = self.new_resource(size, recipe);
+        self.programs.insert(id, resource);
+    }
+
+    /// Removes a resource. Does nothing if it isn't in the registry.
+    pub fn remove(&mut self, kind: ResourceKind, id: gl::types::GLuint) {
+        match kind {
+            ResourceKind::Buffer => { self.buffers.remove(&id); },
+            ResourceKind::Texture => { self.textures.remove(&id); },
+            ResourceKind::RenderBuffer => { self.render_buffers.remove(&id); },
+            ResourceKind::Program => { self.programs.remove(&id); },
+        }
+    }
+
+    /// Changes the label of a resource. Does nothing if it isn't in the registry.
+    pub fn set_label(&mut self, kind: ResourceKind, id: gl::types::GLuint, label: Option<String>) {
+        let current = match kind {
+            ResourceKind::Buffer => self.buffers.get_mut(&id).map(|r| &mut r.label),
+            ResourceKind::Texture => self.textures.get_mut(&id).map(|r| &mut r.label),
+            ResourceKind::RenderBuffer => self.render_buffers.get_mut(&id).map(|r| &mut r.label),
+            ResourceKind::Program => self.programs.get_mut(&id).map(|r| &mut r.label),
+        };
+
+        if let Some(current) = current {
+            *current = label;
+        }
+    }
+
+    /// Returns the list of the resources, sorted by decreasing size.
+    pub fn snapshot(&self) -> Vec<ResourceInfo> {
+        fn infos<'a, R>(kind: ResourceKind, resources: &'a Resources<R>)
+                        -> impl Iterator<Item = ResourceInfo> + 'a
+        {
+            resources.iter().map(move |(&id, resource)| {
+                ResourceInfo {
+                    kind,
+                    id,
+                    size: resource.size,
+                    label: resource.label.clone(),
+                }
+            })
+        }
+
+        let mut resources = infos(ResourceKind::Buffer, &self.buffers)
+            .chain(infos(ResourceKind::Texture, &self.textures))
+            .chain(infos(ResourceKind::RenderBuffer, &self.render_buffers))
+            .chain(infos(ResourceKind::Program, &self.programs))
+            .collect::<Vec<_>>();
+
+        resources.sort_by(|a, b| {
+            b.size.cmp(&a.size).then((a.kind as u8).cmp(&(b.kind as u8))).then(a.id.cmp(&b.id))
+        });
+
+        resources
+    }
+
+    /// Recreates all the resources in the context, which must be a newly-created one.
+    ///
+    /// Returns the list of resources that need the attention of the application. The resources
+    /// that couldn't be recreated are kept in the registry without a recipe.
+    pub unsafe fn recreate(&mut self, ctxt: &mut CommandContext<'_>) -> Vec<LostResource> {
+        let mut lost = Vec::new();
+
+        // buffers
+        {
+            let names = reserve_names(ctxt, self.buffers.keys().cloned(), gen_buffers);
+            for (&id, resource) in self.buffers.iter_mut() {
+                let status = match (names.contains(&id), resource.recipe.as_ref()) {
+                    (true, Some(r)) if crate::buffer::recreate_buffer(ctxt, id, r) => {
+                        if r.has_content() { None } else { Some(RecoveryStatus::ContentLost) }
+                    },
+                    _ => Some(RecoveryStatus::Destroyed),
+                };
+
+                if status == Some(RecoveryStatus::Destroyed) {
+                    resource.recipe = None;
+                }
+
+                if let Some(status) = status {
+                    lost.push(LostResource { kind: ResourceKind::Buffer, id, status });
+                }
+            }
+            delete_unused_names(ctxt, names, &self.buffers, delete_buffer);
+        }
+
+        // textures
+        {
+            let names = reserve_names(ctxt, self.textures.keys().cloned(), gen_textures);
+            for (&id, resource) in self.textures.iter_mut() {
+                let status = match (names.contains(&id), resource.recipe.as_ref()) {
+                    (true, Some(r)) => {
+                        crate::texture::recreate_texture(ctxt, id, r);
+                        RecoveryStatus::ContentLost
+                    },
+                    _ => {
+                        resource.recipe = None;
+                        RecoveryStatus::Destroyed
+                    },
+                };
+
+                lost.push(LostResource { kind: ResourceKind::Texture, id, status });
+            }
+            delete_unused_names(ctxt, names, &self.textures, delete_texture);
+        }
+
+        // render buffers
+        for &id in self.render_buffers.keys() {
+            lost.push(LostResource {
+                kind: ResourceKind::RenderBuffer,
+                id,
+                status: RecoveryStatus::Destroyed,
+            });
+        }
+
+        // programs
+        {
+            // shaders share their names with programs, so all the program names must be
+            // reserved before any shader is created
+            let names = reserve_names(ctxt, self.programs.keys().cloned(), create_programs);
+            for (&id, resource) in self.programs.iter_mut() {
+                let recreated = match (names.contains(&id), resource.recipe.as_ref()) {
+                    (true, Some(r)) => crate::program::recreate_program(ctxt, id, r),
+                    _ => false,
+                };
+
+                if !recreated {
+                    resource.recipe = None;
+                    lost.push(LostResource {
+                        kind: ResourceKind::Program,
+                        id,
+                        status: RecoveryStatus::Destroyed,
+                    });
+                }
+            }
+            delete_unused_names(ctxt, names, &self.programs, delete_program);
+        }
+
+        lost.sort_by_key(|r| (r.kind as u8, r.id));
+        lost
+    }
+
+    /// Builds the entry of a new resource.
+    #[inline]
+    fn new_resource<R>(&self, size: usize, recipe: Option<R>) -> Resource<R> {
+        Resource {
+            recipe: if self.is_recovery_enabled() { recipe } else { None },
+            size,
+            label: None,
+        }
+    }
+}
+
+/// Maximum number of names that are generated in addition to the ones that are wanted, in case
+/// the driver doesn't hand out the names of a new context in increasing order.
+const MAX_EXTRA_NAMES: usize = 1024;
+
+/// Generates names with `gen` until all the names of `wanted` have been obtained or until too
+/// many names have been generated. Returns all the names that have been generated.
+unsafe fn reserve_names<I>(ctxt: &mut CommandContext<'_>, wanted: I,
+                           gen: unsafe fn(&mut CommandContext<'_>, &mut [gl::types::GLuint]))
+                           -> HashSet<gl::types::GLuint, BuildHasherDefault<FnvHasher>>
+    where I: Iterator<Item = gl::types::GLuint>
+{
+    let mut missing = wanted.collect::<HashSet<_, BuildHasherDefault<FnvHasher>>>();
+    let max_names = missing.len() + MAX_EXTRA_NAMES;
+    let mut names = HashSet::with_hasher(Default::default());
+
+    while !missing.is_empty() && names.len() < max_names {
+        let mut chunk = vec![0; cmp::min(missing.len(), max_names - names.len())];
+        gen(ctxt, &mut chunk);
+
+        // the driver can't give us any more name
+        if chunk[0] == 0 {
+            break;
+        }
+
+        for name in chunk.into_iter().filter(|&n| n != 0) {
+            missing.remove(&name);
+            names.insert(name);
+        }
+    }
+
+    names
+}
+
+/// Deletes the names of `names` that don't belong to a resource.
+///
+/// The names of the resources that couldn't be recreated are kept, so that they aren't given to
+/// another resource before the destructor of the old one is called.
+unsafe fn delete_unused_names<T>(ctxt: &mut CommandContext<'_>,
+                                 names: HashSet<gl::types::GLuint, BuildHasherDefault<FnvHasher>>,
+                                 resources: &Resources<T>,
+                                 delete: unsafe fn(&mut CommandContext<'_>, gl::types::GLuint))
+{
+    for name in names {
+        if !resources.contains_key(&name) {
+            delete(ctxt, name);
+        }
+    }
+}
+
+unsafe fn gen_buffers(ctxt: &mut CommandContext<'_>, names: &mut [gl::types::GLuint]) {
+    if ctxt.version >= &Version(Api::Gl, 1, 5) || ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        ctxt.gl.GenBuffers(names.len() as gl::types::GLsizei, names.as_mut_ptr());
+    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+        ctxt.gl.GenBuffersARB(names.len() as gl::types::GLsizei, names.as_mut_ptr());
+    } else {
+        unreachable!();
+    }
+}
+
+unsafe fn delete_buffer(ctxt: &mut CommandContext<'_>, name: gl::types::GLuint) {
+    if ctxt.version >= &Version(Api::Gl, 1, 5) || ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        ctxt.gl.DeleteBuffers(1, &name);
+    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+        ctxt.gl.DeleteBuffersARB(1, &name);
+    } else {
+        unreachable!();
+    }
+}
+
+unsafe fn gen_textures(ctxt: &mut CommandContext<'_>, names: &mut [gl::types::GLuint]) {
+    ctxt.gl.GenTextures(names.len() as gl::types::GLsizei, names.as_mut_ptr());
+}
+
+unsafe fn delete_texture(ctxt: &mut CommandContext<'_>, name: gl::types::GLuint) {
+    ctxt.gl.DeleteTextures(1, &name);
+}
+
+unsafe fn create_programs(ctxt: &mut CommandContext<'_>, names: &mut [gl::types::GLuint]) {
+    // programs can only be created one by one
+    for name in names.iter_mut() {
+        *name = ctxt.gl.CreateProgram();
+        if *name == 0 {
+            break;
+        }
+    }
+}
+
+unsafe fn delete_program(ctxt: &mut CommandContext<'_>, name: gl::types::GLuint) {
+    ctxt.gl.DeleteProgram(name);
+}
@@ -0,0 +1,58 @@
+//! Report of the resources that are alive in a context and of their size.
+
+use crate::gl;
+use crate::context::ResourceKind;
+
+/// Information about a resource that is alive in a context.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceInfo {
+    /// Type of the resource.
+    pub kind: ResourceKind,
+
+    /// OpenGL name of the resource, as returned by `GlObject::get_id`.
+    pub id: gl::types::GLuint,
+
+    /// Estimate of the amount of video memory used by the resource, in bytes.
+    ///
+    /// For textures and render buffers, this is computed from the format, the dimensions and the
+    /// number of mipmap levels and samples. The driver can use more memory because of padding or
+    /// compression. Texture views share the storage of their source and have a size of `0`.
+    /// For programs, this is the size of their binary if it is known, otherwise `0`.
+    pub size: usize,
+
+    /// The label of the resource, if any.
+    pub label: Option<String>,
+}
+
+/// Snapshot of the resources that are alive in a context.
+///
+/// Obtained with `Context::get_resources_report`.
+#[derive(Clone, Debug)]
+pub struct ResourcesReport {
+    /// The resources, sorted by decreasing size.
+    pub resources: Vec<ResourceInfo>,
+
+    /// Estimate of the amount of video memory available in bytes, as returned by
+    /// `Context::get_free_video_memory`.
+    pub free_video_memory: Option<usize>,
+}
+
+impl ResourcesReport {
+    /// Returns the sum of the sizes of all the resources, in bytes.
+    #[inline]
+    pub fn get_total_size(&self) -> usize {
+        self.resources.iter().map(|r| r.size).sum()
+    }
+
+    /// Returns the sum of the sizes of the resources of the given type, in bytes.
+    #[inline]
+    pub fn get_total_size_of(&self, kind: ResourceKind) -> usize {
+        self.resources.iter().filter(|r| r.kind == kind).map(|r| r.size).sum()
+    }
+
+    /// Returns the number of resources of the given type.
+    #[inline]
+    pub fn get_count_of(&self, kind: ResourceKind) -> usize {
+        self.resources.iter().filter(|r| r.kind == kind).count()
+    }
+}
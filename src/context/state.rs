@@ -15,6 +15,9 @@ pub struct GlState {
     /// Whether GL_BLEND is enabled
     pub enabled_blend: bool,
 
+    /// Whether GL_COLOR_LOGIC_OP is enabled
+    pub enabled_color_logic_op: bool,
+
     /// Whether GL_CULL_FACE is enabled
     pub enabled_cull_face: bool,
 
@@ -27,6 +30,9 @@ pub struct GlState {
     /// Whether GL_DEPTH_TEST is enabled
     pub enabled_depth_test: bool,
 
+    /// Whether GL_DEPTH_BOUNDS_TEST_EXT is enabled
+    pub enabled_depth_bounds_test: bool,
+
     /// Whether DEPTH_CLAMP_NEAR is enabled.
     pub enabled_depth_clamp_near: bool,
 
@@ -63,6 +69,12 @@ pub struct GlState {
     /// Whether GL_SAMPLE_COVERAGE is enabled
     pub enabled_sample_coverage: bool,
 
+    /// Whether GL_SAMPLE_MASK is enabled
+    pub enabled_sample_mask: bool,
+
+    /// Whether GL_SAMPLE_SHADING is enabled
+    pub enabled_sample_shading: bool,
+
     /// Whether GL_SCISSOR_TEST is enabled
     pub enabled_scissor_test: bool,
 
@@ -169,6 +181,20 @@ pub struct GlState {
     pub blend_func: (gl::types::GLenum, gl::types::GLenum,
                      gl::types::GLenum, gl::types::GLenum),
 
+    /// Blending state of each draw buffer after a call to one of the indexed functions
+    /// (`glEnablei(GL_BLEND)`, `glBlendEquationSeparatei` and `glBlendFuncSeparatei`).
+    ///
+    /// Empty if the non-indexed functions were called last, in which case `enabled_blend`,
+    /// `blend_equation` and `blend_func` apply to all the draw buffers.
+    pub indexed_blend: SmallVec<[IndexedBlendState; 8]>,
+
+    /// The latest values passed to `glColorMaski` for each draw buffer.
+    ///
+    /// Empty if `glColorMask` was called last, in which case `color_mask` applies to all the
+    /// draw buffers.
+    pub indexed_color_mask: SmallVec<[(gl::types::GLboolean, gl::types::GLboolean,
+                                       gl::types::GLboolean, gl::types::GLboolean); 8]>,
+
     /// The latest value passed to `glBlendColor`.
     pub blend_color: (gl::types::GLclampf, gl::types::GLclampf,
                       gl::types::GLclampf, gl::types::GLclampf),
@@ -182,6 +208,12 @@ pub struct GlState {
     /// The latest values passed to `glDepthRange`.
     pub depth_range: (f32, f32),
 
+    /// The latest values passed to `glDepthBoundsEXT`.
+    pub depth_bounds: (f32, f32),
+
+    /// The latest values passed to `glClipControl`.
+    pub clip_control: (gl::types::GLenum, gl::types::GLenum),
+
     /// The latest values passed to `glStencilFuncSeparate` with face `GL_FRONT`.
     pub stencil_func_front: (gl::types::GLenum, gl::types::GLint, gl::types::GLuint),
 
@@ -208,6 +240,16 @@ pub struct GlState {
     pub scissor: Option<(gl::types::GLint, gl::types::GLint,
                          gl::types::GLsizei, gl::types::GLsizei)>,
 
+    /// The latest values passed to `glViewportArrayv`.
+    ///
+    /// Empty if `glViewport` was called last. When this is not empty, `viewport` is `None`.
+    pub indexed_viewports: SmallVec<[[gl::types::GLfloat; 4]; 16]>,
+
+    /// The latest values passed to `glScissorArrayv`.
+    ///
+    /// Empty if `glScissor` was called last. When this is not empty, `scissor` is `None`.
+    pub indexed_scissors: SmallVec<[[gl::types::GLint; 4]; 16]>,
+
     /// The latest value passed to `glLineWidth`.
     pub line_width: gl::types::GLfloat,
 
@@ -229,6 +271,15 @@ pub struct GlState {
     /// The latest value passed to `glProvokingVertex`.
     pub provoking_vertex: gl::types::GLenum,
 
+    /// The latest value passed to `glLogicOp`.
+    pub logic_op: gl::types::GLenum,
+
+    /// The latest value passed to `glSampleMaski` for the first mask word.
+    pub sample_mask: gl::types::GLbitfield,
+
+    /// The latest value passed to `glMinSampleShading`.
+    pub min_sample_shading: gl::types::GLfloat,
+
     /// The latest value passed to `glPixelStore` with `GL_UNPACK_ALIGNMENT`.
     pub pixel_store_unpack_alignment: gl::types::GLint,
 
@@ -267,6 +318,10 @@ pub struct GlState {
     /// Current query being used for GL_TIME_ELAPSED​.
     pub time_elapsed_query: gl::types::GLuint,
 
+    /// Current queries being used for the statistics of `GL_ARB_pipeline_statistics_query`,
+    /// in the order of `PipelineStatistic::ALL`.
+    pub pipeline_statistics_queries: [gl::types::GLuint; 11],
+
     /// Latest value passed to `glBeginConditionalRender​`.
     pub conditional_render: Option<(gl::types::GLuint, gl::types::GLenum)>,
 
@@ -365,6 +420,20 @@ pub struct IndexedBufferState {
     pub size: gl::types::GLsizeiptr,
 }
 
+/// Blending state of a single draw buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IndexedBlendState {
+    /// Whether GL_BLEND is enabled for this draw buffer.
+    pub enabled: bool,
+
+    /// The latest values passed to `glBlendEquationSeparatei`.
+    pub equation: (gl::types::GLenum, gl::types::GLenum),
+
+    /// The latest values passed to `glBlendFuncSeparatei`.
+    pub func: (gl::types::GLenum, gl::types::GLenum,
+               gl::types::GLenum, gl::types::GLenum),
+}
+
 /// Builds the `GlState` corresponding to a newly-created OpenGL context.
 impl Default for GlState {
     fn default() -> GlState {
@@ -378,10 +447,12 @@ impl Default for GlState {
             lost_context: false,
 
             enabled_blend: false,
+            enabled_color_logic_op: false,
             enabled_cull_face: false,
             enabled_debug_output: None,
             enabled_debug_output_synchronous: false,
             enabled_depth_test: false,
+            enabled_depth_bounds_test: false,
             enabled_depth_clamp_near: false,
             enabled_depth_clamp_far: false,
             enabled_dither: false,
@@ -393,6 +464,8 @@ impl Default for GlState {
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
             enabled_sample_coverage: false,
+            enabled_sample_mask: false,
+            enabled_sample_shading: false,
             enabled_scissor_test: false,
             enabled_stencil_test: false,
             enabled_line_smooth: false,
@@ -430,6 +503,8 @@ impl Default for GlState {
             depth_func: gl::LESS,
             depth_mask: true,
             depth_range: (0.0, 1.0),
+            depth_bounds: (0.0, 1.0),
+            clip_control: (gl::LOWER_LEFT, gl::NEGATIVE_ONE_TO_ONE),
             stencil_func_front: (gl::ALWAYS, 0, 0xffffffff),
             stencil_func_back: (gl::ALWAYS, 0, 0xffffffff),
             stencil_mask_front: 0xffffffff,
@@ -438,15 +513,22 @@ impl Default for GlState {
             stencil_op_back: (gl::KEEP, gl::KEEP, gl::KEEP),
             blend_equation: (gl::FUNC_ADD, gl::FUNC_ADD),
             blend_func: (gl::ONE, gl::ZERO, gl::ONE, gl::ZERO),
+            indexed_blend: SmallVec::new(),
+            indexed_color_mask: SmallVec::new(),
             blend_color: (0.0, 0.0, 0.0, 0.0),
             viewport: None,
             scissor: None,
+            indexed_viewports: SmallVec::new(),
+            indexed_scissors: SmallVec::new(),
             line_width: 1.0,
             point_size: 1.0,
             cull_face: gl::BACK,
             polygon_mode: gl::FILL,
             smooth: (gl::DONT_CARE, gl::DONT_CARE),
             provoking_vertex: gl::LAST_VERTEX_CONVENTION,
+            logic_op: gl::COPY,
+            sample_mask: 0xffffffff,
+            min_sample_shading: 0.0,
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
             clamp_color: gl::FIXED_ONLY,
@@ -459,6 +541,7 @@ impl Default for GlState {
             primitives_generated_query: 0,
             transform_feedback_primitives_written_query: 0,
             time_elapsed_query: 0,
+            pipeline_statistics_queries: [0; 11],
             conditional_render: None,
             transform_feedback_enabled: None,
             transform_feedback_paused: false,
@@ -4,11 +4,15 @@
 */
 
 use crate::backend::Facade;
+use crate::context::CommandContext;
 use crate::context::Context;
+use crate::CapabilitiesSource;
 use crate::ContextExt;
 use crate::version::Api;
 use crate::version::Version;
 use crate::gl;
+use std::error::Error;
+use std::fmt;
 use std::rc::Rc;
 
 pub use crate::context::DebugCallbackBehavior;
@@ -156,6 +160,14 @@ impl TimestampQuery {
         })
     }
 
+    /// Gives a label to the query, which is shown by debuggers such as RenderDoc.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        let mut ctxt = self.context.make_current();
+        unsafe { set_object_label(&mut ctxt, gl::QUERY, self.id, label) }
+    }
+
     /// Queries the counter to see if the timestamp is already available.
     ///
     /// It takes some time to retrieve the value, during which you can execute other
@@ -209,3 +221,171 @@ impl TimestampQuery {
         }
     }
 }
+
+/// Returns true if the backend supports giving labels to objects.
+///
+/// When this returns false, the `set_label` methods of the objects return an error. The label
+/// still appears in `Context::get_resources_report`.
+#[inline]
+pub fn is_object_label_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_capabilities().max_label_length.is_some()
+}
+
+/// Error that can happen when giving a label to an object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LabelNotSupportedError;
+
+impl fmt::Display for LabelNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Giving a label to this object is not supported by the backend")
+    }
+}
+
+impl Error for LabelNotSupportedError {}
+
+/// Calls `glObjectLabel` on an object. `identifier` is the namespace of the object, for
+/// example `GL_BUFFER`.
+///
+/// Labels that are too long for the backend are truncated.
+///
+/// *Safety*: the object must exist, which isn't the case of names that have been generated but
+/// never bound.
+pub(crate) unsafe fn set_object_label(ctxt: &mut CommandContext<'_>, identifier: gl::types::GLenum,
+                                      name: gl::types::GLuint, label: &str)
+                                      -> Result<(), LabelNotSupportedError>
+{
+    let max_length = match ctxt.capabilities.max_label_length {
+        Some(l) => l,
+        None => return Err(LabelNotSupportedError),
+    };
+
+    // the length must be strictly inferior to `GL_MAX_LABEL_LENGTH`
+    let mut length = label.len().min((max_length - 1).max(0) as usize);
+    while !label.is_char_boundary(length) {
+        length -= 1;
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+       (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+    {
+        ctxt.gl.ObjectLabel(identifier, name, length as gl::types::GLsizei,
+                            label.as_ptr() as *const _);
+    } else if ctxt.extensions.gl_khr_debug {
+        ctxt.gl.ObjectLabelKHR(identifier, name, length as gl::types::GLsizei,
+                               label.as_ptr() as *const _);
+    } else {
+        unreachable!();
+    }
+
+    Ok(())
+}
+
+/// Groups all the commands that are executed while it is alive.
+///
+/// Debuggers such as RenderDoc or apitrace show the commands of a group nested under its name,
+/// which makes it easier to find a specific pass in a frame. Groups are global to the context,
+/// so they can be used around draws on any surface, and they can be nested.
+///
+/// The group is opened when the `DebugGroup` is created and closed when it is destroyed. Groups
+/// must be destroyed in the reverse order of their creation, which is always the case for local
+/// variables.
+///
+/// This uses `GL_KHR_debug` if it is supported, or `GL_EXT_debug_marker` otherwise. If neither
+/// is supported, creating a group does nothing.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # fn example(display: glium::Display) {
+/// let mut target = display.draw();
+///
+/// {
+///     let _group = glium::debug::DebugGroup::new(&display, "background");
+///     target.clear_color(0.0, 0.0, 0.0, 1.0);
+///     // draw the background here
+/// }
+///
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct DebugGroup {
+    context: Rc<Context>,
+    kind: Option<DebugGroupKind>,
+}
+
+/// Functions that are used to push and pop a debug group.
+#[derive(Copy, Clone)]
+enum DebugGroupKind {
+    Core,
+    Khr,
+    Ext,
+}
+
+impl DebugGroup {
+    /// Opens a new debug group with the given name.
+    pub fn new<F: ?Sized>(facade: &F, name: &str) -> DebugGroup where F: Facade {
+        let ctxt = facade.get_context().make_current();
+
+        let kind = if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                      ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                      (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+        {
+            unsafe {
+                ctxt.gl.PushDebugGroup(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                       name.len() as gl::types::GLsizei,
+                                       name.as_ptr() as *const _);
+            }
+            Some(DebugGroupKind::Core)
+
+        } else if ctxt.extensions.gl_khr_debug {
+            unsafe {
+                ctxt.gl.PushDebugGroupKHR(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                          name.len() as gl::types::GLsizei,
+                                          name.as_ptr() as *const _);
+            }
+            Some(DebugGroupKind::Khr)
+
+        } else if ctxt.extensions.gl_ext_debug_marker {
+            unsafe {
+                ctxt.gl.PushGroupMarkerEXT(name.len() as gl::types::GLsizei,
+                                           name.as_ptr() as *const _);
+            }
+            Some(DebugGroupKind::Ext)
+
+        } else {
+            None
+        };
+
+        DebugGroup {
+            context: facade.get_context().clone(),
+            kind,
+        }
+    }
+
+    /// Returns false if the backend doesn't support debug groups, in which case this group
+    /// does nothing.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.kind.is_some()
+    }
+}
+
+impl Drop for DebugGroup {
+    fn drop(&mut self) {
+        let kind = match self.kind {
+            Some(k) => k,
+            None => return,
+        };
+
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            match kind {
+                DebugGroupKind::Core => ctxt.gl.PopDebugGroup(),
+                DebugGroupKind::Khr => ctxt.gl.PopDebugGroupKHR(),
+                DebugGroupKind::Ext => ctxt.gl.PopGroupMarkerEXT(),
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use crate::context::CommandContext;
+use crate::context::IndexedBlendState;
 use crate::version::Api;
 use crate::version::Version;
 
 use crate::DrawError;
 use crate::gl;
 
+use smallvec::SmallVec;
+use std::iter;
+
 /// Blend effect that the GPU will use for blending.
 ///
 /// Blending happens at the end of the rendering process, when the GPU wants to write the
@@ -223,121 +227,259 @@ impl LinearBlendingFactor {
     }
 }
 
-pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(), DrawError> {
-    #[inline(always)]
-    fn blend_eq(ctxt: &mut CommandContext<'_>, blending_function: BlendingFunction)
-                -> Result<gl::types::GLenum, DrawError>
-    {
-        match blending_function {
-            BlendingFunction::AlwaysReplace |
-            BlendingFunction::Addition { .. } => Ok(gl::FUNC_ADD),
-            BlendingFunction::Subtraction { .. } => Ok(gl::FUNC_SUBTRACT),
-            BlendingFunction::ReverseSubtraction { .. } => Ok(gl::FUNC_REVERSE_SUBTRACT),
-
-            BlendingFunction::Min => {
-                if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
-                   !ctxt.extensions.gl_ext_blend_minmax
-                {
-                    Err(DrawError::BlendingParameterNotSupported)
-                } else {
-                    Ok(gl::MIN)
-                }
-            },
+#[inline(always)]
+fn blend_eq(ctxt: &mut CommandContext<'_>, blending_function: BlendingFunction)
+            -> Result<gl::types::GLenum, DrawError>
+{
+    match blending_function {
+        BlendingFunction::AlwaysReplace |
+        BlendingFunction::Addition { .. } => Ok(gl::FUNC_ADD),
+        BlendingFunction::Subtraction { .. } => Ok(gl::FUNC_SUBTRACT),
+        BlendingFunction::ReverseSubtraction { .. } => Ok(gl::FUNC_REVERSE_SUBTRACT),
+
+        BlendingFunction::Min => {
+            if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
+               !ctxt.extensions.gl_ext_blend_minmax
+            {
+                Err(DrawError::BlendingParameterNotSupported)
+            } else {
+                Ok(gl::MIN)
+            }
+        },
+
+        BlendingFunction::Max => {
+            if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
+               !ctxt.extensions.gl_ext_blend_minmax
+            {
+                Err(DrawError::BlendingParameterNotSupported)
+            } else {
+                Ok(gl::MAX)
+            }
+        },
+    }
+}
 
-            BlendingFunction::Max => {
-                if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
-                   !ctxt.extensions.gl_ext_blend_minmax
-                {
-                    Err(DrawError::BlendingParameterNotSupported)
-                } else {
-                    Ok(gl::MAX)
-                }
-            },
-        }
+#[inline(always)]
+fn blending_factors(blending_function: BlendingFunction)
+                    -> Option<(LinearBlendingFactor, LinearBlendingFactor)>
+{
+    match blending_function {
+        BlendingFunction::AlwaysReplace |
+        BlendingFunction::Min |
+        BlendingFunction::Max => None,
+        BlendingFunction::Addition { source, destination } =>
+            Some((source, destination)),
+        BlendingFunction::Subtraction { source, destination } =>
+            Some((source, destination)),
+        BlendingFunction::ReverseSubtraction { source, destination } =>
+            Some((source, destination)),
     }
+}
 
-    #[inline(always)]
-    fn blending_factors(blending_function: BlendingFunction)
-                        -> Option<(LinearBlendingFactor, LinearBlendingFactor)>
-    {
-        match blending_function {
-            BlendingFunction::AlwaysReplace |
-            BlendingFunction::Min |
-            BlendingFunction::Max => None,
-            BlendingFunction::Addition { source, destination } =>
-                Some((source, destination)),
-            BlendingFunction::Subtraction { source, destination } =>
-                Some((source, destination)),
-            BlendingFunction::ReverseSubtraction { source, destination } =>
-                Some((source, destination)),
-        }
+#[inline]
+fn uses_constant_value(factor: LinearBlendingFactor) -> bool {
+    match factor {
+        LinearBlendingFactor::ConstantColor |
+        LinearBlendingFactor::OneMinusConstantColor |
+        LinearBlendingFactor::ConstantAlpha |
+        LinearBlendingFactor::OneMinusConstantAlpha => true,
+        _ => false,
     }
+}
+
+/// Values to pass to OpenGL in order to apply a `Blend`.
+struct BlendValues {
+    equation: (gl::types::GLenum, gl::types::GLenum),
+    func: (gl::types::GLenum, gl::types::GLenum, gl::types::GLenum, gl::types::GLenum),
+    uses_constant_value: bool,
+}
 
+/// Computes the values that correspond to `blend`. Returns `None` if blending must be disabled.
+fn blend_values(ctxt: &mut CommandContext<'_>, blend: &Blend)
+                -> Result<Option<BlendValues>, DrawError>
+{
     if let (BlendingFunction::AlwaysReplace, BlendingFunction::AlwaysReplace) =
            (blend.color, blend.alpha)
     {
         // Both color and alpha always replace. This equals no blending.
+        return Ok(None);
+    }
+
+    let equation = (blend_eq(ctxt, blend.color)?, blend_eq(ctxt, blend.alpha)?);
+
+    // Map to dummy factors if the blending equation does not use the factors.
+    let (color_factor_src, color_factor_dst) = blending_factors(blend.color)
+        .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
+    let (alpha_factor_src, alpha_factor_dst) = blending_factors(blend.alpha)
+        .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
+
+    let uses_constant_value = uses_constant_value(color_factor_src) ||
+                              uses_constant_value(color_factor_dst) ||
+                              uses_constant_value(alpha_factor_src) ||
+                              uses_constant_value(alpha_factor_dst);
+
+    Ok(Some(BlendValues {
+        equation,
+        func: (color_factor_src.to_glenum(), color_factor_dst.to_glenum(),
+               alpha_factor_src.to_glenum(), alpha_factor_dst.to_glenum()),
+        uses_constant_value,
+    }))
+}
+
+fn sync_blend_color(ctxt: &mut CommandContext<'_>, color: (f32, f32, f32, f32)) {
+    if ctxt.state.blend_color != color {
+        let (r, g, b, a) = color;
+        unsafe { ctxt.gl.BlendColor(r, g, b, a); }
+        ctxt.state.blend_color = color;
+    }
+}
+
+/// If the indexed blending functions have been used, applies the non-indexed state back to
+/// all the draw buffers.
+fn reset_indexed_blending(ctxt: &mut CommandContext<'_>) {
+    if ctxt.state.indexed_blend.is_empty() {
+        return;
+    }
+
+    unsafe {
         if ctxt.state.enabled_blend {
-            unsafe { ctxt.gl.Disable(gl::BLEND); }
-            ctxt.state.enabled_blend = false;
+            ctxt.gl.Enable(gl::BLEND);
+        } else {
+            ctxt.gl.Disable(gl::BLEND);
         }
 
-    } else {
-        if !ctxt.state.enabled_blend {
-            unsafe { ctxt.gl.Enable(gl::BLEND); }
-            ctxt.state.enabled_blend = true;
-        }
+        let (color_eq, alpha_eq) = ctxt.state.blend_equation;
+        ctxt.gl.BlendEquationSeparate(color_eq, alpha_eq);
 
-        let (color_eq, alpha_eq) = (blend_eq(ctxt, blend.color)?,
-                                    blend_eq(ctxt, blend.alpha)?);
-        if ctxt.state.blend_equation != (color_eq, alpha_eq) {
-            unsafe { ctxt.gl.BlendEquationSeparate(color_eq, alpha_eq); }
-            ctxt.state.blend_equation = (color_eq, alpha_eq);
-        }
+        let (color_src, color_dst, alpha_src, alpha_dst) = ctxt.state.blend_func;
+        ctxt.gl.BlendFuncSeparate(color_src, color_dst, alpha_src, alpha_dst);
+    }
 
-        // Map to dummy factors if the blending equation does not use the factors.
-        let (color_factor_src, color_factor_dst) = blending_factors(blend.color)
-            .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
-        let (alpha_factor_src, alpha_factor_dst) = blending_factors(blend.alpha)
-            .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
-
-        // Updating the blending color if necessary.
-        if (color_factor_src == LinearBlendingFactor::ConstantColor ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_dst == LinearBlendingFactor::ConstantColor ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha) && ctxt.state.blend_color != blend.constant_value {
-            let (r, g, b, a) = blend.constant_value;
-            unsafe { ctxt.gl.BlendColor(r, g, b, a); }
-            ctxt.state.blend_color = blend.constant_value;
-        }
+    ctxt.state.indexed_blend.clear();
+}
+
+pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(), DrawError> {
+    reset_indexed_blending(ctxt);
+
+    match blend_values(ctxt, &blend)? {
+        None => {
+            if ctxt.state.enabled_blend {
+                unsafe { ctxt.gl.Disable(gl::BLEND); }
+                ctxt.state.enabled_blend = false;
+            }
+        },
+
+        Some(values) => {
+            if !ctxt.state.enabled_blend {
+                unsafe { ctxt.gl.Enable(gl::BLEND); }
+                ctxt.state.enabled_blend = true;
+            }
+
+            if ctxt.state.blend_equation != values.equation {
+                let (color_eq, alpha_eq) = values.equation;
+                unsafe { ctxt.gl.BlendEquationSeparate(color_eq, alpha_eq); }
+                ctxt.state.blend_equation = values.equation;
+            }
+
+            // Updating the blending color if necessary.
+            if values.uses_constant_value {
+                sync_blend_color(ctxt, blend.constant_value);
+            }
+
+            // Updating the blending function if necessary.
+            if ctxt.state.blend_func != values.func {
+                let (color_src, color_dst, alpha_src, alpha_dst) = values.func;
+                unsafe { ctxt.gl.BlendFuncSeparate(color_src, color_dst, alpha_src, alpha_dst); }
+                ctxt.state.blend_func = values.func;
+            }
+        },
+    }
 
-        // Updating the blending function if necessary.
-        let color_factor_src = color_factor_src.to_glenum();
-        let color_factor_dst = color_factor_dst.to_glenum();
-        let alpha_factor_src = alpha_factor_src.to_glenum();
-        let alpha_factor_dst = alpha_factor_dst.to_glenum();
-        if ctxt.state.blend_func != (color_factor_src, color_factor_dst,
-                                     alpha_factor_src, alpha_factor_dst)
-        {
-            unsafe {
-                ctxt.gl.BlendFuncSeparate(color_factor_src, color_factor_dst,
-                                          alpha_factor_src, alpha_factor_dst);
+    Ok(())
+}
+
+/// Applies `draw_buffers_blend[i]` to the draw buffer `i`, and `blend` to the draw buffers
+/// that are not in the list.
+pub fn sync_indexed_blending(ctxt: &mut CommandContext<'_>, blend: &Blend,
+                             draw_buffers_blend: &[Blend]) -> Result<(), DrawError>
+{
+    if !(ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2)) {
+        return Err(DrawError::IndexedBlendingNotSupported);
+    }
+
+    let num_draw_buffers = ctxt.capabilities.max_draw_buffers as usize;
+    if draw_buffers_blend.len() > num_draw_buffers {
+        return Err(DrawError::DrawBufferIndexOutOfBounds);
+    }
+
+    // the constant color is shared by all the draw buffers, so all the blends that use it
+    // must agree on its value
+    let mut constant_value = None;
+    let mut values: SmallVec<[Option<BlendValues>; 8]> = SmallVec::new();
+    for index in 0 .. num_draw_buffers {
+        let blend = draw_buffers_blend.get(index).unwrap_or(blend);
+        let buffer_values = blend_values(ctxt, blend)?;
+
+        if let Some(BlendValues { uses_constant_value: true, .. }) = buffer_values {
+            match constant_value {
+                None => constant_value = Some(blend.constant_value),
+                Some(value) if value != blend.constant_value => {
+                    return Err(DrawError::BlendingParameterNotSupported);
+                },
+                Some(_) => (),
             }
+        }
 
-            ctxt.state.blend_func = (color_factor_src, color_factor_dst,
-                                     alpha_factor_src, alpha_factor_dst);
+        values.push(buffer_values);
+    }
+
+    if ctxt.state.indexed_blend.is_empty() {
+        let current = IndexedBlendState {
+            enabled: ctxt.state.enabled_blend,
+            equation: ctxt.state.blend_equation,
+            func: ctxt.state.blend_func,
+        };
+
+        ctxt.state.indexed_blend.extend(iter::repeat(current).take(num_draw_buffers));
+    }
+
+    if let Some(constant_value) = constant_value {
+        sync_blend_color(ctxt, constant_value);
+    }
+
+    for (index, values) in values.into_iter().enumerate() {
+        let current = ctxt.state.indexed_blend[index];
+        let buf = index as gl::types::GLuint;
+
+        match values {
+            None => {
+                if current.enabled {
+                    unsafe { ctxt.gl.Disablei(gl::BLEND, buf); }
+                    ctxt.state.indexed_blend[index].enabled = false;
+                }
+            },
+
+            Some(values) => {
+                if !current.enabled {
+                    unsafe { ctxt.gl.Enablei(gl::BLEND, buf); }
+                    ctxt.state.indexed_blend[index].enabled = true;
+                }
+
+                if current.equation != values.equation {
+                    let (color_eq, alpha_eq) = values.equation;
+                    unsafe { ctxt.gl.BlendEquationSeparatei(buf, color_eq, alpha_eq); }
+                    ctxt.state.indexed_blend[index].equation = values.equation;
+                }
+
+                if current.func != values.func {
+                    let (color_src, color_dst, alpha_src, alpha_dst) = values.func;
+                    unsafe {
+                        ctxt.gl.BlendFuncSeparatei(buf, color_src, color_dst,
+                                                   alpha_src, alpha_dst);
+                    }
+                    ctxt.state.indexed_blend[index].func = values.func;
+                }
+            },
         }
     }
 
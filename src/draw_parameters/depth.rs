@@ -37,12 +37,41 @@ pub struct Depth {
     /// The first value of the tuple must be the "near" value, where `-1.0` will be mapped.
     /// The second value must be the "far" value, where `1.0` will be mapped.
     /// It is possible for the "near" value to be greater than the "far" value.
+    ///
+    /// If `clip_depth` is `ZeroToOne`, then `0.0` is mapped to the "near" value instead
+    /// of `-1.0`.
     pub range: (f32, f32),
 
     /// Sets whether the depth values of samples should be clamped to `0.0` and `1.0`.
     ///
     /// The default value is `NoClamp`.
     pub clamp: DepthClamp,
+
+    /// The corner of the surface that corresponds to the `(-1, -1)` coordinates.
+    ///
+    /// The default value is `LowerLeft`. Any other value can trigger a
+    /// `ClipControlNotSupported` error.
+    pub clip_origin: ClipOrigin,
+
+    /// The range of Z coordinates that are kept after clipping.
+    ///
+    /// The default value is `NegativeOneToOne`. Any other value can trigger a
+    /// `ClipControlNotSupported` error.
+    ///
+    /// Using `ZeroToOne` together with a depth buffer cleared to `0.0` and a depth test of
+    /// `IfMore` is known as "reverse Z", and greatly improves the precision of floating-point
+    /// depth buffers.
+    pub clip_depth: ClipDepthMode,
+
+    /// If set, samples are discarded if the value *already in the depth buffer* at their
+    /// location is outside of this range. The default value is `None`.
+    ///
+    /// The two values must be between `0.0` and `1.0` and the first one must be less than or
+    /// equal to the second one, otherwise an `InvalidDepthBounds` error is returned.
+    ///
+    /// Requires `GL_EXT_depth_bounds_test`, otherwise a `DepthBoundsNotSupported` error is
+    /// returned.
+    pub bounds: Option<(f32, f32)>,
 }
 
 impl Default for Depth {
@@ -53,6 +82,9 @@ impl Default for Depth {
             write: false,
             range: (0.0, 1.0),
             clamp: DepthClamp::NoClamp,
+            clip_origin: ClipOrigin::LowerLeft,
+            clip_depth: ClipDepthMode::NegativeOneToOne,
+            bounds: None,
         }
     }
 }
@@ -163,6 +195,55 @@ pub enum DepthClamp {
     ClampFar,
 }
 
+/// Specifies the corner of the surface that corresponds to the `(-1, -1)` coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipOrigin {
+    /// `(-1, -1)` corresponds to the lower-left corner of the surface.
+    ///
+    /// This is the default value and is supported everywhere.
+    LowerLeft,
+
+    /// `(-1, -1)` corresponds to the upper-left corner of the surface. Note that this also
+    /// inverts the winding of the primitives.
+    ///
+    /// Requires OpenGL 4.5 or `GL_ARB_clip_control`.
+    UpperLeft,
+}
+
+impl ClipOrigin {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ClipOrigin::LowerLeft => gl::LOWER_LEFT,
+            ClipOrigin::UpperLeft => gl::UPPER_LEFT,
+        }
+    }
+}
+
+/// Specifies the range of Z coordinates that are kept after clipping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipDepthMode {
+    /// Z coordinates between `-1.0` and `1.0` are kept.
+    ///
+    /// This is the default value and is supported everywhere.
+    NegativeOneToOne,
+
+    /// Z coordinates between `0.0` and `1.0` are kept.
+    ///
+    /// Requires OpenGL 4.5 or `GL_ARB_clip_control`.
+    ZeroToOne,
+}
+
+impl ClipDepthMode {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ClipDepthMode::NegativeOneToOne => gl::NEGATIVE_ONE_TO_ONE,
+            ClipDepthMode::ZeroToOne => gl::ZERO_TO_ONE,
+        }
+    }
+}
+
 pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), DrawError> {
     // depth clamp
     {
@@ -258,6 +339,42 @@ pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), Dr
         ctxt.state.depth_range = depth.range;
     }
 
+    // clip control
+    let clip_control = (depth.clip_origin.to_glenum(), depth.clip_depth.to_glenum());
+    if clip_control != ctxt.state.clip_control {
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_clip_control {
+            unsafe { ctxt.gl.ClipControl(clip_control.0, clip_control.1); }
+            ctxt.state.clip_control = clip_control;
+        } else {
+            return Err(DrawError::ClipControlNotSupported);
+        }
+    }
+
+    // depth bounds
+    if let Some((min, max)) = depth.bounds {
+        if !(0.0 <= min && min <= max && max <= 1.0) {
+            return Err(DrawError::InvalidDepthBounds);
+        }
+
+        if !ctxt.extensions.gl_ext_depth_bounds_test {
+            return Err(DrawError::DepthBoundsNotSupported);
+        }
+
+        if !ctxt.state.enabled_depth_bounds_test {
+            unsafe { ctxt.gl.Enable(gl::DEPTH_BOUNDS_TEST_EXT) };
+            ctxt.state.enabled_depth_bounds_test = true;
+        }
+
+        if ctxt.state.depth_bounds != (min, max) {
+            unsafe { ctxt.gl.DepthBoundsEXT(min as f64, max as f64); }
+            ctxt.state.depth_bounds = (min, max);
+        }
+
+    } else if ctxt.state.enabled_depth_bounds_test {
+        unsafe { ctxt.gl.Disable(gl::DEPTH_BOUNDS_TEST_EXT) };
+        ctxt.state.enabled_depth_bounds_test = false;
+    }
+
     if depth.test == DepthTest::Overwrite && !depth.write {
         // simply disabling GL_DEPTH_TEST
         if ctxt.state.enabled_depth_test {
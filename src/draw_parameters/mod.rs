@@ -79,13 +79,22 @@ use crate::Rect;
 use crate::ToGlEnum;
 use crate::vertex::TransformFeedbackSession;
 
+use smallvec::SmallVec;
+use std::iter;
 use std::ops::Range;
 
 pub use self::blend::{Blend, BlendingFunction, LinearBlendingFactor};
-pub use self::depth::{Depth, DepthTest, DepthClamp};
+pub use self::depth::{Depth, DepthTest, DepthClamp, ClipOrigin, ClipDepthMode};
 pub use self::query::{QueryCreationError};
 pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
+pub use self::query::{VerticesSubmittedQuery, PrimitivesSubmittedQuery};
+pub use self::query::{VertexShaderInvocationsQuery, TessControlShaderPatchesQuery};
+pub use self::query::{TessEvaluationShaderInvocationsQuery, GeometryShaderInvocationsQuery};
+pub use self::query::{GeometryShaderPrimitivesEmittedQuery, FragmentShaderInvocationsQuery};
+pub use self::query::{ComputeShaderInvocationsQuery, ClippingInputPrimitivesQuery};
+pub use self::query::{ClippingOutputPrimitivesQuery, PipelineStatisticsQueryParam};
+pub(crate) use self::query::sync_pipeline_statistics_queries;
 pub use self::stencil::{StencilTest, StencilOperation, Stencil};
 
 mod blend;
@@ -230,6 +239,71 @@ pub enum ProvokingVertex {
     FirstVertex,
 }
 
+/// Logical operation to apply between the color of a fragment and the color that is already
+/// in the framebuffer.
+///
+/// In the descriptions below, `s` is the color of the fragment and `d` is the color in the
+/// framebuffer. The operations are applied bitwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LogicOp {
+    /// `0`
+    Clear,
+    /// `s & d`
+    And,
+    /// `s & !d`
+    AndReverse,
+    /// `s`
+    Copy,
+    /// `!s & d`
+    AndInverted,
+    /// `d`
+    Noop,
+    /// `s ^ d`
+    Xor,
+    /// `s | d`
+    Or,
+    /// `!(s | d)`
+    Nor,
+    /// `!(s ^ d)`
+    Equiv,
+    /// `!d`
+    Invert,
+    /// `s | !d`
+    OrReverse,
+    /// `!s`
+    CopyInverted,
+    /// `!s | d`
+    OrInverted,
+    /// `!(s & d)`
+    Nand,
+    /// All bits set to `1`.
+    Set,
+}
+
+impl ToGlEnum for LogicOp {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            LogicOp::Clear => gl::CLEAR,
+            LogicOp::And => gl::AND,
+            LogicOp::AndReverse => gl::AND_REVERSE,
+            LogicOp::Copy => gl::COPY,
+            LogicOp::AndInverted => gl::AND_INVERTED,
+            LogicOp::Noop => gl::NOOP,
+            LogicOp::Xor => gl::XOR,
+            LogicOp::Or => gl::OR,
+            LogicOp::Nor => gl::NOR,
+            LogicOp::Equiv => gl::EQUIV,
+            LogicOp::Invert => gl::INVERT,
+            LogicOp::OrReverse => gl::OR_REVERSE,
+            LogicOp::CopyInverted => gl::COPY_INVERTED,
+            LogicOp::OrInverted => gl::OR_INVERTED,
+            LogicOp::Nand => gl::NAND,
+            LogicOp::Set => gl::SET,
+        }
+    }
+}
+
 /// Represents the parameters to use when drawing.
 ///
 /// Example:
@@ -267,6 +341,34 @@ pub struct DrawParameters<'a> {
     /// is `(true, true, true, true)`.
     pub color_mask: (bool, bool, bool, bool),
 
+    /// Blending of each individual draw buffer.
+    ///
+    /// The element `i` of this list replaces `blend` for the draw buffer `i`, which corresponds
+    /// to the `i`th attachment of a `MultiOutputFrameBuffer`. Draw buffers that are not in the
+    /// list use `blend`. The default value is an empty list, which means that `blend` applies
+    /// to all the draw buffers.
+    ///
+    /// The constant color is shared by all the draw buffers. If several blends use it, they
+    /// must have the same `constant_value` or a `BlendingParameterNotSupported` error is
+    /// returned.
+    ///
+    /// ## Compatibility
+    ///
+    /// Requires OpenGL 4.0 or OpenGL ES 3.2 if the list is not empty. The list can't contain
+    /// more elements than the maximum number of draw buffers.
+    pub draw_buffers_blend: &'a [Blend],
+
+    /// Color mask of each individual draw buffer.
+    ///
+    /// The element `i` of this list replaces `color_mask` for the draw buffer `i`. Draw buffers
+    /// that are not in the list use `color_mask`. The default value is an empty list.
+    ///
+    /// ## Compatibility
+    ///
+    /// Requires OpenGL 3.0 or OpenGL ES 3.2 if the list is not empty. The list can't contain
+    /// more elements than the maximum number of draw buffers.
+    pub draw_buffers_color_mask: &'a [(bool, bool, bool, bool)],
+
     /// Width in pixels of the lines to draw when drawing lines.
     ///
     /// `None` means "don't care". Use this when you don't draw lines.
@@ -326,6 +428,31 @@ pub struct DrawParameters<'a> {
     /// not the scissor box.
     pub scissor: Option<Rect>,
 
+    /// List of viewports to use when drawing. The default value is an empty list.
+    ///
+    /// If the list is not empty, `viewport` is ignored and each primitive uses the viewport
+    /// whose index is written to `gl_ViewportIndex` by the geometry shader, or the first one if
+    /// there is no geometry shader. Viewports whose index is not in the list cover the whole
+    /// surface.
+    ///
+    /// ## Compatibility
+    ///
+    /// Requires OpenGL 4.1 or `GL_ARB_viewport_array` if the list is not empty, otherwise a
+    /// `ViewportArrayNotSupported` error is returned. The list can't contain more elements than
+    /// the maximum number of viewports.
+    pub viewports: &'a [Rect],
+
+    /// List of scissor boxes to use when drawing. The default value is an empty list.
+    ///
+    /// If the list is not empty, `scissor` is ignored and each primitive is clipped by the
+    /// scissor box whose index is written to `gl_ViewportIndex` by the geometry shader.
+    /// Scissor boxes whose index is not in the list cover the whole surface.
+    ///
+    /// ## Compatibility
+    ///
+    /// Same as `viewports`.
+    pub scissors: &'a [Rect],
+
     /// If `false`, the pipeline will stop after the primitives generation stage. The default
     /// value is `true`.
     ///
@@ -353,6 +480,13 @@ pub struct DrawParameters<'a> {
     pub transform_feedback_primitives_written_query:
                                     Option<&'a TransformFeedbackPrimitivesWrittenQuery>,
 
+    /// Pipeline statistics queries to which the statistics of the draw command are added,
+    /// for example the number of times the fragment shader is invoked. Only one query per
+    /// statistic can be used, otherwise a `WrongQueryOperation` error is returned.
+    ///
+    /// The default value is an empty list.
+    pub pipeline_statistics_queries: &'a [PipelineStatisticsQueryParam<'a>],
+
     /// If set, the commands will only be executed if the specified query contains `true` or
     /// a number different than 0.
     pub condition: Option<ConditionalRendering<'a>>,
@@ -399,6 +533,33 @@ pub struct DrawParameters<'a> {
     /// If enabled, shifts the depth value of towards of away from the camera. This is useful for
     /// drawing decals and wireframes, for example.
     pub polygon_offset: PolygonOffset,
+
+    /// If set, the color of each fragment is combined with the color already in the framebuffer
+    /// with this logical operation. The default value is `None`.
+    ///
+    /// Logical operations replace blending and only apply to integer and normalized integer
+    /// color buffers.
+    ///
+    /// OpenGL ES doesn't support logical operations. If the backend doesn't support them, a
+    /// `LogicOpNotSupported` error is returned.
+    pub logic_op: Option<LogicOp>,
+
+    /// If set, the bit `i` of the mask indicates whether the sample `i` of each pixel can be
+    /// written. The default value is `None`, which means that all the samples can be written.
+    ///
+    /// Requires OpenGL 3.2, OpenGL ES 3.1 or `GL_ARB_texture_multisample`. If the backend
+    /// doesn't support it, a `SampleMaskNotSupported` error is returned.
+    pub sample_mask: Option<u32>,
+
+    /// If set, the fragment shader is executed for at least this fraction of the samples of
+    /// each pixel, instead of once per pixel. The value is clamped between `0.0` and `1.0`.
+    /// The default value is `None`.
+    ///
+    /// This only has an effect when drawing to a multisampled framebuffer.
+    ///
+    /// Requires OpenGL 4.0 or OpenGL ES 3.2. If the backend doesn't support it, a
+    /// `SampleShadingNotSupported` error is returned.
+    pub min_sample_shading: Option<f32>,
 }
 
 /// Condition whether to render or not.
@@ -473,6 +634,8 @@ impl<'a> Default for DrawParameters<'a> {
             stencil: Default::default(),
             blend: Default::default(),
             color_mask: (true, true, true, true),
+            draw_buffers_blend: &[],
+            draw_buffers_color_mask: &[],
             line_width: None,
             point_size: None,
             backface_culling: BackfaceCullingMode::CullingDisabled,
@@ -482,11 +645,14 @@ impl<'a> Default for DrawParameters<'a> {
             dithering: true,
             viewport: None,
             scissor: None,
+            viewports: &[],
+            scissors: &[],
             draw_primitives: true,
             samples_passed_query: None,
             time_elapsed_query: None,
             primitives_generated_query: None,
             transform_feedback_primitives_written_query: None,
+            pipeline_statistics_queries: &[],
             condition: None,
             transform_feedback: None,
             smooth: None,
@@ -494,6 +660,9 @@ impl<'a> Default for DrawParameters<'a> {
             primitive_bounding_box: (-1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0),
             primitive_restart_index: false,
             polygon_offset: Default::default(),
+            logic_op: None,
+            sample_mask: None,
+            min_sample_shading: None,
         }
     }
 }
@@ -521,38 +690,76 @@ pub fn sync(ctxt: &mut context::CommandContext<'_>, draw_parameters: &DrawParame
 {
     depth::sync_depth(ctxt, &draw_parameters.depth)?;
     stencil::sync_stencil(ctxt, &draw_parameters.stencil);
-    blend::sync_blending(ctxt, draw_parameters.blend)?;
-    sync_color_mask(ctxt, draw_parameters.color_mask);
+    if draw_parameters.draw_buffers_blend.is_empty() {
+        blend::sync_blending(ctxt, draw_parameters.blend)?;
+    } else {
+        blend::sync_indexed_blending(ctxt, &draw_parameters.blend,
+                                     draw_parameters.draw_buffers_blend)?;
+    }
+    if draw_parameters.draw_buffers_color_mask.is_empty() {
+        sync_color_mask(ctxt, draw_parameters.color_mask);
+    } else {
+        sync_indexed_color_mask(ctxt, draw_parameters.color_mask,
+                                draw_parameters.draw_buffers_color_mask)?;
+    }
     sync_line_width(ctxt, draw_parameters.line_width);
     sync_point_size(ctxt, draw_parameters.point_size);
     sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
     sync_clip_planes_bitmask(ctxt, draw_parameters.clip_planes_bitmask)?;
     sync_multisampling(ctxt, draw_parameters.multisampling);
     sync_dithering(ctxt, draw_parameters.dithering);
-    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.scissor,
-                          dimensions);
+    sync_viewport_scissor(ctxt, draw_parameters, dimensions)?;
     sync_rasterizer_discard(ctxt, draw_parameters.draw_primitives)?;
     sync_queries(ctxt, draw_parameters.samples_passed_query,
                       draw_parameters.time_elapsed_query,
                       draw_parameters.primitives_generated_query,
                       draw_parameters.transform_feedback_primitives_written_query)?;
+    sync_pipeline_statistics_queries(ctxt, draw_parameters.pipeline_statistics_queries)?;
     sync_conditional_render(ctxt, draw_parameters.condition);
     sync_smooth(ctxt, draw_parameters.smooth, primitives_types)?;
     sync_provoking_vertex(ctxt, draw_parameters.provoking_vertex)?;
     sync_primitive_bounding_box(ctxt, &draw_parameters.primitive_bounding_box);
     sync_primitive_restart_index(ctxt, draw_parameters.primitive_restart_index)?;
     sync_polygon_offset(ctxt, draw_parameters.polygon_offset);
+    sync_logic_op(ctxt, draw_parameters.logic_op)?;
+    sync_sample_mask(ctxt, draw_parameters.sample_mask)?;
+    sync_min_sample_shading(ctxt, draw_parameters.min_sample_shading)?;
 
     Ok(())
 }
 
-fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bool, bool)) {
-    let mask = (
+#[inline]
+fn color_mask_to_gl(mask: (bool, bool, bool, bool))
+                    -> (gl::types::GLboolean, gl::types::GLboolean,
+                        gl::types::GLboolean, gl::types::GLboolean)
+{
+    (
         if mask.0 { 1 } else { 0 },
         if mask.1 { 1 } else { 0 },
         if mask.2 { 1 } else { 0 },
         if mask.3 { 1 } else { 0 },
-    );
+    )
+}
+
+/// If `glColorMaski` has been used, applies the non-indexed color mask back to all the
+/// draw buffers.
+pub(crate) fn reset_indexed_color_mask(ctxt: &mut context::CommandContext<'_>) {
+    if ctxt.state.indexed_color_mask.is_empty() {
+        return;
+    }
+
+    let mask = ctxt.state.color_mask;
+    unsafe {
+        ctxt.gl.ColorMask(mask.0, mask.1, mask.2, mask.3);
+    }
+
+    ctxt.state.indexed_color_mask.clear();
+}
+
+fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bool, bool)) {
+    reset_indexed_color_mask(ctxt);
+
+    let mask = color_mask_to_gl(mask);
 
     if ctxt.state.color_mask != mask {
         unsafe {
@@ -563,6 +770,39 @@ fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bo
     }
 }
 
+fn sync_indexed_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bool, bool),
+                           draw_buffers_mask: &[(bool, bool, bool, bool)])
+                           -> Result<(), DrawError>
+{
+    if !(ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2)) {
+        return Err(DrawError::IndexedBlendingNotSupported);
+    }
+
+    let num_draw_buffers = ctxt.capabilities.max_draw_buffers as usize;
+    if draw_buffers_mask.len() > num_draw_buffers {
+        return Err(DrawError::DrawBufferIndexOutOfBounds);
+    }
+
+    if ctxt.state.indexed_color_mask.is_empty() {
+        let current = ctxt.state.color_mask;
+        ctxt.state.indexed_color_mask.extend(iter::repeat(current).take(num_draw_buffers));
+    }
+
+    for index in 0 .. num_draw_buffers {
+        let mask = color_mask_to_gl(*draw_buffers_mask.get(index).unwrap_or(&mask));
+
+        if ctxt.state.indexed_color_mask[index] != mask {
+            unsafe {
+                ctxt.gl.ColorMaski(index as gl::types::GLuint, mask.0, mask.1, mask.2, mask.3);
+            }
+
+            ctxt.state.indexed_color_mask[index] = mask;
+        }
+    }
+
+    Ok(())
+}
+
 fn sync_line_width(ctxt: &mut context::CommandContext<'_>, line_width: Option<f32>) {
     if let Some(line_width) = line_width {
         if ctxt.state.line_width != line_width {
@@ -686,11 +926,15 @@ fn sync_dithering(ctxt: &mut context::CommandContext<'_>, dithering: bool) {
     }
 }
 
-fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Option<Rect>,
-                         scissor: Option<Rect>, surface_dimensions: (u32, u32))
+fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>,
+                         draw_parameters: &DrawParameters<'_>, surface_dimensions: (u32, u32))
+                         -> Result<(), DrawError>
 {
     // viewport
-    if let Some(viewport) = viewport {
+    if !draw_parameters.viewports.is_empty() {
+        sync_viewport_array(ctxt, draw_parameters.viewports, surface_dimensions)?;
+
+    } else if let Some(viewport) = draw_parameters.viewport {
         assert!(viewport.width <= ctxt.capabilities.max_viewport_dims.0 as u32,
                 "Viewport dimensions are too large");
         assert!(viewport.height <= ctxt.capabilities.max_viewport_dims.1 as u32,
@@ -703,6 +947,7 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
         if ctxt.state.viewport != Some(viewport) {
             unsafe { ctxt.gl.Viewport(viewport.0, viewport.1, viewport.2, viewport.3); }
             ctxt.state.viewport = Some(viewport);
+            ctxt.state.indexed_viewports.clear();
         }
 
     } else {
@@ -717,11 +962,15 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
         if ctxt.state.viewport != Some(viewport) {
             unsafe { ctxt.gl.Viewport(viewport.0, viewport.1, viewport.2, viewport.3); }
             ctxt.state.viewport = Some(viewport);
+            ctxt.state.indexed_viewports.clear();
         }
     }
 
     // scissor
-    if let Some(scissor) = scissor {
+    if !draw_parameters.scissors.is_empty() {
+        sync_scissor_array(ctxt, draw_parameters.scissors, surface_dimensions)?;
+
+    } else if let Some(scissor) = draw_parameters.scissor {
         let scissor = (scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
                        scissor.width as gl::types::GLsizei,
                        scissor.height as gl::types::GLsizei);
@@ -730,6 +979,7 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
             if ctxt.state.scissor != Some(scissor) {
                 ctxt.gl.Scissor(scissor.0, scissor.1, scissor.2, scissor.3);
                 ctxt.state.scissor = Some(scissor);
+                ctxt.state.indexed_scissors.clear();
             }
 
             if !ctxt.state.enabled_scissor_test {
@@ -745,6 +995,96 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
             }
         }
     }
+
+    Ok(())
+}
+
+fn sync_viewport_array(ctxt: &mut context::CommandContext<'_>, viewports: &[Rect],
+                       surface_dimensions: (u32, u32)) -> Result<(), DrawError>
+{
+    let max_viewports = match ctxt.capabilities.max_viewports {
+        Some(max) => max as usize,
+        None => return Err(DrawError::ViewportArrayNotSupported),
+    };
+
+    if viewports.len() > max_viewports {
+        return Err(DrawError::ViewportIndexOutOfBounds);
+    }
+
+    let whole_surface = Rect {
+        left: 0,
+        bottom: 0,
+        width: surface_dimensions.0,
+        height: surface_dimensions.1,
+    };
+
+    let mut values: SmallVec<[[gl::types::GLfloat; 4]; 16]> = SmallVec::new();
+    for index in 0 .. max_viewports {
+        let viewport = viewports.get(index).unwrap_or(&whole_surface);
+
+        if viewport.width > ctxt.capabilities.max_viewport_dims.0 as u32 ||
+           viewport.height > ctxt.capabilities.max_viewport_dims.1 as u32
+        {
+            return Err(DrawError::ViewportTooLarge);
+        }
+
+        values.push([viewport.left as gl::types::GLfloat, viewport.bottom as gl::types::GLfloat,
+                     viewport.width as gl::types::GLfloat, viewport.height as gl::types::GLfloat]);
+    }
+
+    if ctxt.state.indexed_viewports != values {
+        unsafe {
+            ctxt.gl.ViewportArrayv(0, values.len() as gl::types::GLsizei,
+                                   values.as_ptr() as *const gl::types::GLfloat);
+        }
+
+        ctxt.state.indexed_viewports = values;
+        ctxt.state.viewport = None;
+    }
+
+    Ok(())
+}
+
+fn sync_scissor_array(ctxt: &mut context::CommandContext<'_>, scissors: &[Rect],
+                      surface_dimensions: (u32, u32)) -> Result<(), DrawError>
+{
+    let max_viewports = match ctxt.capabilities.max_viewports {
+        Some(max) => max as usize,
+        None => return Err(DrawError::ViewportArrayNotSupported),
+    };
+
+    if scissors.len() > max_viewports {
+        return Err(DrawError::ViewportIndexOutOfBounds);
+    }
+
+    let whole_surface = Rect {
+        left: 0,
+        bottom: 0,
+        width: surface_dimensions.0,
+        height: surface_dimensions.1,
+    };
+
+    let values: SmallVec<[[gl::types::GLint; 4]; 16]> = (0 .. max_viewports)
+        .map(|index| scissors.get(index).unwrap_or(&whole_surface))
+        .map(|scissor| [scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
+                        scissor.width as gl::types::GLsizei, scissor.height as gl::types::GLsizei])
+        .collect();
+
+    unsafe {
+        if ctxt.state.indexed_scissors != values {
+            ctxt.gl.ScissorArrayv(0, values.len() as gl::types::GLsizei,
+                                  values.as_ptr() as *const gl::types::GLint);
+            ctxt.state.indexed_scissors = values;
+            ctxt.state.scissor = None;
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+    }
+
+    Ok(())
 }
 
 fn sync_rasterizer_discard(ctxt: &mut context::CommandContext<'_>, draw_primitives: bool)
@@ -1025,3 +1365,100 @@ fn sync_polygon_offset(ctxt: &mut context::CommandContext<'_>, offset: PolygonOf
     }
 }
 
+fn sync_logic_op(ctxt: &mut context::CommandContext<'_>, logic_op: Option<LogicOp>)
+                 -> Result<(), DrawError>
+{
+    let logic_op = match logic_op {
+        Some(logic_op) => logic_op.to_glenum(),
+        None => {
+            if ctxt.state.enabled_color_logic_op {
+                unsafe { ctxt.gl.Disable(gl::COLOR_LOGIC_OP); }
+                ctxt.state.enabled_color_logic_op = false;
+            }
+
+            return Ok(());
+        },
+    };
+
+    if !(ctxt.version >= &Version(Api::Gl, 1, 0)) {
+        return Err(DrawError::LogicOpNotSupported);
+    }
+
+    if !ctxt.state.enabled_color_logic_op {
+        unsafe { ctxt.gl.Enable(gl::COLOR_LOGIC_OP); }
+        ctxt.state.enabled_color_logic_op = true;
+    }
+
+    if ctxt.state.logic_op != logic_op {
+        unsafe { ctxt.gl.LogicOp(logic_op); }
+        ctxt.state.logic_op = logic_op;
+    }
+
+    Ok(())
+}
+
+fn sync_sample_mask(ctxt: &mut context::CommandContext<'_>, sample_mask: Option<u32>)
+                    -> Result<(), DrawError>
+{
+    let sample_mask = match sample_mask {
+        Some(sample_mask) => sample_mask,
+        None => {
+            if ctxt.state.enabled_sample_mask {
+                unsafe { ctxt.gl.Disable(gl::SAMPLE_MASK); }
+                ctxt.state.enabled_sample_mask = false;
+            }
+
+            return Ok(());
+        },
+    };
+
+    if !(ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+         ctxt.extensions.gl_arb_texture_multisample)
+    {
+        return Err(DrawError::SampleMaskNotSupported);
+    }
+
+    if !ctxt.state.enabled_sample_mask {
+        unsafe { ctxt.gl.Enable(gl::SAMPLE_MASK); }
+        ctxt.state.enabled_sample_mask = true;
+    }
+
+    if ctxt.state.sample_mask != sample_mask {
+        unsafe { ctxt.gl.SampleMaski(0, sample_mask); }
+        ctxt.state.sample_mask = sample_mask;
+    }
+
+    Ok(())
+}
+
+fn sync_min_sample_shading(ctxt: &mut context::CommandContext<'_>, value: Option<f32>)
+                           -> Result<(), DrawError>
+{
+    let value = match value {
+        Some(value) => value.max(0.0).min(1.0),
+        None => {
+            if ctxt.state.enabled_sample_shading {
+                unsafe { ctxt.gl.Disable(gl::SAMPLE_SHADING); }
+                ctxt.state.enabled_sample_shading = false;
+            }
+
+            return Ok(());
+        },
+    };
+
+    if !(ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2)) {
+        return Err(DrawError::SampleShadingNotSupported);
+    }
+
+    if !ctxt.state.enabled_sample_shading {
+        unsafe { ctxt.gl.Enable(gl::SAMPLE_SHADING); }
+        ctxt.state.enabled_sample_shading = true;
+    }
+
+    if ctxt.state.min_sample_shading != value {
+        unsafe { ctxt.gl.MinSampleShading(value); }
+        ctxt.state.min_sample_shading = value;
+    }
+
+    Ok(())
+}
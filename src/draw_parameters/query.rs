@@ -6,8 +6,9 @@ use crate::DrawError;
 use crate::ToGlEnum;
 use crate::GlObject;
 use crate::QueryExt;
+use crate::debug::{self, LabelNotSupportedError};
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::rc::Rc;
 use std::error::Error;
@@ -29,6 +30,9 @@ pub struct RawQuery {
     // true means that this query has already been used or is being used to get data
     // this is important to know because we want to avoid erasing data
     has_been_used: Cell<bool>,
+
+    // label that will be given to the query when it starts being used
+    pending_label: RefCell<Option<String>>,
 }
 
 pub enum QueryType {
@@ -39,6 +43,64 @@ pub enum QueryType {
     Timestamp,
     PrimitivesGenerated,
     TransformFeedbackPrimitivesWritten,
+    PipelineStatistic(PipelineStatistic),
+}
+
+/// A statistic of `GL_ARB_pipeline_statistics_query`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PipelineStatistic {
+    VerticesSubmitted,
+    PrimitivesSubmitted,
+    VertexShaderInvocations,
+    TessControlShaderPatches,
+    TessEvaluationShaderInvocations,
+    GeometryShaderInvocations,
+    GeometryShaderPrimitivesEmitted,
+    FragmentShaderInvocations,
+    ComputeShaderInvocations,
+    ClippingInputPrimitives,
+    ClippingOutputPrimitives,
+}
+
+impl PipelineStatistic {
+    /// All the statistics, in the order of their index in
+    /// `GlState::pipeline_statistics_queries`.
+    pub const ALL: [PipelineStatistic; 11] = [
+        PipelineStatistic::VerticesSubmitted,
+        PipelineStatistic::PrimitivesSubmitted,
+        PipelineStatistic::VertexShaderInvocations,
+        PipelineStatistic::TessControlShaderPatches,
+        PipelineStatistic::TessEvaluationShaderInvocations,
+        PipelineStatistic::GeometryShaderInvocations,
+        PipelineStatistic::GeometryShaderPrimitivesEmitted,
+        PipelineStatistic::FragmentShaderInvocations,
+        PipelineStatistic::ComputeShaderInvocations,
+        PipelineStatistic::ClippingInputPrimitives,
+        PipelineStatistic::ClippingOutputPrimitives,
+    ];
+}
+
+impl ToGlEnum for PipelineStatistic {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            PipelineStatistic::VerticesSubmitted => gl::VERTICES_SUBMITTED,
+            PipelineStatistic::PrimitivesSubmitted => gl::PRIMITIVES_SUBMITTED,
+            PipelineStatistic::VertexShaderInvocations => gl::VERTEX_SHADER_INVOCATIONS,
+            PipelineStatistic::TessControlShaderPatches => gl::TESS_CONTROL_SHADER_PATCHES,
+            PipelineStatistic::TessEvaluationShaderInvocations => {
+                gl::TESS_EVALUATION_SHADER_INVOCATIONS
+            },
+            PipelineStatistic::GeometryShaderInvocations => gl::GEOMETRY_SHADER_INVOCATIONS,
+            PipelineStatistic::GeometryShaderPrimitivesEmitted => {
+                gl::GEOMETRY_SHADER_PRIMITIVES_EMITTED
+            },
+            PipelineStatistic::FragmentShaderInvocations => gl::FRAGMENT_SHADER_INVOCATIONS,
+            PipelineStatistic::ComputeShaderInvocations => gl::COMPUTE_SHADER_INVOCATIONS,
+            PipelineStatistic::ClippingInputPrimitives => gl::CLIPPING_INPUT_PRIMITIVES,
+            PipelineStatistic::ClippingOutputPrimitives => gl::CLIPPING_OUTPUT_PRIMITIVES,
+        }
+    }
 }
 
 impl ToGlEnum for QueryType {
@@ -54,6 +116,7 @@ impl ToGlEnum for QueryType {
             QueryType::TransformFeedbackPrimitivesWritten => {
                 gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN
             },
+            QueryType::PipelineStatistic(statistic) => statistic.to_glenum(),
         }
     }
 }
@@ -117,6 +180,9 @@ impl RawQuery {
                     QueryType::AnySamplesPassedConservative if
                             ctxt.extensions.gl_arb_es3_compatibility ||
                             ctxt.version >= &Version(Api:: Gl, 4, 3) => (),
+                    QueryType::PipelineStatistic(_) if
+                            ctxt.extensions.gl_arb_pipeline_statistics_query ||
+                            ctxt.version >= &Version(Api:: Gl, 4, 6) => (),
                     _ => return Err(QueryCreationError::NotSupported)
                 };
 
@@ -135,6 +201,8 @@ impl RawQuery {
                     QueryType::AnySamplesPassed if ctxt.extensions.gl_arb_occlusion_query2 => (),
                     QueryType::AnySamplesPassedConservative if ctxt.extensions.gl_arb_es3_compatibility => (),
                     QueryType::TimeElapsed if ctxt.extensions.gl_arb_timer_query => (),
+                    QueryType::PipelineStatistic(_) if
+                            ctxt.extensions.gl_arb_pipeline_statistics_query => (),
 
                     _ => return Err(QueryCreationError::NotSupported)
                 };
@@ -189,9 +257,29 @@ impl RawQuery {
             id,
             ty,
             has_been_used: Cell::new(false),
+            pending_label: RefCell::new(None),
         })
     }
 
+    /// Gives a label to the query, which is shown by debuggers.
+    ///
+    /// The names returned by `glGenQueries` only become objects once the query is started, so
+    /// the label is applied when the query is used for the first time.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        let mut ctxt = self.context.make_current();
+
+        if !debug::is_object_label_supported(&*self.context) {
+            return Err(LabelNotSupportedError);
+        }
+
+        if self.has_been_used.get() {
+            unsafe { debug::set_object_label(&mut ctxt, gl::QUERY, self.id, label) }
+        } else {
+            *self.pending_label.borrow_mut() = Some(label.to_owned());
+            Ok(())
+        }
+    }
+
     /// Queries the counter to see if the result is already available.
     pub fn is_ready(&self) -> bool {
         let mut ctxt = self.context.make_current();
@@ -368,6 +456,23 @@ impl RawQuery {
             unsafe { raw_end_query(ctxt, gl::TIME_ELAPSED) };
             ctxt.state.time_elapsed_query = 0;
         }
+
+        for (index, statistic) in PipelineStatistic::ALL.iter().enumerate() {
+            if ctxt.state.pipeline_statistics_queries[index] == self.id {
+                unsafe { raw_end_query(ctxt, statistic.to_glenum()) };
+                ctxt.state.pipeline_statistics_queries[index] = 0;
+            }
+        }
+    }
+
+    /// Stops the query that is active for this statistic, if any.
+    fn end_pipeline_statistics_query(ctxt: &mut CommandContext<'_>, statistic: PipelineStatistic) {
+        let index = statistic as usize;
+
+        if ctxt.state.pipeline_statistics_queries[index] != 0 {
+            ctxt.state.pipeline_statistics_queries[index] = 0;
+            unsafe { raw_end_query(ctxt, statistic.to_glenum()); }
+        }
     }
 }
 
@@ -543,8 +648,36 @@ impl QueryExt for RawQuery {
                     ctxt.state.transform_feedback_primitives_written_query = self.id;
                 }
             },
+
+            QueryType::PipelineStatistic(statistic) => {
+                let index = statistic as usize;
+                let current = ctxt.state.pipeline_statistics_queries[index];
+
+                if current != self.id {
+                    if self.has_been_used.get() {
+                        return Err(DrawError::WrongQueryOperation);
+                    }
+
+                    unsafe {
+                        if current != 0 {
+                            raw_end_query(ctxt, statistic.to_glenum());
+                        }
+                        raw_begin_query(ctxt, statistic.to_glenum(), self.id);
+                    }
+
+                    self.has_been_used.set(true);
+                    ctxt.state.pipeline_statistics_queries[index] = self.id;
+                }
+            },
         };
 
+        if self.has_been_used.get() {
+            if let Some(label) = self.pending_label.borrow_mut().take() {
+                // support has been checked in `set_label`
+                let _ = unsafe { debug::set_object_label(ctxt, gl::QUERY, self.id, &label) };
+            }
+        }
+
         Ok(())
     }
 
@@ -737,6 +870,14 @@ macro_rules! impl_helper {
                 self.query.$get_fn()
             }
 
+            /// Gives a label to the query, which is shown by debuggers such as RenderDoc.
+            ///
+            /// Returns an error if the backend doesn't support `GL_KHR_debug`.
+            #[inline]
+            pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+                self.query.set_label(label)
+            }
+
             /// Writes the result of the query to a buffer when it is available.
             ///
             /// This function doesn't block. Instead it submits a commands to the GPU's commands
@@ -924,3 +1065,136 @@ impl TransformFeedbackPrimitivesWrittenQuery {
 }
 
 impl_helper!(TransformFeedbackPrimitivesWrittenQuery, u32, get_u32);
+
+macro_rules! pipeline_statistics_queries {
+    ($($(#[$attr:meta])* $name:ident => $statistic:ident,)+) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// ## Compatibility
+            ///
+            /// Requires OpenGL 4.6 or `GL_ARB_pipeline_statistics_query`.
+            #[derive(Debug)]
+            pub struct $name {
+                query: RawQuery,
+            }
+
+            impl $name {
+                /// Builds a new query.
+                #[inline]
+                pub fn new<F: ?Sized>(facade: &F) -> Result<$name, QueryCreationError>
+                                      where F: Facade
+                {
+                    let ty = QueryType::PipelineStatistic(PipelineStatistic::$statistic);
+                    RawQuery::new(facade, ty).map(|q| $name { query: q })
+                }
+            }
+
+            impl_helper!($name, u32, get_u32);
+
+            impl<'a> From<&'a $name> for PipelineStatisticsQueryParam<'a> {
+                #[inline]
+                fn from(q: &'a $name) -> PipelineStatisticsQueryParam<'a> {
+                    PipelineStatisticsQueryParam::$name(q)
+                }
+            }
+        )+
+
+        /// A pipeline statistics query to use while drawing or dispatching compute work.
+        ///
+        /// See `DrawParameters::pipeline_statistics_queries` and
+        /// `ComputeShader::execute_with_queries`.
+        #[derive(Debug, Copy, Clone)]
+        pub enum PipelineStatisticsQueryParam<'a> {
+            $(
+                #[doc = concat!("A `", stringify!($name), "`.")]
+                $name(&'a $name),
+            )+
+        }
+
+        impl<'a> PipelineStatisticsQueryParam<'a> {
+            #[inline]
+            fn query(&self) -> &'a RawQuery {
+                match *self {
+                    $(
+                        PipelineStatisticsQueryParam::$name(q) => &q.query,
+                    )+
+                }
+            }
+
+            #[inline]
+            fn statistic(&self) -> PipelineStatistic {
+                match *self {
+                    $(
+                        PipelineStatisticsQueryParam::$name(_) => PipelineStatistic::$statistic,
+                    )+
+                }
+            }
+        }
+    };
+}
+
+pipeline_statistics_queries! {
+    /// Query that allows you to know the number of vertices submitted to the primitive
+    /// assembler.
+    VerticesSubmittedQuery => VerticesSubmitted,
+
+    /// Query that allows you to know the number of primitives submitted to the primitive
+    /// assembler.
+    PrimitivesSubmittedQuery => PrimitivesSubmitted,
+
+    /// Query that allows you to know the number of times the vertex shader has been invoked.
+    VertexShaderInvocationsQuery => VertexShaderInvocations,
+
+    /// Query that allows you to know the number of patches processed by the tessellation
+    /// control shader.
+    TessControlShaderPatchesQuery => TessControlShaderPatches,
+
+    /// Query that allows you to know the number of times the tessellation evaluation shader has
+    /// been invoked.
+    TessEvaluationShaderInvocationsQuery => TessEvaluationShaderInvocations,
+
+    /// Query that allows you to know the number of times the geometry shader has been invoked.
+    GeometryShaderInvocationsQuery => GeometryShaderInvocations,
+
+    /// Query that allows you to know the number of primitives emitted by the geometry shader.
+    GeometryShaderPrimitivesEmittedQuery => GeometryShaderPrimitivesEmitted,
+
+    /// Query that allows you to know the number of times the fragment shader has been invoked.
+    FragmentShaderInvocationsQuery => FragmentShaderInvocations,
+
+    /// Query that allows you to know the number of times the compute shader has been invoked.
+    ComputeShaderInvocationsQuery => ComputeShaderInvocations,
+
+    /// Query that allows you to know the number of primitives that reached the clipping stage.
+    ClippingInputPrimitivesQuery => ClippingInputPrimitives,
+
+    /// Query that allows you to know the number of primitives that came out of the clipping
+    /// stage.
+    ClippingOutputPrimitivesQuery => ClippingOutputPrimitives,
+}
+
+/// Starts the given pipeline statistics queries and stops the queries of the other statistics.
+pub fn sync_pipeline_statistics_queries(ctxt: &mut CommandContext<'_>,
+                                        queries: &[PipelineStatisticsQueryParam<'_>])
+                                        -> Result<(), DrawError>
+{
+    // only one query can be active for each statistic
+    for (index, query) in queries.iter().enumerate() {
+        if queries[.. index].iter().any(|q| q.statistic() == query.statistic()) {
+            return Err(DrawError::WrongQueryOperation);
+        }
+    }
+
+    for &statistic in PipelineStatistic::ALL.iter() {
+        if !queries.iter().any(|q| q.statistic() == statistic) {
+            RawQuery::end_pipeline_statistics_query(ctxt, statistic);
+        }
+    }
+
+    for query in queries {
+        query.query().begin_query(ctxt)?;
+    }
+
+    Ok(())
+}
@@ -66,6 +66,7 @@ You can check whether they are supported by calling `EmptyFrameBuffer::is_suppor
 Not yet supported
 
 */
+use std::cell::RefCell;
 use std::rc::Rc;
 use smallvec::SmallVec;
 
@@ -88,7 +89,9 @@ use crate::uniforms;
 use crate::{Program, Surface};
 use crate::DrawError;
 
+use crate::gl;
 use crate::fbo;
+use crate::debug::{self, LabelNotSupportedError};
 
 pub use self::default_fb::{DefaultFramebufferAttachment, DefaultFramebuffer};
 pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
@@ -263,6 +266,17 @@ impl<'a> SimpleFrameBuffer<'a> {
             attachments,
         })
     }
+
+    /// Gives a label to the framebuffer object, which is shown by debuggers such as RenderDoc.
+    ///
+    /// Framebuffer objects are shared between all the framebuffers that have the same
+    /// attachments, so they also get this label.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        set_framebuffer_label(&self.context, &self.attachments, label)
+    }
 }
 
 impl<'a> Surface for SimpleFrameBuffer<'a> {
@@ -355,6 +369,7 @@ pub struct MultiOutputFrameBuffer<'a> {
     example_attachments: fbo::ValidatedAttachments<'a>,
     color_attachments: Vec<(String, fbo::RegularAttachment<'a>)>,
     depth_stencil_attachments: fbo::DepthStencilAttachments<fbo::RegularAttachment<'a>>,
+    label: RefCell<Option<String>>,
 }
 
 impl<'a> MultiOutputFrameBuffer<'a> {
@@ -502,9 +517,24 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             example_attachments,
             color_attachments: color,
             depth_stencil_attachments,
+            label: RefCell::new(None),
         })
     }
 
+    /// Gives a label to the framebuffer objects, which is shown by debuggers such as RenderDoc.
+    ///
+    /// The framebuffer object used when drawing depends on the locations of the outputs of the
+    /// program, so the label is also given to the framebuffer objects used by the next draws.
+    /// Framebuffer objects are shared between all the framebuffers that have the same
+    /// attachments, so they also get this label.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        set_framebuffer_label(&self.context, &self.example_attachments, label)?;
+        *self.label.borrow_mut() = Some(label.to_owned());
+        Ok(())
+    }
+
     fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments<'_> {
         let mut colors = SmallVec::new();
 
@@ -572,7 +602,14 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             }
         }
 
-        ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
+        let attachments = self.build_attachments(program);
+
+        if let Some(ref label) = *self.label.borrow() {
+            // support has been checked in `set_label`
+            let _ = set_framebuffer_label(&self.context, &attachments, label);
+        }
+
+        ops::draw(&self.context, Some(&attachments), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
@@ -684,6 +721,17 @@ impl<'a> EmptyFrameBuffer {
             attachments,
         })
     }
+
+    /// Gives a label to the framebuffer object, which is shown by debuggers such as RenderDoc.
+    ///
+    /// Framebuffer objects are shared between all the framebuffers that have the same
+    /// attachments, so they also get this label.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        set_framebuffer_label(&self.context, &self.attachments, label)
+    }
 }
 
 impl Surface for EmptyFrameBuffer {
@@ -794,6 +842,19 @@ pub trait ToColorAttachment<'a> {
     fn to_color_attachment(self) -> ColorAttachment<'a>;
 }
 
+/// Gives a label to the framebuffer object corresponding to the attachments.
+fn set_framebuffer_label(context: &Context, attachments: &fbo::ValidatedAttachments<'_>,
+                         label: &str) -> Result<(), LabelNotSupportedError>
+{
+    if !debug::is_object_label_supported(context) {
+        return Err(LabelNotSupportedError);
+    }
+
+    let mut ctxt = context.make_current();
+    let id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, Some(attachments));
+    unsafe { debug::set_object_label(&mut ctxt, gl::FRAMEBUFFER, id, label) }
+}
+
 impl<'a> ToColorAttachment<'a> for ColorAttachment<'a> {
     #[inline]
     fn to_color_attachment(self) -> ColorAttachment<'a> {
@@ -25,6 +25,8 @@ use crate::GlObject;
 use crate::fbo::FramebuffersContainer;
 use crate::backend::Facade;
 use crate::context::Context;
+use crate::context::ResourceKind;
+use crate::debug::{self, LabelNotSupportedError};
 use crate::ContextExt;
 use crate::version::Version;
 use crate::version::Api;
@@ -434,6 +436,10 @@ impl RenderBufferAny {
                 unreachable!();
             }
 
+            let size = image_format::estimate_image_size(format, width, height, 1) *
+                       samples.unwrap_or(1) as usize;
+            facade.get_context().resource_registry().insert_render_buffer(id, size);
+
             RenderBufferAny {
                 context: facade.get_context().clone(),
                 id,
@@ -484,6 +490,19 @@ impl RenderBufferAny {
             (depth_bits as u16, stencil_bits as u16)
         }
     }
+
+    /// Gives a label to the render buffer, which is shown by debuggers such as RenderDoc and in
+    /// `Context::get_resources_report`.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`, in which case the label
+    /// is only shown in the resources report.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.context.resource_registry().set_label(ResourceKind::RenderBuffer, self.id,
+                                                   Some(label.to_owned()));
+
+        let mut ctxt = self.context.make_current();
+        unsafe { debug::set_object_label(&mut ctxt, gl::RENDERBUFFER, self.id, label) }
+    }
 }
 
 impl Drop for RenderBufferAny {
@@ -514,6 +533,8 @@ impl Drop for RenderBufferAny {
                 unreachable!();
             }
         }
+
+        self.context.resource_registry().remove(ResourceKind::RenderBuffer, self.id);
     }
 }
 
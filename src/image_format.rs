@@ -1042,6 +1042,50 @@ pub enum CompressedFormat {
     S3tcDxt3Alpha,
     /// S3TC DXT5, see https://www.opengl.org/wiki/S3_Texture_Compression.
     S3tcDxt5Alpha,
+
+    /// ETC2 format with three components (no alpha). ETC1 data is also valid ETC2 data.
+    Etc2Rgb8,
+    /// ETC2 format with three components and 1-bit alpha.
+    Etc2Rgb8A1,
+    /// ETC2 format with four components, the alpha being compressed with EAC.
+    Etc2Rgba8,
+    /// EAC format with one unsigned component.
+    EacR11U,
+    /// EAC format with one signed component.
+    EacR11I,
+    /// EAC format with two unsigned components.
+    EacRg11UU,
+    /// EAC format with two signed components.
+    EacRg11II,
+
+    /// ASTC format with four components and blocks of 4x4 texels.
+    Astc4x4,
+    /// ASTC format with four components and blocks of 5x4 texels.
+    Astc5x4,
+    /// ASTC format with four components and blocks of 5x5 texels.
+    Astc5x5,
+    /// ASTC format with four components and blocks of 6x5 texels.
+    Astc6x5,
+    /// ASTC format with four components and blocks of 6x6 texels.
+    Astc6x6,
+    /// ASTC format with four components and blocks of 8x5 texels.
+    Astc8x5,
+    /// ASTC format with four components and blocks of 8x6 texels.
+    Astc8x6,
+    /// ASTC format with four components and blocks of 8x8 texels.
+    Astc8x8,
+    /// ASTC format with four components and blocks of 10x5 texels.
+    Astc10x5,
+    /// ASTC format with four components and blocks of 10x6 texels.
+    Astc10x6,
+    /// ASTC format with four components and blocks of 10x8 texels.
+    Astc10x8,
+    /// ASTC format with four components and blocks of 10x10 texels.
+    Astc10x10,
+    /// ASTC format with four components and blocks of 12x10 texels.
+    Astc12x10,
+    /// ASTC format with four components and blocks of 12x12 texels.
+    Astc12x12,
 }
 
 impl CompressedFormat {
@@ -1060,6 +1104,27 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt1Alpha,
             CompressedFormat::S3tcDxt3Alpha,
             CompressedFormat::S3tcDxt5Alpha,
+            CompressedFormat::Etc2Rgb8,
+            CompressedFormat::Etc2Rgb8A1,
+            CompressedFormat::Etc2Rgba8,
+            CompressedFormat::EacR11U,
+            CompressedFormat::EacR11I,
+            CompressedFormat::EacRg11UU,
+            CompressedFormat::EacRg11II,
+            CompressedFormat::Astc4x4,
+            CompressedFormat::Astc5x4,
+            CompressedFormat::Astc5x5,
+            CompressedFormat::Astc6x5,
+            CompressedFormat::Astc6x6,
+            CompressedFormat::Astc8x5,
+            CompressedFormat::Astc8x6,
+            CompressedFormat::Astc8x8,
+            CompressedFormat::Astc10x5,
+            CompressedFormat::Astc10x6,
+            CompressedFormat::Astc10x8,
+            CompressedFormat::Astc10x10,
+            CompressedFormat::Astc12x10,
+            CompressedFormat::Astc12x12,
         ]
     }
 
@@ -1108,6 +1173,22 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc
             },
+            CompressedFormat::Etc2Rgb8 | CompressedFormat::Etc2Rgb8A1 |
+            CompressedFormat::Etc2Rgba8 | CompressedFormat::EacR11U |
+            CompressedFormat::EacR11I | CompressedFormat::EacRg11UU |
+            CompressedFormat::EacRg11II => {
+                version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+                extensions.gl_arb_es3_compatibility
+            },
+            CompressedFormat::Astc4x4 | CompressedFormat::Astc5x4 | CompressedFormat::Astc5x5 |
+            CompressedFormat::Astc6x5 | CompressedFormat::Astc6x6 | CompressedFormat::Astc8x5 |
+            CompressedFormat::Astc8x6 | CompressedFormat::Astc8x8 | CompressedFormat::Astc10x5 |
+            CompressedFormat::Astc10x6 | CompressedFormat::Astc10x8 |
+            CompressedFormat::Astc10x10 | CompressedFormat::Astc12x10 |
+            CompressedFormat::Astc12x12 => {
+                version >= &Version(Api::GlEs, 3, 2) ||
+                extensions.gl_khr_texture_compression_astc_ldr
+            },
         }
     }
 
@@ -1124,6 +1205,27 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt1Alpha => gl::COMPRESSED_RGBA_S3TC_DXT1_EXT,
             CompressedFormat::S3tcDxt3Alpha => gl::COMPRESSED_RGBA_S3TC_DXT3_EXT,
             CompressedFormat::S3tcDxt5Alpha => gl::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            CompressedFormat::Etc2Rgb8 => gl::COMPRESSED_RGB8_ETC2,
+            CompressedFormat::Etc2Rgb8A1 => gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            CompressedFormat::Etc2Rgba8 => gl::COMPRESSED_RGBA8_ETC2_EAC,
+            CompressedFormat::EacR11U => gl::COMPRESSED_R11_EAC,
+            CompressedFormat::EacR11I => gl::COMPRESSED_SIGNED_R11_EAC,
+            CompressedFormat::EacRg11UU => gl::COMPRESSED_RG11_EAC,
+            CompressedFormat::EacRg11II => gl::COMPRESSED_SIGNED_RG11_EAC,
+            CompressedFormat::Astc4x4 => gl::COMPRESSED_RGBA_ASTC_4x4,
+            CompressedFormat::Astc5x4 => gl::COMPRESSED_RGBA_ASTC_5x4,
+            CompressedFormat::Astc5x5 => gl::COMPRESSED_RGBA_ASTC_5x5,
+            CompressedFormat::Astc6x5 => gl::COMPRESSED_RGBA_ASTC_6x5,
+            CompressedFormat::Astc6x6 => gl::COMPRESSED_RGBA_ASTC_6x6,
+            CompressedFormat::Astc8x5 => gl::COMPRESSED_RGBA_ASTC_8x5,
+            CompressedFormat::Astc8x6 => gl::COMPRESSED_RGBA_ASTC_8x6,
+            CompressedFormat::Astc8x8 => gl::COMPRESSED_RGBA_ASTC_8x8,
+            CompressedFormat::Astc10x5 => gl::COMPRESSED_RGBA_ASTC_10x5,
+            CompressedFormat::Astc10x6 => gl::COMPRESSED_RGBA_ASTC_10x6,
+            CompressedFormat::Astc10x8 => gl::COMPRESSED_RGBA_ASTC_10x8,
+            CompressedFormat::Astc10x10 => gl::COMPRESSED_RGBA_ASTC_10x10,
+            CompressedFormat::Astc12x10 => gl::COMPRESSED_RGBA_ASTC_12x10,
+            CompressedFormat::Astc12x12 => gl::COMPRESSED_RGBA_ASTC_12x12,
         }
     }
 }
@@ -1138,6 +1240,40 @@ pub enum CompressedSrgbFormat {
     S3tcDxt1Alpha,
     S3tcDxt3Alpha,
     S3tcDxt5Alpha,
+    /// ETC2 format without alpha.
+    Etc2Rgb8,
+    /// ETC2 format with 1-bit alpha.
+    Etc2Rgb8A1,
+    /// ETC2 format with alpha compressed with EAC.
+    Etc2Rgba8,
+    /// ASTC format with blocks of 4x4 texels. sRGB with alpha.
+    Astc4x4,
+    /// ASTC format with blocks of 5x4 texels. sRGB with alpha.
+    Astc5x4,
+    /// ASTC format with blocks of 5x5 texels. sRGB with alpha.
+    Astc5x5,
+    /// ASTC format with blocks of 6x5 texels. sRGB with alpha.
+    Astc6x5,
+    /// ASTC format with blocks of 6x6 texels. sRGB with alpha.
+    Astc6x6,
+    /// ASTC format with blocks of 8x5 texels. sRGB with alpha.
+    Astc8x5,
+    /// ASTC format with blocks of 8x6 texels. sRGB with alpha.
+    Astc8x6,
+    /// ASTC format with blocks of 8x8 texels. sRGB with alpha.
+    Astc8x8,
+    /// ASTC format with blocks of 10x5 texels. sRGB with alpha.
+    Astc10x5,
+    /// ASTC format with blocks of 10x6 texels. sRGB with alpha.
+    Astc10x6,
+    /// ASTC format with blocks of 10x8 texels. sRGB with alpha.
+    Astc10x8,
+    /// ASTC format with blocks of 10x10 texels. sRGB with alpha.
+    Astc10x10,
+    /// ASTC format with blocks of 12x10 texels. sRGB with alpha.
+    Astc12x10,
+    /// ASTC format with blocks of 12x12 texels. sRGB with alpha.
+    Astc12x12,
 }
 
 impl CompressedSrgbFormat {
@@ -1150,6 +1286,23 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt1Alpha,
             CompressedSrgbFormat::S3tcDxt3Alpha,
             CompressedSrgbFormat::S3tcDxt5Alpha,
+            CompressedSrgbFormat::Etc2Rgb8,
+            CompressedSrgbFormat::Etc2Rgb8A1,
+            CompressedSrgbFormat::Etc2Rgba8,
+            CompressedSrgbFormat::Astc4x4,
+            CompressedSrgbFormat::Astc5x4,
+            CompressedSrgbFormat::Astc5x5,
+            CompressedSrgbFormat::Astc6x5,
+            CompressedSrgbFormat::Astc6x6,
+            CompressedSrgbFormat::Astc8x5,
+            CompressedSrgbFormat::Astc8x6,
+            CompressedSrgbFormat::Astc8x8,
+            CompressedSrgbFormat::Astc10x5,
+            CompressedSrgbFormat::Astc10x6,
+            CompressedSrgbFormat::Astc10x8,
+            CompressedSrgbFormat::Astc10x10,
+            CompressedSrgbFormat::Astc12x10,
+            CompressedSrgbFormat::Astc12x12,
         ]
     }
 
@@ -1180,6 +1333,21 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc && extensions.gl_ext_texture_srgb
             },
+            CompressedSrgbFormat::Etc2Rgb8 | CompressedSrgbFormat::Etc2Rgb8A1 |
+            CompressedSrgbFormat::Etc2Rgba8 => {
+                version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+                extensions.gl_arb_es3_compatibility
+            },
+            CompressedSrgbFormat::Astc4x4 | CompressedSrgbFormat::Astc5x4 |
+            CompressedSrgbFormat::Astc5x5 | CompressedSrgbFormat::Astc6x5 |
+            CompressedSrgbFormat::Astc6x6 | CompressedSrgbFormat::Astc8x5 |
+            CompressedSrgbFormat::Astc8x6 | CompressedSrgbFormat::Astc8x8 |
+            CompressedSrgbFormat::Astc10x5 | CompressedSrgbFormat::Astc10x6 |
+            CompressedSrgbFormat::Astc10x8 | CompressedSrgbFormat::Astc10x10 |
+            CompressedSrgbFormat::Astc12x10 | CompressedSrgbFormat::Astc12x12 => {
+                version >= &Version(Api::GlEs, 3, 2) ||
+                extensions.gl_khr_texture_compression_astc_ldr
+            },
         }
     }
 
@@ -1190,6 +1358,23 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt1Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT,
             CompressedSrgbFormat::S3tcDxt3Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT,
             CompressedSrgbFormat::S3tcDxt5Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT,
+            CompressedSrgbFormat::Etc2Rgb8 => gl::COMPRESSED_SRGB8_ETC2,
+            CompressedSrgbFormat::Etc2Rgb8A1 => gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            CompressedSrgbFormat::Etc2Rgba8 => gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC,
+            CompressedSrgbFormat::Astc4x4 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4,
+            CompressedSrgbFormat::Astc5x4 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4,
+            CompressedSrgbFormat::Astc5x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5,
+            CompressedSrgbFormat::Astc6x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5,
+            CompressedSrgbFormat::Astc6x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6,
+            CompressedSrgbFormat::Astc8x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5,
+            CompressedSrgbFormat::Astc8x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6,
+            CompressedSrgbFormat::Astc8x8 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8,
+            CompressedSrgbFormat::Astc10x5 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5,
+            CompressedSrgbFormat::Astc10x6 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6,
+            CompressedSrgbFormat::Astc10x8 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8,
+            CompressedSrgbFormat::Astc10x10 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10,
+            CompressedSrgbFormat::Astc12x10 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10,
+            CompressedSrgbFormat::Astc12x12 => gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12,
         }
     }
 }
@@ -1463,6 +1648,17 @@ impl TextureFormat {
         }
     }
 
+    /// Returns true if a texture whose storage uses this format can be viewed with the other
+    /// format, and vice versa.
+    ///
+    /// Formats are compatible if they are identical or if they belong to the same compatibility
+    /// class, for example two uncompressed formats with the same number of bits per texel. See
+    /// `TextureAny::new_view`.
+    #[inline]
+    pub fn is_view_compatible(&self, other: &TextureFormat) -> bool {
+        is_view_compatible(self.to_glenum(), other.to_glenum())
+    }
+
     /// Returns true if the format is color-renderable, depth-renderable, depth-stencil-renderable
     /// or stencil-renderable.
     #[inline]
@@ -1497,6 +1693,305 @@ impl ToGlEnum for TextureFormat {
     }
 }
 
+/// Compatibility class of an internal format for texture views.
+///
+/// A texture view can only reinterpret the storage of a texture with a format of the same class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewClass {
+    Bits128,
+    Bits96,
+    Bits64,
+    Bits48,
+    Bits32,
+    Bits24,
+    Bits16,
+    Bits8,
+    Rgtc1Red,
+    Rgtc2Rg,
+    BptcUnorm,
+    BptcFloat,
+    S3tcDxt1Rgb,
+    S3tcDxt1Rgba,
+    S3tcDxt3Rgba,
+    S3tcDxt5Rgba,
+    Etc2Rgb,
+    Etc2Rgba,
+    Etc2EacRgba,
+    EacR11,
+    EacRg11,
+    /// ASTC, with the width and height of the blocks.
+    Astc(u32, u32),
+}
+
+impl ViewClass {
+    /// Returns the class of an internal format, or `None` if the format can only be viewed with
+    /// itself.
+    fn from_internal_format(format: gl::types::GLenum) -> Option<ViewClass> {
+        Some(match format {
+            gl::RGBA32F | gl::RGBA32UI | gl::RGBA32I => ViewClass::Bits128,
+            gl::RGB32F | gl::RGB32UI | gl::RGB32I => ViewClass::Bits96,
+            gl::RGBA16F | gl::RG32F | gl::RGBA16UI | gl::RG32UI | gl::RGBA16I | gl::RG32I |
+            gl::RGBA16 | gl::RGBA16_SNORM => ViewClass::Bits64,
+            gl::RGB16 | gl::RGB16_SNORM | gl::RGB16F | gl::RGB16UI | gl::RGB16I => ViewClass::Bits48,
+            gl::RG16F | gl::R11F_G11F_B10F | gl::R32F | gl::RGB10_A2UI | gl::RGBA8UI |
+            gl::RG16UI | gl::R32UI | gl::RGBA8I | gl::RG16I | gl::R32I | gl::RGB10_A2 |
+            gl::RGBA8 | gl::RG16 | gl::RGBA8_SNORM | gl::RG16_SNORM | gl::SRGB8_ALPHA8 |
+            gl::RGB9_E5 => ViewClass::Bits32,
+            gl::RGB8 | gl::RGB8_SNORM | gl::SRGB8 | gl::RGB8UI | gl::RGB8I => ViewClass::Bits24,
+            gl::R16F | gl::RG8UI | gl::R16UI | gl::RG8I | gl::R16I | gl::RG8 | gl::R16 |
+            gl::RG8_SNORM | gl::R16_SNORM => ViewClass::Bits16,
+            gl::R8UI | gl::R8I | gl::R8 | gl::R8_SNORM => ViewClass::Bits8,
+            gl::COMPRESSED_RED_RGTC1 | gl::COMPRESSED_SIGNED_RED_RGTC1 => ViewClass::Rgtc1Red,
+            gl::COMPRESSED_RG_RGTC2 | gl::COMPRESSED_SIGNED_RG_RGTC2 => ViewClass::Rgtc2Rg,
+            gl::COMPRESSED_RGBA_BPTC_UNORM | gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => {
+                ViewClass::BptcUnorm
+            },
+            gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT | gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => {
+                ViewClass::BptcFloat
+            },
+            gl::COMPRESSED_RGB_S3TC_DXT1_EXT | gl::COMPRESSED_SRGB_S3TC_DXT1_EXT => {
+                ViewClass::S3tcDxt1Rgb
+            },
+            gl::COMPRESSED_RGBA_S3TC_DXT1_EXT | gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => {
+                ViewClass::S3tcDxt1Rgba
+            },
+            gl::COMPRESSED_RGBA_S3TC_DXT3_EXT | gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT => {
+                ViewClass::S3tcDxt3Rgba
+            },
+            gl::COMPRESSED_RGBA_S3TC_DXT5_EXT | gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => {
+                ViewClass::S3tcDxt5Rgba
+            },
+            gl::COMPRESSED_RGB8_ETC2 | gl::COMPRESSED_SRGB8_ETC2 => ViewClass::Etc2Rgb,
+            gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 |
+            gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => ViewClass::Etc2Rgba,
+            gl::COMPRESSED_RGBA8_ETC2_EAC | gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => {
+                ViewClass::Etc2EacRgba
+            },
+            gl::COMPRESSED_R11_EAC | gl::COMPRESSED_SIGNED_R11_EAC => ViewClass::EacR11,
+            gl::COMPRESSED_RG11_EAC | gl::COMPRESSED_SIGNED_RG11_EAC => ViewClass::EacRg11,
+            gl::COMPRESSED_RGBA_ASTC_4x4 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4 => {
+                ViewClass::Astc(4, 4)
+            },
+            gl::COMPRESSED_RGBA_ASTC_5x4 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4 => {
+                ViewClass::Astc(5, 4)
+            },
+            gl::COMPRESSED_RGBA_ASTC_5x5 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5 => {
+                ViewClass::Astc(5, 5)
+            },
+            gl::COMPRESSED_RGBA_ASTC_6x5 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5 => {
+                ViewClass::Astc(6, 5)
+            },
+            gl::COMPRESSED_RGBA_ASTC_6x6 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6 => {
+                ViewClass::Astc(6, 6)
+            },
+            gl::COMPRESSED_RGBA_ASTC_8x5 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5 => {
+                ViewClass::Astc(8, 5)
+            },
+            gl::COMPRESSED_RGBA_ASTC_8x6 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6 => {
+                ViewClass::Astc(8, 6)
+            },
+            gl::COMPRESSED_RGBA_ASTC_8x8 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8 => {
+                ViewClass::Astc(8, 8)
+            },
+            gl::COMPRESSED_RGBA_ASTC_10x5 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5 => {
+                ViewClass::Astc(10, 5)
+            },
+            gl::COMPRESSED_RGBA_ASTC_10x6 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6 => {
+                ViewClass::Astc(10, 6)
+            },
+            gl::COMPRESSED_RGBA_ASTC_10x8 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8 => {
+                ViewClass::Astc(10, 8)
+            },
+            gl::COMPRESSED_RGBA_ASTC_10x10 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10 => {
+                ViewClass::Astc(10, 10)
+            },
+            gl::COMPRESSED_RGBA_ASTC_12x10 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10 => {
+                ViewClass::Astc(12, 10)
+            },
+            gl::COMPRESSED_RGBA_ASTC_12x12 | gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12 => {
+                ViewClass::Astc(12, 12)
+            },
+            _ => return None,
+        })
+    }
+}
+
+impl ViewClass {
+    /// Returns true if the class contains compressed formats.
+    #[inline]
+    fn is_compressed(&self) -> bool {
+        !matches!(self, ViewClass::Bits128 | ViewClass::Bits96 | ViewClass::Bits64 |
+                        ViewClass::Bits48 | ViewClass::Bits32 | ViewClass::Bits24 |
+                        ViewClass::Bits16 | ViewClass::Bits8)
+    }
+
+    /// Returns the number of bits of a texel, or of a block for compressed formats.
+    fn get_bits(&self) -> u32 {
+        match self {
+            ViewClass::Bits128 => 128,
+            ViewClass::Bits96 => 96,
+            ViewClass::Bits64 => 64,
+            ViewClass::Bits48 => 48,
+            ViewClass::Bits32 => 32,
+            ViewClass::Bits24 => 24,
+            ViewClass::Bits16 => 16,
+            ViewClass::Bits8 => 8,
+            ViewClass::Rgtc1Red | ViewClass::S3tcDxt1Rgb | ViewClass::S3tcDxt1Rgba |
+            ViewClass::Etc2Rgb | ViewClass::Etc2Rgba | ViewClass::EacR11 => 64,
+            ViewClass::Rgtc2Rg | ViewClass::BptcUnorm | ViewClass::BptcFloat |
+            ViewClass::S3tcDxt3Rgba | ViewClass::S3tcDxt5Rgba | ViewClass::Etc2EacRgba |
+            ViewClass::EacRg11 | ViewClass::Astc(_, _) => 128,
+        }
+    }
+}
+
+/// Returns the width and height in texels of the blocks of an internal format. Uncompressed
+/// formats have blocks of one texel.
+pub fn get_block_dimensions(format: gl::types::GLenum) -> (u32, u32) {
+    match ViewClass::from_internal_format(format) {
+        Some(ViewClass::Astc(width, height)) => (width, height),
+        Some(class) if class.is_compressed() => (4, 4),
+        _ => (1, 1),
+    }
+}
+
+/// Returns an estimate of the number of bytes used in video memory by an image of the given
+/// internal format and dimensions.
+///
+/// Unsized formats and formats whose size is unknown are assumed to use 32 bits per texel.
+pub fn estimate_image_size(format: gl::types::GLenum, width: u32, height: u32, depth: u32) -> usize {
+    let bits = match ViewClass::from_internal_format(format) {
+        Some(class) => class.get_bits(),
+        None => match format {
+            gl::STENCIL_INDEX1 | gl::STENCIL_INDEX4 | gl::STENCIL_INDEX8 | gl::R3_G3_B2 => 8,
+            gl::DEPTH_COMPONENT16 | gl::STENCIL_INDEX16 | gl::RGB565 | gl::RGB5_A1 |
+            gl::RGBA4 => 16,
+            gl::DEPTH32F_STENCIL8 => 64,
+            _ => 32,
+        },
+    };
+
+    let (block_width, block_height) = get_block_dimensions(format);
+    let blocks_x = (width + block_width - 1) / block_width;
+    let blocks_y = (height + block_height - 1) / block_height;
+
+    blocks_x as usize * blocks_y as usize * depth as usize * bits as usize / 8
+}
+
+/// Returns the size in bytes of a compressed image of the given internal format.
+///
+/// ## Panic
+///
+/// Panics if the image is not two-dimensional.
+fn compressed_buffer_size(format: gl::types::GLenum, width: u32, height: Option<u32>,
+                          depth: Option<u32>, array_size: Option<u32>) -> usize
+{
+    let height = height.expect("Compressed textures must have 2 dimensions");
+    if depth.is_some() { // allow `array_size` (2D textures arrays) but not depth (3D textures)
+        panic!("Compressed textures are 2 dimension only.")
+    }
+
+    estimate_image_size(format, width, height, 1) * array_size.unwrap_or(1) as usize
+}
+
+/// Returns true if texels can be copied with `glCopyImageSubData` between images with the
+/// internal formats `source` and `target`.
+///
+/// A block of a compressed format can be copied to or from a texel of an uncompressed format
+/// of the same size.
+pub fn is_copy_compatible(source: gl::types::GLenum, target: gl::types::GLenum) -> bool {
+    if is_view_compatible(source, target) {
+        return true;
+    }
+
+    match (ViewClass::from_internal_format(source), ViewClass::from_internal_format(target)) {
+        (Some(a), Some(b)) => a.is_compressed() != b.is_compressed() && a.get_bits() == b.get_bits(),
+        _ => false,
+    }
+}
+
+/// Returns the client format and type with which the texels of an image of the given
+/// uncompressed internal format can be read and uploaded without any conversion.
+///
+/// Returns `None` for compressed, depth and stencil formats, and for formats whose size in
+/// video memory is unknown.
+pub fn get_raw_client_format(format: gl::types::GLenum)
+                             -> Option<(gl::types::GLenum, gl::types::GLenum)>
+{
+    Some(match format {
+        gl::R8 => (gl::RED, gl::UNSIGNED_BYTE),
+        gl::R8_SNORM => (gl::RED, gl::BYTE),
+        gl::R16 => (gl::RED, gl::UNSIGNED_SHORT),
+        gl::R16_SNORM => (gl::RED, gl::SHORT),
+        gl::RG8 => (gl::RG, gl::UNSIGNED_BYTE),
+        gl::RG8_SNORM => (gl::RG, gl::BYTE),
+        gl::RG16 => (gl::RG, gl::UNSIGNED_SHORT),
+        gl::RG16_SNORM => (gl::RG, gl::SHORT),
+        gl::R3_G3_B2 => (gl::RGB, gl::UNSIGNED_BYTE_3_3_2),
+        gl::RGB565 => (gl::RGB, gl::UNSIGNED_SHORT_5_6_5),
+        gl::RGB8 | gl::SRGB8 => (gl::RGB, gl::UNSIGNED_BYTE),
+        gl::RGB8_SNORM => (gl::RGB, gl::BYTE),
+        gl::RGB16 => (gl::RGB, gl::UNSIGNED_SHORT),
+        gl::RGB16_SNORM => (gl::RGB, gl::SHORT),
+        gl::RGBA4 => (gl::RGBA, gl::UNSIGNED_SHORT_4_4_4_4),
+        gl::RGB5_A1 => (gl::RGBA, gl::UNSIGNED_SHORT_5_5_5_1),
+        gl::RGBA8 | gl::SRGB8_ALPHA8 => (gl::RGBA, gl::UNSIGNED_BYTE),
+        gl::RGBA8_SNORM => (gl::RGBA, gl::BYTE),
+        gl::RGB10_A2 => (gl::RGBA, gl::UNSIGNED_INT_2_10_10_10_REV),
+        gl::RGBA16 => (gl::RGBA, gl::UNSIGNED_SHORT),
+        gl::RGBA16_SNORM => (gl::RGBA, gl::SHORT),
+        gl::R16F => (gl::RED, gl::HALF_FLOAT),
+        gl::RG16F => (gl::RG, gl::HALF_FLOAT),
+        gl::RGB16F => (gl::RGB, gl::HALF_FLOAT),
+        gl::RGBA16F => (gl::RGBA, gl::HALF_FLOAT),
+        gl::R32F => (gl::RED, gl::FLOAT),
+        gl::RG32F => (gl::RG, gl::FLOAT),
+        gl::RGB32F => (gl::RGB, gl::FLOAT),
+        gl::RGBA32F => (gl::RGBA, gl::FLOAT),
+        gl::R11F_G11F_B10F => (gl::RGB, gl::UNSIGNED_INT_10F_11F_11F_REV),
+        gl::RGB9_E5 => (gl::RGB, gl::UNSIGNED_INT_5_9_9_9_REV),
+        gl::R8I => (gl::RED_INTEGER, gl::BYTE),
+        gl::R8UI => (gl::RED_INTEGER, gl::UNSIGNED_BYTE),
+        gl::R16I => (gl::RED_INTEGER, gl::SHORT),
+        gl::R16UI => (gl::RED_INTEGER, gl::UNSIGNED_SHORT),
+        gl::R32I => (gl::RED_INTEGER, gl::INT),
+        gl::R32UI => (gl::RED_INTEGER, gl::UNSIGNED_INT),
+        gl::RG8I => (gl::RG_INTEGER, gl::BYTE),
+        gl::RG8UI => (gl::RG_INTEGER, gl::UNSIGNED_BYTE),
+        gl::RG16I => (gl::RG_INTEGER, gl::SHORT),
+        gl::RG16UI => (gl::RG_INTEGER, gl::UNSIGNED_SHORT),
+        gl::RG32I => (gl::RG_INTEGER, gl::INT),
+        gl::RG32UI => (gl::RG_INTEGER, gl::UNSIGNED_INT),
+        gl::RGB8I => (gl::RGB_INTEGER, gl::BYTE),
+        gl::RGB8UI => (gl::RGB_INTEGER, gl::UNSIGNED_BYTE),
+        gl::RGB16I => (gl::RGB_INTEGER, gl::SHORT),
+        gl::RGB16UI => (gl::RGB_INTEGER, gl::UNSIGNED_SHORT),
+        gl::RGB32I => (gl::RGB_INTEGER, gl::INT),
+        gl::RGB32UI => (gl::RGB_INTEGER, gl::UNSIGNED_INT),
+        gl::RGBA8I => (gl::RGBA_INTEGER, gl::BYTE),
+        gl::RGBA8UI => (gl::RGBA_INTEGER, gl::UNSIGNED_BYTE),
+        gl::RGB10_A2UI => (gl::RGBA_INTEGER, gl::UNSIGNED_INT_2_10_10_10_REV),
+        gl::RGBA16I => (gl::RGBA_INTEGER, gl::SHORT),
+        gl::RGBA16UI => (gl::RGBA_INTEGER, gl::UNSIGNED_SHORT),
+        gl::RGBA32I => (gl::RGBA_INTEGER, gl::INT),
+        gl::RGBA32UI => (gl::RGBA_INTEGER, gl::UNSIGNED_INT),
+        _ => return None,
+    })
+}
+
+/// Returns true if the storage of a texture with the internal format `original` can be viewed
+/// with the internal format `view`.
+pub fn is_view_compatible(original: gl::types::GLenum, view: gl::types::GLenum) -> bool {
+    if original == view {
+        return true;
+    }
+
+    match (ViewClass::from_internal_format(original), ViewClass::from_internal_format(view)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClientFormatAny {
     ClientFormat(ClientFormat),
@@ -1518,6 +2013,8 @@ impl ClientFormatAny {
     /// Gets the size in bytes of the buffer required to store a uncompressed image
     /// of the specified dimensions on this format.
     ///
+    /// For compressed formats, the dimensions are rounded up to a whole number of blocks.
+    ///
     /// ## Panic
     ///
     /// Panics if the dimensions are invalid for this format.
@@ -1529,54 +2026,12 @@ impl ClientFormatAny {
                                 depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize
             },
 
-            // 8 bytes per 4x4 block
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt1Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt1NoAlpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt1NoAlpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatU) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatI) => {
-
-                let width = if width < 4 { 4 } else { width as usize };
-                let height = height.map(|height| if height < 4 { 4 } else { height as usize })
-                                   .expect("ST3C, RGTC and BPTC textures must have 2 dimensions");
-                if (width % 4) != 0 || (height % 4) != 0 {
-                    panic!("ST3C, RGTC and BPTC textures must have a width and height multiple of 4.");
-                }
-                if depth.is_some() { // allow `array_size` (2D textures arrays) but not depth (3D textures)
-                    panic!("ST3C, RGTC and BPTC textures are 2 dimension only.")
-                }
-
-                let uncompressed_bit_size =  4 * width as usize * height as usize *
-                                            depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize;
-                uncompressed_bit_size / 8   // Apply 8:1 compression ratio
-            },
-
-            // 16 bytes per 4x4 block
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt3Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt3Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::S3tcDxt5Alpha) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::S3tcDxt5Alpha) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcUnorm4) |
-            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Bptc) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcSignedFloat3) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::BptcUnsignedFloat3) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatUU) |
-            ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatII) => {
-
-                let width = if width < 4 { 4 } else { width as usize };
-                let height = height.map(|height| if height < 4 { 4 } else { height as usize })
-                                   .expect("ST3C, RGTC and BPTC textures must have 2 dimensions");
-                if (width % 4) != 0 || (height % 4) != 0 {
-                    panic!("ST3C, RGTC and BPTC textures must have a width and height multiple of 4.");
-                }
-                if depth.is_some() { // allow `array_size` (2D textures arrays) but not depth (3D textures)
-                    panic!("ST3C, RGTC and BPTC textures are 2 dimension only.")
-                }
+            ClientFormatAny::CompressedFormat(format) => {
+                compressed_buffer_size(format.to_glenum(), width, height, depth, array_size)
+            },
 
-                let uncompressed_bit_size =  4 * width as usize * height as usize *
-                                            depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize;
-                uncompressed_bit_size / 4   // Apply 4:1 compression ratio
+            ClientFormatAny::CompressedSrgbFormat(format) => {
+                compressed_buffer_size(format.to_glenum(), width, height, depth, array_size)
             },
         }
     }
@@ -1608,6 +2063,44 @@ impl ClientFormatAny {
             gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatI)),
             gl::COMPRESSED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatUU)),
             gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatII)),
+            gl::COMPRESSED_RGB8_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8)),
+            gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8A1)),
+            gl::COMPRESSED_RGBA8_ETC2_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgba8)),
+            gl::COMPRESSED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacR11U)),
+            gl::COMPRESSED_SIGNED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacR11I)),
+            gl::COMPRESSED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11UU)),
+            gl::COMPRESSED_SIGNED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11II)),
+            gl::COMPRESSED_SRGB8_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8)),
+            gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgb8A1)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Rgba8)),
+            gl::COMPRESSED_RGBA_ASTC_4x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc4x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x5)),
+            gl::COMPRESSED_RGBA_ASTC_8x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x5)),
+            gl::COMPRESSED_RGBA_ASTC_10x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x6)),
+            gl::COMPRESSED_RGBA_ASTC_10x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x12 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x12)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc4x4)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc5x4)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc5x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc6x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc6x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc8x8)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x5)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x6)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x8)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc10x10)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc12x10)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Astc12x12)),
             _ => None,
         }
     }
@@ -276,6 +276,14 @@ impl<'a, T: 'a> IndexBufferSlice<'a, T> where T: Index {
         <T as Index>::get_type()
     }
 
+    /// Builds an index buffer slice from a buffer slice and the type of its primitives.
+    #[inline]
+    pub(crate) fn from_buffer_slice(buffer: BufferSlice<'a, [T]>, primitives: PrimitiveType)
+                                    -> IndexBufferSlice<'a, T>
+    {
+        IndexBufferSlice { buffer, primitives }
+    }
+
     /// Returns `None` if out of range.
     #[inline]
     pub fn slice<R: RangeArgument<usize>>(&self, range: R) -> Option<IndexBufferSlice<'a, T>> {
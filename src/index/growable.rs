@@ -0,0 +1,95 @@
+use std::ops::{Deref, DerefMut};
+use crate::utils::range::RangeArgument;
+
+use crate::buffer::{BufferMode, BufferType, GrowableBuffer};
+use crate::index::{Index, IndexBufferSlice, IndexType, IndicesSource, PrimitiveType};
+use crate::index::buffer::CreationError;
+
+use crate::backend::Facade;
+
+/// A list of indices whose length can change, similar to a `Vec`.
+///
+/// The indices are stored in the graphics card's memory. When the capacity is exceeded, the
+/// indices are moved to a larger buffer by the GPU. See `GrowableBuffer` for the methods that
+/// modify the content.
+#[derive(Debug)]
+pub struct GrowableIndexBuffer<T> where T: Index {
+    buffer: GrowableBuffer<T>,
+    primitives: PrimitiveType,
+}
+
+impl<T> GrowableIndexBuffer<T> where T: Index {
+    /// Builds a new empty index buffer.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F, prim: PrimitiveType)
+                          -> Result<GrowableIndexBuffer<T>, CreationError>
+                          where F: Facade
+    {
+        GrowableIndexBuffer::with_capacity(facade, prim, 0)
+    }
+
+    /// Builds a new empty index buffer that can hold `capacity` indices without reallocating.
+    pub fn with_capacity<F: ?Sized>(facade: &F, prim: PrimitiveType, capacity: usize)
+                                    -> Result<GrowableIndexBuffer<T>, CreationError>
+                                    where F: Facade
+    {
+        if !prim.is_supported(facade) {
+            return Err(CreationError::PrimitiveTypeNotSupported);
+        }
+
+        if !T::is_supported(facade) {
+            return Err(CreationError::IndexTypeNotSupported);
+        }
+
+        Ok(GrowableIndexBuffer {
+            buffer: GrowableBuffer::with_capacity(facade, BufferType::ElementArrayBuffer,
+                                                  capacity, BufferMode::Dynamic)?,
+            primitives: prim,
+        })
+    }
+
+    /// Returns the type of primitives associated with this index buffer.
+    #[inline]
+    pub fn get_primitives_type(&self) -> PrimitiveType {
+        self.primitives
+    }
+
+    /// Returns the data type of the indices inside this index buffer.
+    #[inline]
+    pub fn get_indices_type(&self) -> IndexType {
+        <T as Index>::get_type()
+    }
+
+    /// Returns a slice of the indices, or `None` if out of range.
+    #[inline]
+    pub fn slice<R: RangeArgument<usize>>(&self, range: R) -> Option<IndexBufferSlice<'_, T>> {
+        self.buffer.slice(range).map(|b| IndexBufferSlice::from_buffer_slice(b, self.primitives))
+    }
+}
+
+impl<T> Deref for GrowableIndexBuffer<T> where T: Index {
+    type Target = GrowableBuffer<T>;
+
+    #[inline]
+    fn deref(&self) -> &GrowableBuffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T> DerefMut for GrowableIndexBuffer<T> where T: Index {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut GrowableBuffer<T> {
+        &mut self.buffer
+    }
+}
+
+impl<'a, T> From<&'a GrowableIndexBuffer<T>> for IndicesSource<'a> where T: Index {
+    #[inline]
+    fn from(buf: &'a GrowableIndexBuffer<T>) -> IndicesSource<'a> {
+        IndicesSource::IndexBuffer {
+            buffer: buf.buffer.as_slice().as_slice_any(),
+            data_type: buf.get_indices_type(),
+            primitives: buf.primitives,
+        }
+    }
+}
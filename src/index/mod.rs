@@ -41,10 +41,12 @@ use crate::buffer::BufferAnySlice;
 
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::buffer::CreationError as BufferCreationError;
+pub use self::growable::GrowableIndexBuffer;
 pub use self::multidraw::{DrawCommandsNoIndicesBuffer, DrawCommandNoIndices};
 pub use self::multidraw::{DrawCommandsIndicesBuffer, DrawCommandIndices};
 
 mod buffer;
+mod growable;
 mod multidraw;
 
 /// Describes a source of indices used for drawing.
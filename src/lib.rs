@@ -153,6 +153,7 @@ pub mod framebuffer;
 pub mod index;
 pub mod memory_object;
 pub mod pixel_buffer;
+pub mod profiler;
 pub mod program;
 pub mod uniforms;
 pub mod vertex;
@@ -170,14 +171,31 @@ mod utils;
 mod version;
 mod vertex_array_object;
 
+#[cfg(feature = "trace")]
+pub mod trace;
+
+#[cfg(not(feature = "trace"))]
 mod gl {
     #![allow(clippy::all)]
     include!(concat!(env!("OUT_DIR"), "/gl_bindings.rs"));
 }
 
+#[cfg(feature = "trace")]
+mod gl {
+    #![allow(clippy::all)]
+    mod bindings {
+        include!(concat!(env!("OUT_DIR"), "/gl_bindings.rs"));
+    }
+    pub use self::bindings::*;
+    include!(concat!(env!("OUT_DIR"), "/gl_trace.rs"));
+}
+
 #[doc(hidden)]
 pub use memoffset::offset_of as __glium_offset_of;
 
+#[cfg(feature = "derive")]
+pub use glium_derive::{UniformBlock, Uniforms, Vertex};
+
 /// The main object of this library. Controls the whole display.
 ///
 /// This object contains a smart pointer to the real implementation.
@@ -381,6 +399,19 @@ trait TextureExt {
     /// Prepares the texture to be accessed, after possibly being modified in a shader
     /// with image load/store
     fn prepare_for_access(&self, _: &mut CommandContext<'_>, access_type: TextureAccess);
+
+    /// Returns the sampler behavior that is stored in the parameters of the texture, or `None`
+    /// if they are still the ones the texture was created with.
+    ///
+    /// The parameters of the texture are only modified when sampler objects are not supported.
+    fn get_sampler_parameters(&self) -> Option<uniforms::SamplerBehavior>;
+
+    /// Stores a sampler behavior in the parameters of the texture, or restores the parameters
+    /// the texture was created with if `None` is passed.
+    ///
+    /// *Safety*: the texture must be bound to the active texture unit.
+    unsafe fn set_sampler_parameters(&self, _: &mut CommandContext<'_>,
+                                     behavior: Option<uniforms::SamplerBehavior>);
 }
 
 /// Ways a texture could possibly be accessed after being written to in a shader via an image unit
@@ -1018,6 +1049,25 @@ pub enum DrawError {
         err: uniforms::LayoutMismatchError,
     },
 
+    /// The offset of the buffer bound to a uniform block is not a multiple of the alignment
+    /// required by the backend.
+    UniformBlockOffsetNotAligned {
+        /// Name of the block you are trying to bind.
+        name: String,
+        /// Required alignment in bytes.
+        alignment: usize,
+    },
+
+    /// The buffer bound to a uniform block is too small to contain all the members of the block.
+    UniformBlockBufferTooSmall {
+        /// Name of the block you are trying to bind.
+        name: String,
+        /// Minimum size in bytes required by the layout of the block.
+        expected: usize,
+        /// Size in bytes of the buffer.
+        obtained: usize,
+    },
+
     /// Tried to bind a subroutine uniform like a regular uniform value.
     SubroutineUniformToValue {
         /// Name of the uniform you are trying to bind.
@@ -1055,6 +1105,10 @@ pub enum DrawError {
     /// Trying to use a sampler, but they are not supported by the backend.
     SamplersNotSupported,
 
+    /// The same texture is sampled with different sampler behaviors in the same draw call, but
+    /// the backend doesn't support sampler objects.
+    SamplerBehaviorsConflict,
+
     /// When you use instancing, all vertices sources must have the same size.
     InstancesCountMismatch,
 
@@ -1079,6 +1133,16 @@ pub enum DrawError {
     /// Depth clamping isn't supported by the backend.
     DepthClampNotSupported,
 
+    /// The requested clip origin or clip depth mode isn't supported by the backend.
+    ClipControlNotSupported,
+
+    /// The depth bounds test isn't supported by the backend.
+    DepthBoundsNotSupported,
+
+    /// The depth bounds are outside of the `(0, 1)` range, or the minimum is greater than
+    /// the maximum.
+    InvalidDepthBounds,
+
     /// One of the blending parameters is not supported by the backend.
     BlendingParameterNotSupported,
 
@@ -1090,6 +1154,29 @@ pub enum DrawError {
 
     /// Tried to use too many image units simultaneously
     InsufficientImageUnits,
+
+    /// Per-draw-buffer blending or color masks are not supported by the backend.
+    IndexedBlendingNotSupported,
+
+    /// The per-draw-buffer parameters contain more elements than the maximum number of
+    /// draw buffers.
+    DrawBufferIndexOutOfBounds,
+
+    /// Logical operations are not supported by the backend.
+    LogicOpNotSupported,
+
+    /// Sample masks are not supported by the backend.
+    SampleMaskNotSupported,
+
+    /// Sample shading is not supported by the backend.
+    SampleShadingNotSupported,
+
+    /// Viewport arrays and scissor arrays are not supported by the backend.
+    ViewportArrayNotSupported,
+
+    /// The list of viewports or scissor boxes contains more elements than the maximum number
+    /// of viewports.
+    ViewportIndexOutOfBounds,
 }
 
 impl Error for DrawError {
@@ -1125,6 +1212,10 @@ impl fmt::Display for DrawError {
                 "Tried to bind a single uniform value to a uniform block",
             UniformBlockLayoutMismatch { .. } =>
                 "The layout of the content of the uniform buffer does not match the layout of the block",
+            UniformBlockOffsetNotAligned { .. } =>
+                "The offset of the buffer bound to a uniform block is not correctly aligned",
+            UniformBlockBufferTooSmall { .. } =>
+                "The buffer bound to a uniform block is too small to contain the block",
             SubroutineUniformToValue { .. } =>
                 "Tried to bind a subroutine uniform like a regular uniform value",
             SubroutineUniformMissing { .. } =>
@@ -1139,6 +1230,9 @@ impl fmt::Display for DrawError {
                 "Using a program which contains tessellation shaders, but without submitting patches",
             SamplersNotSupported => "
                 Trying to use a sampler, but they are not supported by the backend",
+            SamplerBehaviorsConflict =>
+                "The same texture is sampled with different sampler behaviors, but sampler \
+                 objects are not supported by the backend",
             InstancesCountMismatch =>
                 "When you use instancing, all vertices sources must have the same size",
             VerticesSourcesLengthMismatch =>
@@ -1155,6 +1249,12 @@ impl fmt::Display for DrawError {
                 "Discarding rasterizer output is not supported by the backend",
             DepthClampNotSupported =>
                 "The depth clamp mode is not supported by the backend",
+            ClipControlNotSupported =>
+                "The clip origin or clip depth mode is not supported by the backend",
+            DepthBoundsNotSupported =>
+                "The depth bounds test is not supported by the backend",
+            InvalidDepthBounds =>
+                "The depth bounds are outside of the `(0, 1)` range or the minimum is greater than the maximum",
             BlendingParameterNotSupported =>
                 "One the blending parameters is not supported by the backend",
             FixedIndexRestartingNotSupported =>
@@ -1163,6 +1263,20 @@ impl fmt::Display for DrawError {
                 "Tried to enable a clip plane that does not exist.",
             InsufficientImageUnits =>
                 "Tried to use more image uniforms that the implementation has support for",
+            IndexedBlendingNotSupported =>
+                "Per-draw-buffer blending or color masks are not supported by the backend",
+            DrawBufferIndexOutOfBounds =>
+                "The per-draw-buffer parameters contain more elements than the maximum number of draw buffers",
+            LogicOpNotSupported =>
+                "Logical operations are not supported by the backend",
+            SampleMaskNotSupported =>
+                "Sample masks are not supported by the backend",
+            SampleShadingNotSupported =>
+                "Sample shading is not supported by the backend",
+            ViewportArrayNotSupported =>
+                "Viewport arrays and scissor arrays are not supported by the backend",
+            ViewportIndexOutOfBounds =>
+                "The list of viewports or scissor boxes contains more elements than the maximum number of viewports",
         };
         match self {
             UniformTypeMismatch { ref name, ref expected } =>
@@ -1195,6 +1309,23 @@ impl fmt::Display for DrawError {
                     name,
                     err,
                 ),
+            UniformBlockOffsetNotAligned { name, alignment } =>
+                write!(
+                    fmt,
+                    "{}: {}, the offset must be a multiple of {} bytes",
+                    desc,
+                    name,
+                    alignment,
+                ),
+            UniformBlockBufferTooSmall { name, expected, obtained } =>
+                write!(
+                    fmt,
+                    "{}: {}, got: {} bytes, expected at least: {} bytes",
+                    desc,
+                    name,
+                    obtained,
+                    expected,
+                ),
             _ =>
                 fmt.write_str(desc),
         }
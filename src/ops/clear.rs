@@ -5,7 +5,7 @@ use crate::ContextExt;
 use crate::Rect;
 
 use crate::QueryExt;
-use crate::draw_parameters::TimeElapsedQuery;
+use crate::draw_parameters::{self, TimeElapsedQuery};
 
 use crate::Api;
 use crate::version::Version;
@@ -27,6 +27,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
             ctxt.state.enabled_rasterizer_discard = false;
         }
 
+        draw_parameters::reset_indexed_color_mask(&mut ctxt);
         if ctxt.state.color_mask != (1, 1, 1, 1) {
             ctxt.state.color_mask = (1, 1, 1, 1);
             ctxt.gl.ColorMask(1, 1, 1, 1);
@@ -54,6 +55,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
             if ctxt.state.scissor != Some(rect) {
                 ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
                 ctxt.state.scissor = Some(rect);
+                ctxt.state.indexed_scissors.clear();
             }
 
             if !ctxt.state.enabled_scissor_test {
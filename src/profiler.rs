@@ -0,0 +1,521 @@
+/*!
+A hierarchical GPU profiler built on timestamp queries.
+
+The profiler measures how long the GPU spends executing named scopes, which can be nested.
+Timestamps are written by the GPU asynchronously, so the timings of a frame are only available
+a few frames later. `GpuProfiler::collect_frames` never blocks and returns the frames whose
+timings are available.
+
+```no_run
+# use glium::Surface;
+# fn example(display: glium::Display) {
+let profiler = glium::profiler::GpuProfiler::new(&display).unwrap();
+
+loop {
+    profiler.begin_frame();
+    let mut target = display.draw();
+
+    {
+        let _scope = profiler.scope("shadows");
+        // draw the shadow maps here
+        {
+            let _scope = profiler.scope("sun");
+            // ...
+        }
+    }
+
+    {
+        let _scope = profiler.scope("scene");
+        target.clear_color(0.0, 0.0, 0.0, 1.0);
+        // draw the scene here
+    }
+
+    target.finish().unwrap();
+    profiler.end_frame();
+
+    for frame in profiler.collect_frames() {
+        for scope in &frame.scopes {
+            println!("frame {}: {} took {:?}", frame.index, scope.name, scope.duration);
+        }
+    }
+}
+# }
+```
+*/
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::iter;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::backend::Facade;
+use crate::buffer::Buffer;
+use crate::context::CommandContext;
+use crate::context::Context;
+use crate::draw_parameters::QueryCreationError;
+use crate::gl;
+use crate::version::Api;
+use crate::version::Version;
+use crate::BufferExt;
+use crate::CapabilitiesSource;
+use crate::ContextExt;
+
+/// Number of frames whose results can be waited for before the oldest one is discarded.
+const DEFAULT_MAX_PENDING_FRAMES: usize = 4;
+
+/// Returns true if the backend supports `GpuProfiler`.
+#[inline]
+pub fn is_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 3, 3) ||
+    context.get_extensions().gl_arb_timer_query ||
+    context.get_extensions().gl_ext_disjoint_timer_query
+}
+
+/// Measures the time that the GPU spends in named scopes.
+///
+/// See the documentation of the `profiler` module.
+pub struct GpuProfiler {
+    context: Rc<Context>,
+    state: RefCell<ProfilerState>,
+}
+
+struct ProfilerState {
+    /// Query objects that can be used for new timestamps.
+    free_queries: Vec<gl::types::GLuint>,
+
+    /// Frame between `begin_frame` and `end_frame`.
+    current_frame: Option<PendingFrame>,
+
+    /// Indices within `current_frame.scopes` of the scopes that are open.
+    open_scopes: Vec<usize>,
+
+    /// Frames that have ended but whose timestamps haven't been read yet, oldest first.
+    pending_frames: VecDeque<PendingFrame>,
+
+    /// Maximum length of `pending_frames`.
+    max_pending_frames: usize,
+
+    /// Index of the next frame.
+    next_frame_index: u64,
+}
+
+struct PendingFrame {
+    index: u64,
+
+    /// Moment on the CPU clock and on the GPU clock when the frame began, if available.
+    calibration: Option<(Instant, u64)>,
+
+    start_query: gl::types::GLuint,
+    end_query: gl::types::GLuint,
+    scopes: Vec<PendingScope>,
+}
+
+struct PendingScope {
+    name: String,
+    parent: Option<usize>,
+    start_query: gl::types::GLuint,
+
+    /// `0` if the scope hasn't been closed before the end of the frame.
+    end_query: gl::types::GLuint,
+}
+
+impl PendingFrame {
+    /// Returns the query objects used by the frame.
+    fn queries(&self) -> impl Iterator<Item = gl::types::GLuint> + '_ {
+        let scopes = self.scopes.iter()
+                                .flat_map(|s| iter::once(s.start_query).chain(iter::once(s.end_query)));
+        iter::once(self.start_query).chain(scopes).chain(iter::once(self.end_query))
+                                    .filter(|&q| q != 0)
+    }
+}
+
+/// Timings of a frame measured by a `GpuProfiler`.
+#[derive(Debug, Clone)]
+pub struct FrameTimings {
+    /// Index of the frame. The first frame has the index `0`, and each call to `begin_frame`
+    /// increments it. Frames that have been discarded create gaps.
+    pub index: u64,
+
+    /// Time spent by the GPU between `begin_frame` and `end_frame`.
+    pub duration: Duration,
+
+    /// Moment, on the CPU clock, when the GPU reached `begin_frame`. `None` if the backend
+    /// can't read the current time of the GPU.
+    ///
+    /// Add the `start` of a scope to this value in order to place the scope on the CPU clock.
+    pub cpu_start: Option<Instant>,
+
+    /// Scopes that aren't nested in another one, in the order of their creation.
+    pub scopes: Vec<ScopeTimings>,
+}
+
+/// Timings of a scope measured by a `GpuProfiler`.
+#[derive(Debug, Clone)]
+pub struct ScopeTimings {
+    /// Name of the scope.
+    pub name: String,
+
+    /// Time between the beginning of the frame and the beginning of the scope.
+    pub start: Duration,
+
+    /// Time spent by the GPU in the scope.
+    pub duration: Duration,
+
+    /// Scopes nested in this one, in the order of their creation.
+    pub children: Vec<ScopeTimings>,
+}
+
+/// A scope opened with `GpuProfiler::scope`. The scope is closed when this object is destroyed.
+///
+/// Destroying a scope also closes the scopes nested in it that are still open.
+pub struct ProfilerScope<'a> {
+    profiler: &'a GpuProfiler,
+    frame: u64,
+    index: usize,
+}
+
+impl<'a> Drop for ProfilerScope<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.profiler.end_scope(self.frame, self.index);
+    }
+}
+
+impl GpuProfiler {
+    /// Builds a new profiler.
+    pub fn new<F: ?Sized>(facade: &F) -> Result<GpuProfiler, QueryCreationError> where F: Facade {
+        if !is_supported(&**facade.get_context()) {
+            return Err(QueryCreationError::NotSupported);
+        }
+
+        Ok(GpuProfiler {
+            context: facade.get_context().clone(),
+            state: RefCell::new(ProfilerState {
+                free_queries: Vec::new(),
+                current_frame: None,
+                open_scopes: Vec::new(),
+                pending_frames: VecDeque::new(),
+                max_pending_frames: DEFAULT_MAX_PENDING_FRAMES,
+                next_frame_index: 0,
+            }),
+        })
+    }
+
+    /// Changes the number of frames whose timings can be waited for. The default is `4`.
+    ///
+    /// When a frame begins while this number of frames are still waiting for their timings,
+    /// the oldest one is discarded so that its query objects can be reused.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `frames` is `0`.
+    pub fn set_max_pending_frames(&self, frames: usize) {
+        assert!(frames >= 1);
+        self.state.borrow_mut().max_pending_frames = frames;
+    }
+
+    /// Begins a new frame.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the previous frame hasn't been ended.
+    pub fn begin_frame(&self) {
+        let mut state = self.state.borrow_mut();
+        assert!(state.current_frame.is_none(), "The previous frame hasn't been ended");
+
+        let mut ctxt = self.context.make_current();
+
+        while state.pending_frames.len() >= state.max_pending_frames {
+            let frame = state.pending_frames.pop_front().unwrap();
+            state.free_queries.extend(frame.queries());
+        }
+
+        let calibration = unsafe { get_gpu_time(&mut ctxt) }.map(|t| (Instant::now(), t));
+        let start_query = unsafe { write_timestamp(&mut ctxt, &mut state.free_queries) };
+
+        let index = state.next_frame_index;
+        state.next_frame_index += 1;
+        state.current_frame = Some(PendingFrame {
+            index,
+            calibration,
+            start_query,
+            end_query: 0,
+            scopes: Vec::new(),
+        });
+    }
+
+    /// Opens a scope with the given name. The scope is closed when the returned object is
+    /// destroyed.
+    ///
+    /// Scopes opened while another scope is open are nested in it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if no frame has begun.
+    pub fn scope(&self, name: &str) -> ProfilerScope<'_> {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        let frame = state.current_frame.as_mut().expect("No frame has begun");
+        let mut ctxt = self.context.make_current();
+        let start_query = unsafe { write_timestamp(&mut ctxt, &mut state.free_queries) };
+
+        let frame_index = frame.index;
+        let index = frame.scopes.len();
+        frame.scopes.push(PendingScope {
+            name: name.to_owned(),
+            parent: state.open_scopes.last().cloned(),
+            start_query,
+            end_query: 0,
+        });
+        state.open_scopes.push(index);
+
+        ProfilerScope {
+            profiler: self,
+            frame: frame_index,
+            index,
+        }
+    }
+
+    fn end_scope(&self, frame_index: u64, index: usize) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        // the scope may belong to a frame that has already ended, or may have been closed along
+        // with a scope it is nested in
+        let frame = match state.current_frame.as_mut() {
+            Some(f) if f.index == frame_index => f,
+            _ => return,
+        };
+
+        let position = match state.open_scopes.iter().position(|&s| s == index) {
+            Some(p) => p,
+            None => return,
+        };
+
+        // closing the nested scopes that are still open as well, innermost first
+        let mut ctxt = self.context.make_current();
+        for scope in state.open_scopes.drain(position ..).rev() {
+            frame.scopes[scope].end_query = unsafe {
+                write_timestamp(&mut ctxt, &mut state.free_queries)
+            };
+        }
+    }
+
+    /// Ends the current frame.
+    ///
+    /// The scopes that are still open, for example because their `ProfilerScope` has been
+    /// leaked, are not part of the timings of the frame. The scopes nested in them are
+    /// reported as if they were nested in their parent instead.
+    ///
+    /// # Panic
+    ///
+    /// Panics if no frame has begun.
+    pub fn end_frame(&self) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        let mut frame = state.current_frame.take().expect("No frame has begun");
+        state.open_scopes.clear();
+
+        let mut ctxt = self.context.make_current();
+        frame.end_query = unsafe { write_timestamp(&mut ctxt, &mut state.free_queries) };
+        state.pending_frames.push_back(frame);
+    }
+
+    /// Returns the timings of the frames that are available, oldest first.
+    ///
+    /// This function never blocks. Each frame is returned only once.
+    ///
+    /// Frames during which the GPU timer has been unreliable, for example because the GPU
+    /// changed its frequency, are discarded.
+    pub fn collect_frames(&self) -> Vec<FrameTimings> {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+
+        let mut ctxt = self.context.make_current();
+        Buffer::<u8>::unbind_query(&mut ctxt);
+
+        let mut ready = Vec::new();
+        while let Some(frame) = state.pending_frames.front() {
+            // timestamps are written in order, so the other ones are available as well
+            if !unsafe { is_query_ready(&mut ctxt, frame.end_query) } {
+                break;
+            }
+
+            ready.push(state.pending_frames.pop_front().unwrap());
+        }
+
+        if ready.is_empty() {
+            return Vec::new();
+        }
+
+        let disjoint = ctxt.extensions.gl_ext_disjoint_timer_query && unsafe {
+            let mut value = 0;
+            ctxt.gl.GetIntegerv(gl::GPU_DISJOINT_EXT, &mut value);
+            value != 0
+        };
+
+        let mut result = Vec::with_capacity(ready.len());
+        for frame in ready {
+            if !disjoint {
+                result.push(unsafe { read_frame(&mut ctxt, &frame) });
+            }
+
+            state.free_queries.extend(frame.queries());
+        }
+
+        result
+    }
+}
+
+impl Drop for GpuProfiler {
+    fn drop(&mut self) {
+        let state = self.state.get_mut();
+        let mut queries = state.free_queries.clone();
+        for frame in state.pending_frames.iter().chain(state.current_frame.as_ref()) {
+            queries.extend(frame.queries());
+        }
+
+        if queries.is_empty() {
+            return;
+        }
+
+        let ctxt = self.context.make_current();
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+                ctxt.gl.DeleteQueries(queries.len() as gl::types::GLsizei, queries.as_ptr());
+            } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+                ctxt.gl.DeleteQueriesEXT(queries.len() as gl::types::GLsizei, queries.as_ptr());
+            } else {
+                unreachable!();
+            }
+        }
+    }
+}
+
+/// Writes the current time of the GPU to a query object, taken from `free_queries` if possible,
+/// and returns its id.
+unsafe fn write_timestamp(ctxt: &mut CommandContext<'_>, free_queries: &mut Vec<gl::types::GLuint>)
+                          -> gl::types::GLuint
+{
+    if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+        let id = free_queries.pop().unwrap_or_else(|| {
+            let mut id = 0;
+            ctxt.gl.GenQueries(1, &mut id);
+            id
+        });
+
+        ctxt.gl.QueryCounter(id, gl::TIMESTAMP);
+        id
+
+    } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+        let id = free_queries.pop().unwrap_or_else(|| {
+            let mut id = 0;
+            ctxt.gl.GenQueriesEXT(1, &mut id);
+            id
+        });
+
+        ctxt.gl.QueryCounterEXT(id, gl::TIMESTAMP_EXT);
+        id
+
+    } else {
+        unreachable!();
+    }
+}
+
+/// Returns the current time of the GPU in nanoseconds, or `None` if it can't be read.
+unsafe fn get_gpu_time(ctxt: &mut CommandContext<'_>) -> Option<u64> {
+    if ctxt.version >= &Version(Api::Gl, 3, 3) ||
+       (ctxt.version >= &Version(Api::GlEs, 3, 0) && ctxt.extensions.gl_ext_disjoint_timer_query)
+    {
+        let mut value = 0;
+        ctxt.gl.GetInteger64v(gl::TIMESTAMP, &mut value);
+        Some(value as u64)
+    } else {
+        None
+    }
+}
+
+unsafe fn is_query_ready(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) -> bool {
+    let mut value = 0;
+
+    if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+        ctxt.gl.GetQueryObjectiv(id, gl::QUERY_RESULT_AVAILABLE, &mut value);
+    } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+        ctxt.gl.GetQueryObjectivEXT(id, gl::QUERY_RESULT_AVAILABLE_EXT, &mut value);
+    } else {
+        unreachable!();
+    }
+
+    value != 0
+}
+
+unsafe fn get_query_result(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) -> u64 {
+    let mut value = 0;
+
+    if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
+        ctxt.gl.GetQueryObjectui64v(id, gl::QUERY_RESULT, &mut value);
+    } else if ctxt.extensions.gl_ext_disjoint_timer_query {
+        ctxt.gl.GetQueryObjectui64vEXT(id, gl::QUERY_RESULT_EXT, &mut value);
+    } else {
+        unreachable!();
+    }
+
+    value
+}
+
+/// Reads the timestamps of a frame whose queries are all available.
+unsafe fn read_frame(ctxt: &mut CommandContext<'_>, frame: &PendingFrame) -> FrameTimings {
+    let start = get_query_result(ctxt, frame.start_query);
+    let end = get_query_result(ctxt, frame.end_query);
+
+    let scopes = frame.scopes.iter().map(|scope| {
+        if scope.end_query == 0 {
+            None
+        } else {
+            Some((get_query_result(ctxt, scope.start_query), get_query_result(ctxt, scope.end_query)))
+        }
+    }).collect::<Vec<_>>();
+
+    let cpu_start = frame.calibration.map(|(cpu, gpu)| {
+        if start >= gpu {
+            cpu + Duration::from_nanos(start - gpu)
+        } else {
+            cpu.checked_sub(Duration::from_nanos(gpu - start)).unwrap_or(cpu)
+        }
+    });
+
+    FrameTimings {
+        index: frame.index,
+        duration: Duration::from_nanos(end.saturating_sub(start)),
+        cpu_start,
+        scopes: build_scopes(&frame.scopes, &scopes, start, None),
+    }
+}
+
+/// Builds the tree of the scopes whose parent is `parent`.
+///
+/// The scopes that haven't been closed are skipped, and their children take their place.
+fn build_scopes(scopes: &[PendingScope], timestamps: &[Option<(u64, u64)>], frame_start: u64,
+                parent: Option<usize>) -> Vec<ScopeTimings>
+{
+    let mut result = Vec::new();
+
+    for (index, scope) in scopes.iter().enumerate().filter(|&(_, scope)| scope.parent == parent) {
+        let children = build_scopes(scopes, timestamps, frame_start, Some(index));
+
+        match timestamps[index] {
+            Some((start, end)) => result.push(ScopeTimings {
+                name: scope.name.clone(),
+                start: Duration::from_nanos(start.saturating_sub(frame_start)),
+                duration: Duration::from_nanos(end.saturating_sub(start)),
+                children,
+            }),
+            None => result.extend(children),
+        }
+    }
+
+    result
+}
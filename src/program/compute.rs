@@ -2,6 +2,7 @@ use crate::gl;
 
 use crate::context::CommandContext;
 use crate::backend::Facade;
+use crate::debug::LabelNotSupportedError;
 
 use std::fmt;
 use std::collections::hash_map::{self, HashMap};
@@ -17,6 +18,7 @@ use crate::Handle;
 use crate::RawUniformValue;
 
 use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary, GetBinaryError, SpirvEntryPoint};
+use crate::program::SpecializationConstant;
 
 use crate::program::reflection::{Uniform, UniformBlock};
 use crate::program::reflection::{ShaderStage, SubroutineData};
@@ -25,6 +27,8 @@ use crate::program::shader::{build_shader, build_spirv_shader, check_shader_type
 use crate::program::raw::RawProgram;
 
 use crate::buffer::BufferSlice;
+use crate::draw_parameters::PipelineStatisticsQueryParam;
+use crate::DrawError;
 use crate::uniforms::Uniforms;
 
 /// A combination of compute shaders linked together.
@@ -57,10 +61,22 @@ impl ComputeShader {
     #[inline]
     pub fn from_spirv<F: ?Sized>(facade: &F, spirv: &SpirvEntryPoint) -> Result<ComputeShader, ProgramCreationError>
                           where F: Facade
+    {
+        ComputeShader::from_spirv_specialized(facade, spirv, &[])
+    }
+
+    /// Builds a new compute shader from SPIR-V module, with some specialization constants.
+    ///
+    /// The constants are indexed by their constant ID. The constants that are not in the list
+    /// keep their default value.
+    pub fn from_spirv_specialized<F: ?Sized>(facade: &F, spirv: &SpirvEntryPoint,
+                                             specialization_constants: &[(u32, SpecializationConstant)])
+                                             -> Result<ComputeShader, ProgramCreationError>
+                                             where F: Facade
     {
         let _lock = COMPILER_GLOBAL_LOCK.lock();
 
-        let shader = build_spirv_shader(facade, gl::COMPUTE_SHADER, spirv)?;
+        let shader = build_spirv_shader(facade, gl::COMPUTE_SHADER, spirv, specialization_constants)?;
 
         Ok(ComputeShader {
             raw: RawProgram::from_shaders(facade, &[shader], false, false, false, None)?
@@ -86,7 +102,20 @@ impl ComputeShader {
     /// depending on the attributes of the compute shader itself.
     #[inline]
     pub fn execute<U>(&self, uniforms: U, x: u32, y: u32, z: u32) where U: Uniforms {
-        unsafe { self.raw.dispatch_compute(uniforms, x, y, z) }.unwrap();       // FIXME: return error
+        unsafe { self.raw.dispatch_compute(uniforms, x, y, z, &[]) }.unwrap();       // FIXME: return error
+    }
+
+    /// Executes the compute shader while the given pipeline statistics queries are active.
+    ///
+    /// This is similar to `execute`. Use a `ComputeShaderInvocationsQuery` to know the number
+    /// of times the compute shader has been invoked. Only one query per statistic can be used,
+    /// otherwise a `WrongQueryOperation` error is returned.
+    #[inline]
+    pub fn execute_with_queries<U>(&self, uniforms: U, x: u32, y: u32, z: u32,
+                                   queries: &[PipelineStatisticsQueryParam<'_>])
+                                   -> Result<(), DrawError> where U: Uniforms
+    {
+        unsafe { self.raw.dispatch_compute(uniforms, x, y, z, queries) }
     }
 
     /// Executes the compute shader.
@@ -96,7 +125,19 @@ impl ComputeShader {
     pub fn execute_indirect<U>(&self, uniforms: U, buffer: BufferSlice<'_, ComputeCommand>)
                                where U: Uniforms
     {
-        unsafe { self.raw.dispatch_compute_indirect(uniforms, buffer) }.unwrap();       // FIXME: return error
+        unsafe { self.raw.dispatch_compute_indirect(uniforms, buffer, &[]) }.unwrap();       // FIXME: return error
+    }
+
+    /// Executes the compute shader while the given pipeline statistics queries are active.
+    ///
+    /// This is similar to `execute_indirect`. See `execute_with_queries`.
+    #[inline]
+    pub fn execute_indirect_with_queries<U>(&self, uniforms: U,
+                                            buffer: BufferSlice<'_, ComputeCommand>,
+                                            queries: &[PipelineStatisticsQueryParam<'_>])
+                                            -> Result<(), DrawError> where U: Uniforms
+    {
+        unsafe { self.raw.dispatch_compute_indirect(uniforms, buffer, queries) }
     }
 
     /// Returns the program's compiled binary.
@@ -108,6 +149,16 @@ impl ComputeShader {
         self.raw.get_binary()
     }
 
+    /// Gives a label to the compute shader, which is shown by debuggers such as RenderDoc and in
+    /// `Context::get_resources_report`.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`, in which case the label
+    /// is only shown in the resources report.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.raw.set_label(label)
+    }
+
     /// Returns informations about a uniform variable, if it exists.
     #[inline]
     pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
@@ -14,6 +14,7 @@ pub use self::program::Program;
 pub use self::reflection::{Uniform, UniformBlock, BlockLayout, OutputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 pub use self::reflection::{ShaderStage, SubroutineData, SubroutineUniform};
+pub(crate) use self::raw::{ProgramRecipe, recreate_program};
 
 mod compute;
 mod program;
@@ -135,6 +136,12 @@ pub enum ProgramCreationError {
 
     /// The glium-specific binary header was not found or is corrupt.
     BinaryHeaderError,
+
+    /// Error while specializing one of the SPIR-V shaders.
+    ///
+    /// This happens if the same constant ID is specialized more than once, or if the entry
+    /// point doesn't exist in the module.
+    SpecializationError(String, ShaderType),
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -163,12 +170,24 @@ impl fmt::Display for ProgramCreationError {
                 "Point size is not supported by the backend.",
             BinaryHeaderError =>
                 "The glium-specific binary header was not found or is corrupt.",
+            SpecializationError(_, typ) => {
+                match typ {
+                    ShaderType::Vertex => "Specialization error in vertex shader",
+                    ShaderType::Geometry => "Specialization error in geometry shader",
+                    ShaderType::Fragment => "Specialization error in fragment shader",
+                    ShaderType::TesselationControl => "Specialization error in tesselation control shader",
+                    ShaderType::TesselationEvaluation => "Specialization error in tesselation evaluation shader",
+                    ShaderType::Compute => "Specialization error in compute shader"
+                }
+            },
         };
         match *self {
             CompilationError(ref s, _) =>
                 write!(fmt, "{}: {}", desc, s),
             LinkingError(ref s) =>
                 write!(fmt, "{}: {}", desc, s),
+            SpecializationError(ref s, _) =>
+                write!(fmt, "{}: {}", desc, s),
             _ =>
                 write!(fmt, "{}", desc),
         }
@@ -318,6 +337,12 @@ pub struct SpirvProgram<'a> {
 
     /// Whether the shader uses point size.
     pub uses_point_size: bool,
+
+    /// Values of the specialization constants, indexed by their constant ID.
+    ///
+    /// Each shader only receives the constants that are declared in its module. The constants
+    /// that are not in this list keep their default value.
+    pub specialization_constants: &'a [(u32, SpecializationConstant)],
 }
 
 impl<'a> SpirvProgram<'a> {
@@ -335,6 +360,7 @@ impl<'a> SpirvProgram<'a> {
             transform_feedback_varyings: None,
             outputs_srgb: false,
             uses_point_size: false,
+            specialization_constants: &[],
         }
     }
 
@@ -373,6 +399,12 @@ impl<'a> SpirvProgram<'a> {
         self.uses_point_size = uses_point_size;
         self
     }
+
+    /// Builder method to set `specialization_constants`.
+    pub fn specialization_constants(mut self, specialization_constants: &'a [(u32, SpecializationConstant)]) -> Self {
+        self.specialization_constants = specialization_constants;
+        self
+    }
 }
 
 /// Represents an entry point of a binary SPIR-V module.
@@ -385,6 +417,34 @@ pub struct SpirvEntryPoint<'a> {
     pub entry_point: &'a str,
 }
 
+/// Value of a specialization constant of a SPIR-V module.
+///
+/// The type must match the type of the constant declared in the module.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SpecializationConstant {
+    /// A `bool` constant.
+    Bool(bool),
+    /// An `int` constant.
+    Int(i32),
+    /// A `uint` constant.
+    UnsignedInt(u32),
+    /// A `float` constant.
+    Float(f32),
+}
+
+impl SpecializationConstant {
+    /// Returns the 32 bits that are passed to OpenGL for this value.
+    #[inline]
+    fn to_bits(&self) -> u32 {
+        match *self {
+            SpecializationConstant::Bool(value) => value as u32,
+            SpecializationConstant::Int(value) => value as u32,
+            SpecializationConstant::UnsignedInt(value) => value,
+            SpecializationConstant::Float(value) => value.to_bits(),
+        }
+    }
+}
+
 /// Represents the source code of a program.
 pub struct SourceCode<'a> {
     /// Source code of the vertex shader.
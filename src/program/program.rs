@@ -6,6 +6,7 @@ use crate::version::Api;
 
 use crate::backend::Facade;
 use crate::CapabilitiesSource;
+use crate::debug::LabelNotSupportedError;
 
 use std::fmt;
 use std::collections::hash_map::{self, HashMap};
@@ -113,7 +114,8 @@ impl Program {
             ProgramCreationInput::SpirV(SpirvProgram { vertex_shader, tessellation_control_shader,
                                                tessellation_evaluation_shader, geometry_shader,
                                                fragment_shader, transform_feedback_varyings,
-                                               outputs_srgb, uses_point_size }) =>
+                                               outputs_srgb, uses_point_size,
+                                               specialization_constants }) =>
             {
                 let mut has_geometry_shader = false;
                 let mut has_tessellation_control_shader = false;
@@ -156,7 +158,8 @@ impl Program {
                 let shaders_store = {
                     let mut shaders_store = Vec::new();
                     for (src, ty) in shaders.into_iter() {
-                        shaders_store.push(build_spirv_shader(facade, ty.to_opengl_type(), &src)?);
+                        shaders_store.push(build_spirv_shader(facade, ty.to_opengl_type(), &src,
+                                                               specialization_constants)?);
                     }
                     shaders_store
                 };
@@ -220,6 +223,16 @@ impl Program {
         self.raw.get_binary()
     }
 
+    /// Gives a label to the program, which is shown by debuggers such as RenderDoc and in
+    /// `Context::get_resources_report`.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`, in which case the label
+    /// is only shown in the resources report.
+    #[inline]
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.raw.set_label(label)
+    }
+
     /// Returns the *location* of an output fragment, if it exists.
     ///
     /// The *location* is low-level information that is used internally by glium.
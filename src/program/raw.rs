@@ -6,13 +6,15 @@ use crate::version::Api;
 
 use crate::backend::Facade;
 use crate::context::Context;
+use crate::context::ResourceKind;
+use crate::debug::{self, LabelNotSupportedError};
 use crate::ContextExt;
 use crate::UniformsExt;
 
-use std::{ffi, fmt};
+use std::{ffi, fmt, ptr};
 use std::collections::hash_map::{self, HashMap};
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::os::raw;
 use std::hash::BuildHasherDefault;
 
@@ -26,6 +28,7 @@ use crate::RawUniformValue;
 
 use crate::QueryExt;
 use crate::draw_parameters::TimeElapsedQuery;
+use crate::draw_parameters::{PipelineStatisticsQueryParam, sync_pipeline_statistics_queries};
 
 use crate::buffer::BufferSlice;
 use crate::BufferExt;
@@ -55,6 +58,8 @@ pub struct RawProgram {
     context: Rc<Context>,
     id: Handle,
     uniform_values: UniformsStorage,
+    /// Value of `Context::get_recovery_count` when `uniform_values` was last valid.
+    recoveries: Cell<u64>,
     uniforms: HashMap<String, Uniform, BuildHasherDefault<FnvHasher>>,
     uniform_blocks: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
     subroutine_data: SubroutineData,
@@ -103,40 +108,14 @@ impl RawProgram {
             }
 
             // transform feedback varyings
-            if let Some((names, mode)) = transform_feedback {
+            if let Some((ref names, mode)) = transform_feedback {
                 let id = match id {
                     Handle::Id(id) => id,
                     Handle::Handle(id) => unreachable!()    // transf. feedback shouldn't be
                                                             // available with handles
                 };
 
-                let names = names.into_iter().map(|name| {
-                    ffi::CString::new(name.into_bytes()).unwrap()
-                }).collect::<Vec<_>>();
-                let names_ptr = names.iter().map(|n| n.as_ptr()).collect::<Vec<_>>();
-
-                if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                    let mode = match mode {
-                        TransformFeedbackMode::Interleaved => gl::INTERLEAVED_ATTRIBS,
-                        TransformFeedbackMode::Separate => gl::SEPARATE_ATTRIBS,
-                    };
-
-                    ctxt.gl.TransformFeedbackVaryings(id, names_ptr.len() as gl::types::GLsizei,
-                                                      names_ptr.as_ptr(), mode);
-
-                } else if ctxt.extensions.gl_ext_transform_feedback {
-                    let mode = match mode {
-                        TransformFeedbackMode::Interleaved => gl::INTERLEAVED_ATTRIBS_EXT,
-                        TransformFeedbackMode::Separate => gl::SEPARATE_ATTRIBS_EXT,
-                    };
-
-                    ctxt.gl.TransformFeedbackVaryingsEXT(id, names_ptr.len()
-                                                         as gl::types::GLsizei,
-                                                         names_ptr.as_ptr(), mode);
-
-                } else {
-                    unreachable!();     // has been checked in the frontend
-                }
+                set_transform_feedback_varyings(&mut ctxt, id, names, mode);
             }
 
             // linking
@@ -161,6 +140,21 @@ impl RawProgram {
             // checking for errors
             check_program_link_errors(&mut ctxt, id)?;
 
+            if let Handle::Id(id) = id {
+                let size = get_program_binary_length(&mut ctxt, id);
+                let mut registry = facade.get_context().resource_registry();
+
+                let recipe = if registry.is_recovery_enabled() {
+                    get_shader_sources(&mut ctxt, &shaders_ids).map(|shaders| {
+                        ProgramRecipe::Shaders { shaders, transform_feedback }
+                    })
+                } else {
+                    None
+                };
+
+                registry.insert_program(id, size, recipe);
+            }
+
             id
         };
 
@@ -188,6 +182,7 @@ impl RawProgram {
             id,
             uniforms,
             uniform_values: UniformsStorage::new(),
+            recoveries: Cell::new(facade.get_context().get_recovery_count()),
             uniform_blocks: blocks,
             subroutine_data,
             attributes,
@@ -231,6 +226,22 @@ impl RawProgram {
             // checking for errors
             check_program_link_errors(&mut ctxt, id)?;
 
+            if let Handle::Id(id) = id {
+                let size = get_program_binary_length(&mut ctxt, id);
+                let mut registry = facade.get_context().resource_registry();
+
+                let recipe = if registry.is_recovery_enabled() {
+                    Some(ProgramRecipe::Binary {
+                        format: binary.format,
+                        content: binary.content[1..].to_vec(),
+                    })
+                } else {
+                    None
+                };
+
+                registry.insert_program(id, size, recipe);
+            }
+
             id
         };
 
@@ -260,6 +271,7 @@ impl RawProgram {
             id,
             uniforms,
             uniform_values: UniformsStorage::new(),
+            recoveries: Cell::new(facade.get_context().get_recovery_count()),
             uniform_blocks: blocks,
             subroutine_data,
             attributes,
@@ -274,6 +286,18 @@ impl RawProgram {
         })
     }
 
+    /// Returns the cache of the uniform values, after emptying it if the program has been
+    /// recreated since it was filled.
+    fn uniform_values(&self) -> &UniformsStorage {
+        let recoveries = self.context.get_recovery_count();
+        if self.recoveries.get() != recoveries {
+            self.uniform_values.clear();
+            self.recoveries.set(recoveries);
+        }
+
+        &self.uniform_values
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -316,6 +340,21 @@ impl RawProgram {
         }
     }
 
+    /// Gives a label to the program, which is shown by debuggers and in the resources report
+    /// of the context.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        let id = match self.id {
+            Handle::Id(id) => id,
+            Handle::Handle(_) => return Err(LabelNotSupportedError),
+        };
+
+        self.context.resource_registry().set_label(ResourceKind::Program, id,
+                                                   Some(label.to_owned()));
+
+        let mut ctxt = self.context.make_current();
+        unsafe { debug::set_object_label(&mut ctxt, gl::PROGRAM, id, label) }
+    }
+
     /// Returns the *location* of an output fragment, if it exists.
     ///
     /// The *location* is low-level information that is used internally by glium.
@@ -545,7 +584,8 @@ impl RawProgram {
     ///
     /// The program *must* contain a compute shader.
     /// TODO: check inside the program if it has a compute shader instead of being unsafe
-    pub unsafe fn dispatch_compute<U>(&self, uniforms: U, x: u32, y: u32, z: u32)
+    pub unsafe fn dispatch_compute<U>(&self, uniforms: U, x: u32, y: u32, z: u32,
+                                      queries: &[PipelineStatisticsQueryParam<'_>])
                                       -> Result<(), DrawError>      // TODO: other error?
                                       where U: Uniforms
     {
@@ -561,6 +601,7 @@ impl RawProgram {
                 ctxt.extensions.gl_arb_compute_shader);
 
         TimeElapsedQuery::end_conditional_render(&mut ctxt);
+        sync_pipeline_statistics_queries(&mut ctxt, queries)?;
 
         let mut fences = Vec::with_capacity(0);
 
@@ -584,7 +625,8 @@ impl RawProgram {
     /// The program *must* contain a compute shader.
     /// TODO: check inside the program if it has a compute shader instead of being unsafe
     pub unsafe fn dispatch_compute_indirect<U>(&self, uniforms: U,
-                                               buffer: BufferSlice<'_, ComputeCommand>)
+                                               buffer: BufferSlice<'_, ComputeCommand>,
+                                               queries: &[PipelineStatisticsQueryParam<'_>])
                                                -> Result<(), DrawError>      // TODO: other error?
                                                where U: Uniforms
     {
@@ -595,6 +637,7 @@ impl RawProgram {
                 ctxt.extensions.gl_arb_compute_shader);
 
         TimeElapsedQuery::end_conditional_render(&mut ctxt);
+        sync_pipeline_statistics_queries(&mut ctxt, queries)?;
 
         buffer.prepare_and_bind_for_dispatch_indirect(&mut ctxt);
         let offset = buffer.get_offset_bytes();
@@ -658,14 +701,14 @@ impl ProgramExt for RawProgram {
     fn set_uniform(&self, ctxt: &mut CommandContext<'_>, uniform_location: gl::types::GLint,
                    value: &RawUniformValue)
     {
-        self.uniform_values.set_uniform_value(ctxt, self.id, uniform_location, value);
+        self.uniform_values().set_uniform_value(ctxt, self.id, uniform_location, value);
     }
 
     #[inline]
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext<'_>, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
     {
-        self.uniform_values.set_uniform_block_binding(ctxt, self.id, block_location, value);
+        self.uniform_values().set_uniform_block_binding(ctxt, self.id, block_location, value);
     }
 
     #[inline]
@@ -673,7 +716,7 @@ impl ProgramExt for RawProgram {
                                         block_location: gl::types::GLuint,
                                         value: gl::types::GLuint)
     {
-        self.uniform_values.set_shader_storage_block_binding(ctxt, self.id, block_location, value);
+        self.uniform_values().set_shader_storage_block_binding(ctxt, self.id, block_location, value);
     }
 
     #[inline]
@@ -681,7 +724,7 @@ impl ProgramExt for RawProgram {
                                          stage: ShaderStage,
                                          indices: &[gl::types::GLuint])
     {
-        self.uniform_values.set_subroutine_uniforms_for_stage(ctxt, self.id, stage, indices);
+        self.uniform_values().set_subroutine_uniforms_for_stage(ctxt, self.id, stage, indices);
     }
 
     #[inline]
@@ -732,6 +775,8 @@ impl Drop for RawProgram {
                     }
 
                     ctxt.gl.DeleteProgram(id);
+
+                    self.context.resource_registry().remove(ResourceKind::Program, id);
                 },
                 Handle::Handle(id) => {
                     assert!(ctxt.extensions.gl_arb_shader_objects);
@@ -767,6 +812,154 @@ unsafe fn create_program(ctxt: &mut CommandContext<'_>) -> Handle {
     id
 }
 
+/// Returns the size in bytes of the binary of a linked program, or `0` if it is unknown.
+unsafe fn get_program_binary_length(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) -> usize {
+    if ctxt.version >= &Version(Api::Gl, 4, 1) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_arb_get_programy_binary
+    {
+        let mut length: gl::types::GLint = 0;
+        ctxt.gl.GetProgramiv(id, gl::PROGRAM_BINARY_LENGTH, &mut length);
+        length as usize
+    } else {
+        0
+    }
+}
+
+/// Sets the varyings that are captured by transform feedback. The program must not be linked yet.
+unsafe fn set_transform_feedback_varyings(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint,
+                                          names: &[String], mode: TransformFeedbackMode)
+{
+    let names = names.iter().map(|name| {
+        ffi::CString::new(name.as_bytes()).unwrap()
+    }).collect::<Vec<_>>();
+    let names_ptr = names.iter().map(|n| n.as_ptr()).collect::<Vec<_>>();
+
+    if ctxt.version >= &Version(Api::Gl, 3, 0) {
+        let mode = match mode {
+            TransformFeedbackMode::Interleaved => gl::INTERLEAVED_ATTRIBS,
+            TransformFeedbackMode::Separate => gl::SEPARATE_ATTRIBS,
+        };
+
+        ctxt.gl.TransformFeedbackVaryings(id, names_ptr.len() as gl::types::GLsizei,
+                                          names_ptr.as_ptr(), mode);
+
+    } else if ctxt.extensions.gl_ext_transform_feedback {
+        let mode = match mode {
+            TransformFeedbackMode::Interleaved => gl::INTERLEAVED_ATTRIBS_EXT,
+            TransformFeedbackMode::Separate => gl::SEPARATE_ATTRIBS_EXT,
+        };
+
+        ctxt.gl.TransformFeedbackVaryingsEXT(id, names_ptr.len()
+                                             as gl::types::GLsizei,
+                                             names_ptr.as_ptr(), mode);
+
+    } else {
+        unreachable!();     // has been checked in the frontend
+    }
+}
+
+/// What is needed to create a program again after a context loss.
+pub enum ProgramRecipe {
+    /// The program is compiled and linked from the source code of its shaders.
+    Shaders {
+        /// Type and source code of each shader.
+        shaders: Vec<(gl::types::GLenum, ffi::CString)>,
+        /// The varyings captured by transform feedback.
+        transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>,
+    },
+
+    /// The program is loaded from a binary, without the glium header.
+    Binary {
+        format: gl::types::GLenum,
+        content: Vec<u8>,
+    },
+}
+
+/// Returns the type and the source code of each shader, or `None` if one of them has no source
+/// code, for example because it has been built from SPIR-V.
+unsafe fn get_shader_sources(ctxt: &mut CommandContext<'_>, shaders: &[Handle])
+                             -> Option<Vec<(gl::types::GLenum, ffi::CString)>>
+{
+    shaders.iter().map(|&shader| {
+        let shader = match shader {
+            Handle::Id(id) => id,
+            Handle::Handle(_) => return None,
+        };
+
+        let mut ty: gl::types::GLint = 0;
+        ctxt.gl.GetShaderiv(shader, gl::SHADER_TYPE, &mut ty);
+
+        // the length includes the null terminator
+        let mut length: gl::types::GLint = 0;
+        ctxt.gl.GetShaderiv(shader, gl::SHADER_SOURCE_LENGTH, &mut length);
+        if length <= 1 {
+            return None;
+        }
+
+        let mut source: Vec<u8> = Vec::with_capacity(length as usize);
+        ctxt.gl.GetShaderSource(shader, length, &mut length,
+                                source.as_mut_ptr() as *mut gl::types::GLchar);
+        source.set_len(length as usize);
+
+        ffi::CString::new(source).ok().map(|source| (ty as gl::types::GLenum, source))
+    }).collect()
+}
+
+/// Creates a program again after a context loss. The name must have been obtained with
+/// `glCreateProgram` in the current context.
+///
+/// Returns false if the program couldn't be recreated.
+pub unsafe fn recreate_program(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint,
+                               recipe: &ProgramRecipe) -> bool
+{
+    match *recipe {
+        ProgramRecipe::Shaders { ref shaders, ref transform_feedback } => {
+            let mut shader_ids = Vec::with_capacity(shaders.len());
+
+            ctxt.report_debug_output_errors.set(false);
+
+            for &(ty, ref source) in shaders.iter() {
+                let shader = ctxt.gl.CreateShader(ty);
+                if shader == 0 {
+                    continue;
+                }
+
+                ctxt.gl.ShaderSource(shader, 1, [ source.as_ptr() ].as_ptr(), ptr::null());
+                ctxt.gl.CompileShader(shader);
+                ctxt.gl.AttachShader(id, shader);
+                shader_ids.push(shader);
+            }
+
+            if let Some((ref names, mode)) = *transform_feedback {
+                set_transform_feedback_varyings(ctxt, id, names, mode);
+            }
+
+            // compilation errors are reported by the link status
+            ctxt.gl.LinkProgram(id);
+            ctxt.report_debug_output_errors.set(true);
+
+            for shader in shader_ids {
+                ctxt.gl.DetachShader(id, shader);
+                ctxt.gl.DeleteShader(shader);
+            }
+        },
+
+        ProgramRecipe::Binary { format, ref content } => {
+            if !(ctxt.version >= &Version(Api::Gl, 4, 1) ||
+                 ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                 ctxt.extensions.gl_arb_get_programy_binary)
+            {
+                return false;
+            }
+
+            ctxt.gl.ProgramBinary(id, format, content.as_ptr() as *const _,
+                                  content.len() as gl::types::GLsizei);
+        },
+    }
+
+    check_program_link_errors(ctxt, Handle::Id(id)).is_ok()
+}
+
 unsafe fn check_program_link_errors(ctxt: &mut CommandContext<'_>, id: Handle)
                                     -> Result<(), ProgramCreationError>
 {
@@ -105,6 +105,40 @@ pub enum BlockLayout {
     },
 }
 
+impl BlockLayout {
+    /// Returns the number of bytes that a buffer needs in order to contain all the members of
+    /// the layout.
+    ///
+    /// The stride of arrays is not known, so their elements are assumed to be tightly packed.
+    /// The returned value is therefore a lower bound.
+    pub(crate) fn get_min_size(&self) -> usize {
+        self.get_bounds().map(|(_, end)| end).unwrap_or(0)
+    }
+
+    /// Returns the offsets of the first and past-the-end bytes of the layout, or `None` if it
+    /// is empty.
+    fn get_bounds(&self) -> Option<(usize, usize)> {
+        match *self {
+            BlockLayout::BasicType { ty, offset_in_buffer } => {
+                Some((offset_in_buffer, offset_in_buffer + ty.get_size_bytes()))
+            },
+            BlockLayout::Struct { ref members } => {
+                members.iter().filter_map(|&(_, ref member)| member.get_bounds())
+                       .fold(None, |bounds, (start, end)| match bounds {
+                           Some((s, e)) => Some((cmp::min(s, start), cmp::max(e, end))),
+                           None => Some((start, end)),
+                       })
+            },
+            BlockLayout::Array { ref content, length } => {
+                content.get_bounds().map(|(start, end)| (start, start + (end - start) * length))
+            },
+            BlockLayout::DynamicSizedArray { ref content } => {
+                content.get_bounds().map(|(start, _)| (start, start))
+            },
+        }
+    }
+}
+
 /// Information about an attribute of a program (except its name).
 ///
 /// Internal struct. Not public.
@@ -13,7 +13,7 @@ use std::rc::Rc;
 use crate::GlObject;
 use crate::Handle;
 
-use crate::program::{ProgramCreationError, ShaderType, SpirvEntryPoint};
+use crate::program::{ProgramCreationError, ShaderType, SpirvEntryPoint, SpecializationConstant};
 
 /// A single, compiled but unlinked, shader.
 pub struct Shader {
@@ -183,8 +183,11 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
 }
 
 /// Builds an individual shader from a SPIR-V binary.
-pub fn build_spirv_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, spirv: &SpirvEntryPoint)
-                       -> Result<Shader, ProgramCreationError> where F: Facade
+///
+/// Only the specialization constants that are declared in the module are passed to OpenGL.
+pub fn build_spirv_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, spirv: &SpirvEntryPoint,
+                                     specialization_constants: &[(u32, SpecializationConstant)])
+                                     -> Result<Shader, ProgramCreationError> where F: Facade
 {
     unsafe {
         let ctxt = facade.get_context().make_current();
@@ -197,6 +200,22 @@ pub fn build_spirv_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum,
             return Err(ProgramCreationError::ShaderTypeNotSupported);
         }
 
+        for (index, &(id, _)) in specialization_constants.iter().enumerate() {
+            if specialization_constants[.. index].iter().any(|&(other, _)| other == id) {
+                return Err(ProgramCreationError::SpecializationError(
+                    format!("The constant {} is specialized more than once", id),
+                    ShaderType::from_opengl_type(shader_type)));
+            }
+        }
+
+        // `glSpecializeShader` fails if a constant isn't declared in the module, but the same
+        // list is used for all the shaders of a program
+        let declared_ids = spirv_specialization_constant_ids(spirv.binary);
+        let (constant_ids, constant_values): (Vec<_>, Vec<_>) = specialization_constants.iter()
+            .filter(|&&(id, _)| declared_ids.contains(&id))
+            .map(|&(id, value)| (id, value.to_bits()))
+            .unzip();
+
         let id = if ctxt.version >= &Version(Api::Gl, 4, 6) ||
             (ctxt.version >= &Version(Api::Gl, 4, 1) && ctxt.extensions.gl_arb_gl_spirv)
         {
@@ -216,49 +235,102 @@ pub fn build_spirv_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum,
 
         ctxt.gl.ShaderBinary(1, &id, GL_SHADER_BINARY_FORMAT_SPIR_V, binary.as_ptr() as _, binary.len() as _);
 
-        // compiling
+        // specializing
         {
             ctxt.report_debug_output_errors.set(false);
 
-            ctxt.gl.SpecializeShader(id, entry_point.as_ptr() as _, 0, ptr::null(), ptr::null());
+            ctxt.gl.SpecializeShader(id, entry_point.as_ptr() as _, constant_ids.len() as _,
+                                     constant_ids.as_ptr(), constant_values.as_ptr());
 
             ctxt.report_debug_output_errors.set(true);
         }
 
-        // checking compilation success by reading a flag on the shader
-        let compilation_success = {
-            let mut compilation_success: gl::types::GLint = 0;
-            ctxt.gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut compilation_success);
-            compilation_success
+        // checking specialization success by reading a flag on the shader
+        let specialization_success = {
+            let mut specialization_success: gl::types::GLint = 0;
+            ctxt.gl.GetShaderiv(id, gl::COMPILE_STATUS, &mut specialization_success);
+            specialization_success
         };
 
-        if compilation_success == 1 {
-            Ok(Shader {
+        if specialization_success == 1 {
+            return Ok(Shader {
                 context: facade.get_context().clone(),
                 id: Handle::Id(id)
-            })
-        } else {
-            // compilation error
-            let mut error_log_size: gl::types::GLint = 0;
+            });
+        }
 
-            ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
+        // specialization or compilation error
+        let mut error_log_size: gl::types::GLint = 0;
 
-            let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+        ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
+
+        let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
 
+        if error_log_size > 0 {
             ctxt.gl.GetShaderInfoLog(id, error_log_size, &mut error_log_size,
                                      error_log.as_mut_ptr() as *mut gl::types::GLchar);
-
             error_log.set_len(error_log_size as usize);
+        }
 
-            match String::from_utf8(error_log) {
-                Ok(msg) => Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type))),
-                Err(_) => Err(
-                    ProgramCreationError::CompilationError("Could not convert the log \
-                                                            message to UTF-8".to_owned(), ShaderType::from_opengl_type(shader_type))
-                ),
+        ctxt.gl.DeleteShader(id);
+
+        // an unknown entry point generates `GL_INVALID_VALUE` without writing anything to the
+        // info log
+        match String::from_utf8(error_log) {
+            Ok(ref msg) if msg.trim_end_matches('\0').is_empty() => Err(
+                ProgramCreationError::SpecializationError(
+                    format!("The entry point `{}` doesn't exist in the module", spirv.entry_point),
+                    ShaderType::from_opengl_type(shader_type))
+            ),
+            Ok(msg) => Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type))),
+            Err(_) => Err(
+                ProgramCreationError::CompilationError("Could not convert the log \
+                                                        message to UTF-8".to_owned(), ShaderType::from_opengl_type(shader_type))
+            ),
+        }
+    }
+}
+
+/// Returns the IDs of the specialization constants declared in a SPIR-V module, by looking for
+/// the `OpDecorate` instructions with a `SpecId` decoration.
+fn spirv_specialization_constant_ids(binary: &[u8]) -> Vec<u32> {
+    const MAGIC_NUMBER: u32 = 0x07230203;
+    const OP_DECORATE: u32 = 71;
+    const OP_FUNCTION: u32 = 54;
+    const DECORATION_SPEC_ID: u32 = 1;
+
+    let swap = match binary.get(0 .. 4) {
+        Some(&[a, b, c, d]) if u32::from_ne_bytes([a, b, c, d]) == MAGIC_NUMBER => false,
+        Some(&[a, b, c, d]) if u32::from_ne_bytes([a, b, c, d]).swap_bytes() == MAGIC_NUMBER => true,
+        _ => return Vec::new(),
+    };
+
+    let words = binary.chunks_exact(4)
+        .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+        .map(|word| if swap { word.swap_bytes() } else { word })
+        .collect::<Vec<_>>();
+
+    // the header is 5 words long, and the decorations are all before the first function
+    let mut ids = Vec::new();
+    let mut offset = 5;
+    while let Some(&instruction) = words.get(offset) {
+        let (word_count, opcode) = ((instruction >> 16) as usize, instruction & 0xffff);
+        if word_count == 0 || opcode == OP_FUNCTION {
+            break;
+        }
+
+        if opcode == OP_DECORATE && word_count >= 4 {
+            if let (Some(&DECORATION_SPEC_ID), Some(&id)) = (words.get(offset + 2),
+                                                             words.get(offset + 3))
+            {
+                ids.push(id);
             }
         }
+
+        offset += word_count;
     }
+
+    ids
 }
 
 pub fn check_shader_type_compatibility<C: ?Sized>(ctxt: &C, shader_type: gl::types::GLenum)
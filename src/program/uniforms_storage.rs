@@ -34,6 +34,14 @@ impl UniformsStorage {
         }
     }
 
+    /// Forgets all the values, for example because the program has been recreated.
+    pub fn clear(&self) {
+        self.values.borrow_mut().clear();
+        self.uniform_blocks.borrow_mut().clear();
+        self.shader_storage_blocks.borrow_mut().clear();
+        self.subroutine_uniforms.borrow_mut().clear();
+    }
+
     /// Compares `value` with the value stored in this object. If the values differ, updates
     /// the storage and calls `glUniform`.
     pub fn set_uniform_value(&self, ctxt: &mut CommandContext<'_>, program: Handle,
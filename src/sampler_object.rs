@@ -29,35 +29,7 @@ impl SamplerObject {
             sampler
         };
 
-        unsafe {
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_S,
-                                      behavior.wrap_function.0.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_T,
-                                      behavior.wrap_function.1.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_WRAP_R,
-                                      behavior.wrap_function.2.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_MIN_FILTER,
-                                      behavior.minify_filter.to_glenum() as gl::types::GLint);
-            ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_MAG_FILTER,
-                                      behavior.magnify_filter.to_glenum() as gl::types::GLint);
-
-            if let Some(dtc) = behavior.depth_texture_comparison {
-                ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_COMPARE_MODE,
-                                          gl::COMPARE_R_TO_TEXTURE as gl::types::GLint);
-                ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_COMPARE_FUNC,
-                                          dtc.to_glenum() as gl::types::GLint);
-            }
-
-            if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
-                let value = if behavior.max_anisotropy as f32 > max_value {
-                    max_value
-                } else {
-                    behavior.max_anisotropy as f32
-                };
-
-                ctxt.gl.SamplerParameterf(sampler, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
-            }
-        }
+        unsafe { set_parameters(ctxt, ParametersTarget::Sampler(sampler), behavior); }
 
         SamplerObject {
             id: sampler,
@@ -92,13 +64,20 @@ impl Drop for SamplerObject {
     }
 }
 
+/// Returns true if the backend supports sampler objects.
+#[inline]
+pub fn is_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+        ctxt.extensions.gl_arb_sampler_objects
+}
+
 /// Returns the sampler corresponding to the given behavior, or a draw error if
 /// samplers are not supported.
 pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
                    -> Result<gl::types::GLuint, DrawError>
 {
     // checking for compatibility
-    if ctxt.version < &Version(Api::Gl, 3, 2) && !ctxt.extensions.gl_arb_sampler_objects {
+    if !is_supported(ctxt) {
         return Err(DrawError::SamplersNotSupported);
     }
 
@@ -113,3 +92,116 @@ pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
     ctxt.samplers.insert(*behavior, sampler);
     Ok(id)
 }
+
+/// Applies the parameters of a sampler behavior to the texture currently bound to the given
+/// bind point of the active texture unit.
+///
+/// This is used when sampler objects are not supported. Contrary to sampler objects, the
+/// parameters are stored in the texture and stay there until they are modified again.
+///
+/// *Safety*: the texture must be bound to `bind_point` on the active texture unit.
+#[inline]
+pub unsafe fn set_texture_parameters(ctxt: &mut CommandContext<'_>, bind_point: gl::types::GLenum,
+                                     behavior: &SamplerBehavior)
+{
+    set_parameters(ctxt, ParametersTarget::Texture(bind_point), behavior);
+}
+
+/// Object whose sampling parameters are modified by `set_parameters`.
+#[derive(Copy, Clone)]
+enum ParametersTarget {
+    /// A sampler object.
+    Sampler(gl::types::GLuint),
+    /// The texture bound to this bind point of the active texture unit.
+    Texture(gl::types::GLenum),
+}
+
+unsafe fn set_parameters(ctxt: &mut CommandContext<'_>, target: ParametersTarget,
+                         behavior: &SamplerBehavior)
+{
+    let parameteri = |ctxt: &mut CommandContext<'_>, name, value: gl::types::GLenum| {
+        match target {
+            ParametersTarget::Sampler(id) => {
+                ctxt.gl.SamplerParameteri(id, name, value as gl::types::GLint)
+            },
+            ParametersTarget::Texture(bind_point) => {
+                ctxt.gl.TexParameteri(bind_point, name, value as gl::types::GLint)
+            },
+        }
+    };
+
+    let parameterf = |ctxt: &mut CommandContext<'_>, name, value: gl::types::GLfloat| {
+        match target {
+            ParametersTarget::Sampler(id) => ctxt.gl.SamplerParameterf(id, name, value),
+            ParametersTarget::Texture(bind_point) => ctxt.gl.TexParameterf(bind_point, name, value),
+        }
+    };
+
+    // the third coordinate only exists for 3D, array and cubemap textures, and OpenGL ES 2
+    // doesn't know about it without `GL_OES_texture_3D`
+    let has_wrap_r = match target {
+        ParametersTarget::Sampler(_) => true,
+        ParametersTarget::Texture(bind_point) => {
+            bind_point != gl::TEXTURE_1D && bind_point != gl::TEXTURE_2D &&
+            (ctxt.version >= &Version(Api::Gl, 1, 2) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_oes_texture_3d)
+        },
+    };
+
+    parameteri(ctxt, gl::TEXTURE_WRAP_S, behavior.wrap_function.0.to_glenum());
+    parameteri(ctxt, gl::TEXTURE_WRAP_T, behavior.wrap_function.1.to_glenum());
+    if has_wrap_r {
+        parameteri(ctxt, gl::TEXTURE_WRAP_R, behavior.wrap_function.2.to_glenum());
+    }
+    parameteri(ctxt, gl::TEXTURE_MIN_FILTER, behavior.minify_filter.to_glenum());
+    parameteri(ctxt, gl::TEXTURE_MAG_FILTER, behavior.magnify_filter.to_glenum());
+
+    // depth comparison requires OpenGL 1.4, OpenGL ES 3.0 or `GL_EXT_shadow_samplers`
+    if ctxt.version >= &Version(Api::Gl, 1, 4) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_ext_shadow_samplers
+    {
+        // sampler objects are created once per behavior, but texture parameters persist and
+        // must be reset explicitly
+        if let Some(dtc) = behavior.depth_texture_comparison {
+            parameteri(ctxt, gl::TEXTURE_COMPARE_MODE, gl::COMPARE_R_TO_TEXTURE);
+            parameteri(ctxt, gl::TEXTURE_COMPARE_FUNC, dtc.to_glenum());
+        } else if let ParametersTarget::Texture(_) = target {
+            parameteri(ctxt, gl::TEXTURE_COMPARE_MODE, gl::NONE);
+        }
+    }
+
+    if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
+        let value = if behavior.max_anisotropy as f32 > max_value {
+            max_value
+        } else {
+            behavior.max_anisotropy as f32
+        };
+
+        parameterf(ctxt, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        parameterf(ctxt, gl::TEXTURE_MIN_LOD, behavior.min_lod);
+        parameterf(ctxt, gl::TEXTURE_MAX_LOD, behavior.max_lod);
+    }
+
+    // OpenGL ES doesn't have a LOD bias parameter
+    if ctxt.version >= &Version(Api::Gl, 1, 4) {
+        parameterf(ctxt, gl::TEXTURE_LOD_BIAS, behavior.lod_bias);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 1, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+       ctxt.extensions.gl_ext_texture_border_clamp || ctxt.extensions.gl_oes_texture_border_clamp
+    {
+        let color = behavior.border_color.as_ptr();
+
+        match target {
+            ParametersTarget::Sampler(id) => {
+                ctxt.gl.SamplerParameterfv(id, gl::TEXTURE_BORDER_COLOR, color)
+            },
+            ParametersTarget::Texture(bind_point) => {
+                ctxt.gl.TexParameterfv(bind_point, gl::TEXTURE_BORDER_COLOR, color)
+            },
+        }
+    }
+}
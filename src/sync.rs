@@ -42,6 +42,33 @@ impl SyncFence {
         unsafe { new_linear_sync_fence(&mut ctxt) }.map(|f| f.into_sync_fence(facade))
     }
 
+    /// Returns true if the operation has finished on the server. Doesn't block.
+    ///
+    /// The commands queue is flushed, so that the fence is guaranteed to be signaled at some
+    /// point if you keep calling this function.
+    pub fn is_signaled(&self) -> bool {
+        let sync = self.id.unwrap();
+
+        let ctxt = self.context.make_current();
+        let result = unsafe {
+            if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+            {
+                ctxt.gl.ClientWaitSync(sync, gl::SYNC_FLUSH_COMMANDS_BIT, 0)
+            } else if ctxt.extensions.gl_apple_sync {
+                ctxt.gl.ClientWaitSyncAPPLE(sync, gl::SYNC_FLUSH_COMMANDS_BIT_APPLE, 0)
+            } else {
+                unreachable!();
+            }
+        };
+
+        match result {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => true,
+            gl::TIMEOUT_EXPIRED => false,
+            _ => panic!("Could not query the status of the fence")
+        }
+    }
+
     /// Blocks until the operation has finished on the server.
     pub fn wait(mut self) {
         let sync = self.id.take().unwrap();
@@ -101,6 +128,71 @@ impl Drop for LinearSyncFence {
     }
 }
 
+/// Fence that marks the end of the commands that prepared an object for being transferred
+/// to another context of the same share group.
+///
+/// Contrary to `LinearSyncFence`, this fence can be dropped without being waited upon. In this
+/// situation the fence object is leaked, as there is no context to destroy it.
+pub struct TransferFence {
+    id: Option<gl::types::GLsync>,
+}
+
+unsafe impl Send for TransferFence {}
+
+impl TransferFence {
+    /// Inserts a fence in the commands queue and flushes it, so that the fence can be waited
+    /// upon from another context.
+    ///
+    /// If fences are not supported, calls `glFinish` instead.
+    pub unsafe fn new(ctxt: &mut CommandContext<'_>) -> TransferFence {
+        match new_linear_sync_fence(ctxt) {
+            Ok(mut fence) => {
+                ctxt.gl.Flush();
+                TransferFence { id: fence.id.take() }
+            },
+            Err(_) => {
+                ctxt.gl.Finish();
+                TransferFence { id: None }
+            },
+        }
+    }
+
+    /// Makes the server of `ctxt` wait for the fence, then destroys it.
+    ///
+    /// `ctxt` must belong to the same share group as the context that created the fence.
+    pub unsafe fn wait_and_drop(mut self, ctxt: &mut CommandContext<'_>) {
+        let fence = match self.id.take() {
+            Some(f) => f,
+            None => return,     // `glFinish` has already been called
+        };
+
+        if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+           ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+        {
+            ctxt.gl.WaitSync(fence, 0, gl::TIMEOUT_IGNORED);
+        } else if ctxt.extensions.gl_apple_sync {
+            ctxt.gl.WaitSyncAPPLE(fence, 0, gl::TIMEOUT_IGNORED_APPLE);
+        } else {
+            // contexts of the same share group are provided by the same implementation, so
+            // this one supports fences as well
+            unreachable!();
+        }
+
+        delete_fence(ctxt, fence);
+    }
+
+    /// Blocks until the fence is signaled, then destroys it.
+    ///
+    /// Contrary to `wait_and_drop`, this also synchronizes accesses made by the CPU, for example
+    /// through a persistent mapping.
+    pub unsafe fn client_wait_and_drop(mut self, ctxt: &mut CommandContext<'_>) {
+        if let Some(fence) = self.id.take() {
+            client_wait(ctxt, fence);
+            delete_fence(ctxt, fence);
+        }
+    }
+}
+
 pub unsafe fn new_linear_sync_fence(ctxt: &mut CommandContext<'_>)
                                     -> Result<LinearSyncFence, SyncNotSupportedError>
 {
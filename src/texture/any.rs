@@ -6,39 +6,54 @@ use crate::backend::Facade;
 use crate::memory_object::MemoryObject;
 use crate::version::Version;
 use crate::context::Context;
+use crate::context::{Resource, ResourceKind};
 use crate::context::CommandContext;
+use crate::context::ShareGroup;
+use crate::debug::{self, LabelNotSupportedError};
+use crate::sync::TransferFence;
 use crate::CapabilitiesSource;
 use crate::ContextExt;
+use crate::ToGlEnum;
 use crate::TextureExt;
 use crate::TextureMipmapExt;
 use crate::version::Api;
 use crate::Rect;
+use crate::BlitTarget;
 
 use crate::image_format::{self, TextureFormatRequest, ClientFormatAny};
 use crate::texture::Texture2dDataSink;
 use crate::texture::TextureKind;
 use crate::texture::{MipmapsOption, TextureFormat, TextureCreationError, CubeLayer};
+use crate::texture::{TextureViewCreationError, CopyError};
+use crate::framebuffer::RenderBufferAny;
 use crate::texture::{get_format, InternalFormat, GetFormatError};
 use crate::texture::pixel::PixelValue;
 use crate::texture::pixel_buffer::PixelBuffer;
 
 use crate::fbo::ClearBufferData;
 
+use crate::uniforms::{SamplerBehavior, SamplerWrapFunction};
+use crate::uniforms::{MinifySamplerFilter, MagnifySamplerFilter};
+
 use crate::buffer::BufferSlice;
 use crate::buffer::BufferAny;
 use crate::BufferExt;
 use crate::BufferSliceExt;
 
 use std::cmp;
+use std::error::Error;
 use std::fmt;
 use std::mem;
 use std::ptr;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::rc::Rc;
+use std::marker::PhantomData;
 use std::ops::Range;
 use std::ffi::c_void;
 
+use smallvec::SmallVec;
+
 use crate::ops;
 use crate::fbo;
 
@@ -57,6 +72,122 @@ pub enum Dimensions {
     CubemapArray { dimension: u32, array_size: u32 },
 }
 
+/// Type of a texture view.
+///
+/// Same as `Dimensions`, except that the dimensions of a view are determined by the texture it
+/// is created from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum TextureViewType {
+    Texture1d,
+    Texture1dArray,
+    Texture2d,
+    Texture2dArray,
+    Texture2dMultisample,
+    Texture2dMultisampleArray,
+    Texture3d,
+    Cubemap,
+    CubemapArray,
+}
+
+/// Source of a component of a texture when it is sampled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SwizzleComponent {
+    /// The red component of the texture.
+    Red,
+    /// The green component of the texture.
+    Green,
+    /// The blue component of the texture.
+    Blue,
+    /// The alpha component of the texture.
+    Alpha,
+    /// Always `0`.
+    Zero,
+    /// Always `1`.
+    One,
+}
+
+impl ToGlEnum for SwizzleComponent {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            SwizzleComponent::Red => gl::RED,
+            SwizzleComponent::Green => gl::GREEN,
+            SwizzleComponent::Blue => gl::BLUE,
+            SwizzleComponent::Alpha => gl::ALPHA,
+            SwizzleComponent::Zero => gl::ZERO,
+            SwizzleComponent::One => gl::ONE,
+        }
+    }
+}
+
+/// Swizzle mask of a texture.
+///
+/// Indicates where each component returned by a texture lookup in a shader comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Swizzle {
+    /// Source of the red component.
+    pub r: SwizzleComponent,
+    /// Source of the green component.
+    pub g: SwizzleComponent,
+    /// Source of the blue component.
+    pub b: SwizzleComponent,
+    /// Source of the alpha component.
+    pub a: SwizzleComponent,
+}
+
+impl Swizzle {
+    /// Each component comes from itself. This is the default swizzle of a texture.
+    pub const IDENTITY: Swizzle = Swizzle {
+        r: SwizzleComponent::Red,
+        g: SwizzleComponent::Green,
+        b: SwizzleComponent::Blue,
+        a: SwizzleComponent::Alpha,
+    };
+
+    /// Builds a new swizzle mask.
+    #[inline]
+    pub fn new(r: SwizzleComponent, g: SwizzleComponent, b: SwizzleComponent,
+               a: SwizzleComponent) -> Swizzle
+    {
+        Swizzle { r, g, b, a }
+    }
+
+    /// Returns the red component of a single-channel texture as an opaque gray color.
+    #[inline]
+    pub fn grayscale() -> Swizzle {
+        Swizzle::new(SwizzleComponent::Red, SwizzleComponent::Red, SwizzleComponent::Red,
+                     SwizzleComponent::One)
+    }
+
+    /// Returns the red component of a single-channel texture as the alpha of a white color.
+    #[inline]
+    pub fn alpha_mask() -> Swizzle {
+        Swizzle::new(SwizzleComponent::One, SwizzleComponent::One, SwizzleComponent::One,
+                     SwizzleComponent::Red)
+    }
+}
+
+impl Default for Swizzle {
+    #[inline]
+    fn default() -> Swizzle {
+        Swizzle::IDENTITY
+    }
+}
+
+/// Error that can happen when setting the swizzle mask of a texture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SwizzleNotSupportedError;
+
+impl fmt::Display for SwizzleNotSupportedError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Texture swizzle masks are not supported by the backend")
+    }
+}
+
+impl Error for SwizzleNotSupportedError {}
+
 /// A texture whose type isn't fixed at compile-time.
 pub struct TextureAny {
     context: Rc<Context>,
@@ -83,6 +214,13 @@ pub struct TextureAny {
 
     /// ID of the draw call where the texture was last written as a texture unit.
     latest_shader_write: Cell<u64>,
+
+    /// Current swizzle mask of the texture.
+    swizzle: Cell<Swizzle>,
+
+    /// Sampler behavior stored in the parameters of the texture when sampler objects are not
+    /// supported. `None` if the parameters are the ones the texture was created with.
+    sampler_parameters: Cell<Option<SamplerBehavior>>,
 }
 
 fn extract_dimensions(ty: Dimensions)
@@ -116,6 +254,25 @@ fn get_bind_point(ty: Dimensions) -> gl::types::GLenum {
     }
 }
 
+/// Returns an estimate of the number of bytes used in video memory by a texture.
+fn estimate_texture_size(ty: Dimensions, levels: u32, format: gl::types::GLenum) -> usize {
+    let (width, height, depth, array_size, samples) = extract_dimensions(ty);
+
+    let layers = match ty {
+        Dimensions::Cubemap { .. } => 6,
+        _ => array_size.unwrap_or(1),
+    };
+
+    let levels_size: usize = (0 .. levels).map(|level| {
+        let width = cmp::max(1, width >> level);
+        let height = cmp::max(1, height.unwrap_or(1) >> level);
+        let depth = cmp::max(1, depth.unwrap_or(1) >> level);
+        image_format::estimate_image_size(format, width, height, depth)
+    }).sum();
+
+    levels_size * layers as usize * samples.unwrap_or(1) as usize
+}
+
 unsafe fn generate_mipmaps(ctxt: &CommandContext<'_>,
                            bind_point: gl::types::GLenum) {
     if ctxt.version >= &Version(Api::Gl, 3, 0) ||
@@ -129,6 +286,382 @@ unsafe fn generate_mipmaps(ctxt: &CommandContext<'_>,
     }
 }
 
+/// Returns the internal format of a mipmap level of a texture.
+unsafe fn get_level_internal_format(ctxt: &mut CommandContext<'_>, texture: &TextureAny,
+                                    level: u32) -> gl::types::GLenum
+{
+    let bind_point = texture.bind_to_current(ctxt);
+
+    // the parameters of the levels of a cubemap are queried on one of its faces
+    let target = if bind_point == gl::TEXTURE_CUBE_MAP {
+        gl::TEXTURE_CUBE_MAP_POSITIVE_X
+    } else {
+        bind_point
+    };
+
+    let mut format = 0;
+    ctxt.gl.GetTexLevelParameteriv(target, level as gl::types::GLint,
+                                   gl::TEXTURE_INTERNAL_FORMAT, &mut format);
+    format as gl::types::GLenum
+}
+
+/// Returns the internal format of a renderbuffer.
+unsafe fn get_renderbuffer_internal_format(ctxt: &mut CommandContext<'_>,
+                                           renderbuffer: gl::types::GLuint) -> gl::types::GLenum
+{
+    if ctxt.state.renderbuffer != renderbuffer {
+        ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, renderbuffer);
+        ctxt.state.renderbuffer = renderbuffer;
+    }
+
+    let mut format = 0;
+    ctxt.gl.GetRenderbufferParameteriv(gl::RENDERBUFFER, gl::RENDERBUFFER_INTERNAL_FORMAT,
+                                       &mut format);
+    format as gl::types::GLenum
+}
+
+/// Returns true if `glCopyImageSubData` is supported.
+#[inline]
+fn is_copy_image_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+    ctxt.extensions.gl_arb_copy_image
+}
+
+/// Image or array of images of a texture or renderbuffer that takes part in a copy.
+struct CopyImage {
+    id: gl::types::GLuint,
+    target: gl::types::GLenum,
+    level: u32,
+    format: gl::types::GLenum,
+    /// Width, height and number of layers of the image.
+    dimensions: (u32, u32, u32),
+    samples: Option<u32>,
+}
+
+/// Checks that a region can be copied between two images and returns its size in blocks. The
+/// size is expressed in texels of the source.
+fn check_copy_region(source: &CopyImage, source_offset: (u32, u32, u32), target: &CopyImage,
+                     target_offset: (u32, u32, u32), size: (u32, u32, u32))
+                     -> Result<(u32, u32), CopyError>
+{
+    if !image_format::is_copy_compatible(source.format, target.format) ||
+       source.samples != target.samples
+    {
+        return Err(CopyError::IncompatibleFormats);
+    }
+
+    // the region is copied block by block, a block of a compressed format corresponding to
+    // a texel of an uncompressed format
+    let (source_block_w, source_block_h) = image_format::get_block_dimensions(source.format);
+    let (target_block_w, target_block_h) = image_format::get_block_dimensions(target.format);
+    let blocks = ((size.0 + source_block_w - 1) / source_block_w,
+                  (size.1 + source_block_h - 1) / source_block_h);
+
+    let round_up = |value: u32, block: u32| (value + block - 1) / block * block;
+
+    if source_offset.0 % source_block_w != 0 || source_offset.1 % source_block_h != 0 ||
+       source_offset.0 + size.0 > round_up(source.dimensions.0, source_block_w) ||
+       source_offset.1 + size.1 > round_up(source.dimensions.1, source_block_h) ||
+       source_offset.2 + size.2 > source.dimensions.2
+    {
+        return Err(CopyError::OutOfRange);
+    }
+
+    if target_offset.0 % target_block_w != 0 || target_offset.1 % target_block_h != 0 ||
+       target_offset.0 + blocks.0 * target_block_w > round_up(target.dimensions.0, target_block_w) ||
+       target_offset.1 + blocks.1 * target_block_h > round_up(target.dimensions.1, target_block_h) ||
+       target_offset.2 + size.2 > target.dimensions.2
+    {
+        return Err(CopyError::OutOfRange);
+    }
+
+    Ok(blocks)
+}
+
+/// Copies texels with `glCopyImageSubData`. The size is expressed in texels of the source.
+unsafe fn copy_image_sub_data(ctxt: &mut CommandContext<'_>, source: &CopyImage,
+                              source_offset: (u32, u32, u32), target: &CopyImage,
+                              target_offset: (u32, u32, u32), size: (u32, u32, u32))
+                              -> Result<(), CopyError>
+{
+    check_copy_region(source, source_offset, target, target_offset, size)?;
+
+    ctxt.gl.CopyImageSubData(source.id, source.target, source.level as gl::types::GLint,
+                             source_offset.0 as gl::types::GLint,
+                             source_offset.1 as gl::types::GLint,
+                             source_offset.2 as gl::types::GLint,
+                             target.id, target.target, target.level as gl::types::GLint,
+                             target_offset.0 as gl::types::GLint,
+                             target_offset.1 as gl::types::GLint,
+                             target_offset.2 as gl::types::GLint,
+                             size.0 as gl::types::GLsizei, size.1 as gl::types::GLsizei,
+                             size.2 as gl::types::GLsizei);
+
+    Ok(())
+}
+
+/// Builds the attachments of a framebuffer that only contains `attachment`.
+fn single_attachment(attachment: fbo::RegularAttachment<'_>) -> fbo::FramebufferAttachments<'_> {
+    let mut colors = SmallVec::new();
+
+    let depth_stencil = match attachment.kind() {
+        TextureKind::Depth => fbo::DepthStencilAttachments::DepthAttachment(attachment),
+        TextureKind::Stencil => fbo::DepthStencilAttachments::StencilAttachment(attachment),
+        TextureKind::DepthStencil => fbo::DepthStencilAttachments::DepthStencilAttachment(attachment),
+        TextureKind::Float | TextureKind::Integral | TextureKind::Unsigned => {
+            colors.push((0, attachment));
+            fbo::DepthStencilAttachments::None
+        },
+    };
+
+    fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+        colors,
+        depth_stencil,
+    })
+}
+
+/// Copies texels between two images with a framebuffer blit, for backends that don't support
+/// `glCopyImageSubData`.
+fn blit_image(source: &TextureAnyImage<'_>, source_rect: &Rect, target: CopyTarget<'_>,
+              left: u32, bottom: u32) -> Result<(), CopyError>
+{
+    let context = &source.texture.context;
+
+    if !(context.get_version() >= &Version(Api::Gl, 3, 0) ||
+         context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+         context.get_extensions().gl_ext_framebuffer_blit)
+    {
+        return Err(CopyError::NotSupported);
+    }
+
+    // compressed formats can't be attached to a framebuffer
+    if source.texture.is_compressed() {
+        return Err(CopyError::NotSupported);
+    }
+    if let CopyTarget::Texture(image) = target {
+        if image.texture.is_compressed() {
+            return Err(CopyError::NotSupported);
+        }
+    }
+
+    let kind = source.texture.kind();
+    if kind != target.kind() || source.get_samples() != target.get_samples() {
+        return Err(CopyError::IncompatibleFormats);
+    }
+
+    let (target_width, target_height) = target.get_dimensions();
+    if source_rect.left + source_rect.width > source.width ||
+       source_rect.bottom + source_rect.height > source.height.unwrap_or(1) ||
+       left + source_rect.width > target_width || bottom + source_rect.height > target_height
+    {
+        return Err(CopyError::OutOfRange);
+    }
+
+    let mask = match kind {
+        TextureKind::Float | TextureKind::Integral | TextureKind::Unsigned => gl::COLOR_BUFFER_BIT,
+        TextureKind::Depth => gl::DEPTH_BUFFER_BIT,
+        TextureKind::Stencil => gl::STENCIL_BUFFER_BIT,
+        TextureKind::DepthStencil => gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT,
+    };
+
+    let source_attachments = single_attachment(fbo::RegularAttachment::Texture(*source))
+        .validate(&**context).map_err(|_| CopyError::NotSupported)?;
+    let target_attachments = single_attachment(target.as_attachment())
+        .validate(&**context).map_err(|_| CopyError::NotSupported)?;
+
+    let target_rect = BlitTarget {
+        left,
+        bottom,
+        width: source_rect.width as i32,
+        height: source_rect.height as i32,
+    };
+
+    ops::blit(context, Some(&source_attachments), Some(&target_attachments), mask, source_rect,
+              &target_rect, gl::NEAREST);
+    Ok(())
+}
+
+/// Returns true if texels can be read back from textures with `glGetTexImage` and
+/// `glGetCompressedTexImage`.
+#[inline]
+fn is_readback_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 1, 3)
+}
+
+/// Copies texels between two textures by reading them back from the source and uploading them
+/// to the target, for backends that don't support `glCopyImageSubData`. Contrary to a blit,
+/// this works with compressed and non-renderable formats and copies texels without converting
+/// them.
+///
+/// `blocks` is the size of the region in blocks, as returned by `check_copy_region`.
+unsafe fn readback_image(ctxt: &mut CommandContext<'_>, source: &TextureAnyImage<'_>,
+                         source_format: gl::types::GLenum, source_offset: (u32, u32),
+                         target: &TextureAnyImage<'_>, target_format: gl::types::GLenum,
+                         target_offset: (u32, u32), blocks: (u32, u32))
+                         -> Result<(), CopyError>
+{
+    // multisample images can't be read back
+    if source.get_samples().is_some() || target.get_samples().is_some() {
+        return Err(CopyError::NotSupported);
+    }
+
+    // compressed formats are transferred block by block, and uncompressed formats need a
+    // client format that matches their representation in video memory
+    let (source_block_w, source_block_h) = image_format::get_block_dimensions(source_format);
+    let (target_block_w, target_block_h) = image_format::get_block_dimensions(target_format);
+    let source_client = image_format::get_raw_client_format(source_format);
+    let target_client = image_format::get_raw_client_format(target_format);
+    if (source_client.is_none() && source_block_w == 1) ||
+       (target_client.is_none() && target_block_w == 1)
+    {
+        return Err(CopyError::NotSupported);
+    }
+
+    let block_size = image_format::estimate_image_size(source_format, source_block_w,
+                                                       source_block_h, 1);
+
+    // `glGetTexImage` reads all the layers of a level at once, except for cubemaps which are
+    // read face by face
+    let bind_point = source.texture.bind_to_current(ctxt);
+    let (read_target, layer, layers) = match (source.texture.ty, source.cube_layer) {
+        (Dimensions::Cubemap { .. }, Some(face)) => {
+            (gl::TEXTURE_CUBE_MAP_POSITIVE_X + face.get_layer_index() as gl::types::GLenum, 0, 1)
+        },
+        _ => (bind_point, source.get_layer_face(),
+              source.texture.get_layer_faces_count(source.level)),
+    };
+
+    let row_blocks = ((source.width + source_block_w - 1) / source_block_w) as usize;
+    let rows = ((source.height.unwrap_or(1) + source_block_h - 1) / source_block_h) as usize;
+    let mut data = vec![0u8; row_blocks * rows * layers as usize * block_size];
+
+    BufferAny::unbind_pixel_pack(ctxt);
+    if ctxt.state.pixel_store_pack_alignment != 1 {
+        ctxt.state.pixel_store_pack_alignment = 1;
+        ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+    }
+
+    match source_client {
+        Some((format, ty)) => {
+            ctxt.gl.GetTexImage(read_target, source.level as gl::types::GLint, format, ty,
+                                data.as_mut_ptr() as *mut _);
+        },
+        None => {
+            let mut size = 0;
+            ctxt.gl.GetTexLevelParameteriv(read_target, source.level as gl::types::GLint,
+                                           gl::TEXTURE_COMPRESSED_IMAGE_SIZE, &mut size);
+            if size as usize != data.len() {
+                return Err(CopyError::NotSupported);
+            }
+
+            ctxt.gl.GetCompressedTexImage(read_target, source.level as gl::types::GLint,
+                                          data.as_mut_ptr() as *mut _);
+        },
+    }
+
+    let mut region = Vec::with_capacity(blocks.0 as usize * blocks.1 as usize * block_size);
+    for row in 0 .. blocks.1 as usize {
+        let row = (layer as usize * rows) + (source_offset.1 / source_block_h) as usize + row;
+        let start = (row * row_blocks + (source_offset.0 / source_block_w) as usize) * block_size;
+        region.extend_from_slice(&data[start .. start + blocks.0 as usize * block_size]);
+    }
+
+    let bind_point = target.texture.bind_to_current(ctxt);
+
+    BufferAny::unbind_pixel_unpack(ctxt);
+    if ctxt.state.pixel_store_unpack_alignment != 1 {
+        ctxt.state.pixel_store_unpack_alignment = 1;
+        ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+    }
+
+    // blocks on the border of a compressed image can be partially outside of it
+    let x = target_offset.0 as gl::types::GLint;
+    let y = target_offset.1 as gl::types::GLint;
+    let z = target.get_layer_face() as gl::types::GLint;
+    let width = cmp::min(blocks.0 * target_block_w, target.width - target_offset.0)
+        as gl::types::GLsizei;
+    let height = cmp::min(blocks.1 * target_block_h,
+                          target.height.unwrap_or(1) - target_offset.1) as gl::types::GLsizei;
+    let level = target.level as gl::types::GLint;
+    let ptr = region.as_ptr() as *const _;
+    let size = region.len() as gl::types::GLsizei;
+
+    match (target.texture.ty, target_client) {
+        (Dimensions::Texture1d { .. }, Some((format, ty))) => {
+            ctxt.gl.TexSubImage1D(bind_point, level, x, width, format, ty, ptr);
+        },
+        (Dimensions::Texture1d { .. }, None) => {
+            ctxt.gl.CompressedTexSubImage1D(bind_point, level, x, width, target_format, size, ptr);
+        },
+        (Dimensions::Texture1dArray { .. }, Some((format, ty))) => {
+            ctxt.gl.TexSubImage2D(bind_point, level, x, z, width, 1, format, ty, ptr);
+        },
+        (Dimensions::Texture1dArray { .. }, None) => {
+            ctxt.gl.CompressedTexSubImage2D(bind_point, level, x, z, width, 1, target_format,
+                                            size, ptr);
+        },
+        (Dimensions::Texture2d { .. }, _) | (Dimensions::Cubemap { .. }, _) => {
+            let upload_target = match target.cube_layer {
+                Some(face) => {
+                    gl::TEXTURE_CUBE_MAP_POSITIVE_X + face.get_layer_index() as gl::types::GLenum
+                },
+                None => bind_point,
+            };
+
+            match target_client {
+                Some((format, ty)) => {
+                    ctxt.gl.TexSubImage2D(upload_target, level, x, y, width, height, format, ty,
+                                          ptr);
+                },
+                None => {
+                    ctxt.gl.CompressedTexSubImage2D(upload_target, level, x, y, width, height,
+                                                    target_format, size, ptr);
+                },
+            }
+        },
+        (Dimensions::Texture2dArray { .. }, Some((format, ty))) |
+        (Dimensions::Texture3d { .. }, Some((format, ty))) |
+        (Dimensions::CubemapArray { .. }, Some((format, ty))) => {
+            ctxt.gl.TexSubImage3D(bind_point, level, x, y, z, width, height, 1, format, ty, ptr);
+        },
+        (Dimensions::Texture2dArray { .. }, None) | (Dimensions::Texture3d { .. }, None) |
+        (Dimensions::CubemapArray { .. }, None) => {
+            ctxt.gl.CompressedTexSubImage3D(bind_point, level, x, y, z, width, height, 1,
+                                            target_format, size, ptr);
+        },
+        (Dimensions::Texture2dMultisample { .. }, _) |
+        (Dimensions::Texture2dMultisampleArray { .. }, _) => unreachable!(),
+    }
+
+    Ok(())
+}
+
+/// Returns the sampling parameters that a texture has after its creation. They are set by
+/// `init_texture`, and restored by `set_sampler_parameters`.
+fn get_creation_behavior(format: TextureFormatRequest, has_mipmaps: bool) -> SamplerBehavior {
+    let nearest = matches!(format,
+        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
+        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) |
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) |
+        TextureFormatRequest::AnyIntegral | TextureFormatRequest::AnyUnsigned |
+        TextureFormatRequest::AnyStencil);
+
+    let (minify_filter, magnify_filter) = match (nearest, has_mipmaps) {
+        (true, true) => (MinifySamplerFilter::NearestMipmapNearest, MagnifySamplerFilter::Nearest),
+        (true, false) => (MinifySamplerFilter::Nearest, MagnifySamplerFilter::Nearest),
+        (false, true) => (MinifySamplerFilter::LinearMipmapLinear, MagnifySamplerFilter::Linear),
+        (false, false) => (MinifySamplerFilter::Linear, MagnifySamplerFilter::Linear),
+    };
+
+    SamplerBehavior {
+        wrap_function: (SamplerWrapFunction::Repeat, SamplerWrapFunction::Repeat,
+                        SamplerWrapFunction::Repeat),
+        minify_filter,
+        magnify_filter,
+        .. Default::default()
+    }
+}
+
 /// Builds a new texture.
 ///
 /// # Panic
@@ -185,23 +718,26 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         (&None, _) => (gl::RGBA, gl::UNSIGNED_BYTE),
     };
 
-    let (filtering, mipmap_filtering) = match format {
-        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormatRequest::AnyIntegral => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormatRequest::AnyUnsigned => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormatRequest::AnyStencil => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        _ => (gl::LINEAR, gl::LINEAR_MIPMAP_LINEAR),
-    };
+    let creation_behavior = get_creation_behavior(format, texture_levels > 1);
 
-    let is_multisampled = matches!(ty, Dimensions::Texture2dMultisample {..}
-        | Dimensions::Texture2dMultisampleArray {..});
+    let recipe = TextureRecipe {
+        ty,
+        levels: texture_levels,
+        teximg_internal_format,
+        storage_internal_format,
+        client_format,
+        client_type,
+        is_client_compressed,
+        data_bufsize,
+        min_filter: creation_behavior.minify_filter.to_glenum(),
+        mag_filter: creation_behavior.magnify_filter.to_glenum(),
+        generate_mipmaps: should_generate_mipmaps,
+        swizzle: Swizzle::IDENTITY,
+    };
 
     let mut ctxt = facade.get_context().make_current();
 
     let id = unsafe {
-        let has_mipmaps = texture_levels > 1;
         let data = data;
         let data_raw = if let Some((_, ref data)) = data {
             data.as_ptr() as *const c_void
@@ -218,251 +754,302 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
 
         let mut id: gl::types::GLuint = 0;
         ctxt.gl.GenTextures(1, &mut id);
+        init_texture(&mut ctxt, id, &recipe, data_raw);
+        id
+    };
 
-        {
-            ctxt.gl.BindTexture(bind_point, id);
-            let act = ctxt.state.active_texture as usize;
-            ctxt.state.texture_units[act].texture = id;
-        }
+    facade.get_context().resource_registry().insert_texture(id,
+        estimate_texture_size(ty, texture_levels as u32,
+                              storage_internal_format.unwrap_or(teximg_internal_format)),
+        Some(recipe));
 
-        if !is_multisampled {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
-        }
+    Ok(TextureAny {
+        context: facade.get_context().clone(),
+        id,
+        requested_format: format,
+        actual_format: Cell::new(None),
+        ty,
+        levels: texture_levels as u32,
+        generate_mipmaps: should_generate_mipmaps,
+        owned: true,
+        memory: None,
+        latest_shader_write: Cell::new(0),
+        swizzle: Cell::new(Swizzle::IDENTITY),
+        sampler_parameters: Cell::new(None),
+    })
+}
 
-        match ty {
-            Dimensions::Texture1d { .. } => (),
-            Dimensions::Texture2dMultisample { .. } => (),
-            Dimensions::Texture2dMultisampleArray { .. } => (),
-            _ => {
-                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
-            },
-        };
+/// Allocates the storage of a texture whose name has been generated but never bound, and sets
+/// its default parameters.
+///
+/// `data_raw` is the content of the main level, or null. It is ignored if a dimension is `0`.
+unsafe fn init_texture(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint, recipe: &TextureRecipe,
+                       data_raw: *const c_void)
+{
+    let TextureRecipe { ty, levels: texture_levels, teximg_internal_format,
+                        storage_internal_format, client_format, client_type,
+                        is_client_compressed, data_bufsize, min_filter, mag_filter,
+                        generate_mipmaps: should_generate_mipmaps, .. } = *recipe;
 
-        match ty {
-            Dimensions::Texture1d { .. } => (),
-            Dimensions::Texture2d { .. } => (),
-            Dimensions::Texture2dMultisample { .. } => (),
-            _ => {
-                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
-            },
-        };
+    let (width, height, depth, array_size, samples) = extract_dimensions(ty);
+    let bind_point = get_bind_point(ty);
+    let is_multisampled = matches!(ty, Dimensions::Texture2dMultisample {..}
+        | Dimensions::Texture2dMultisampleArray {..});
+    let has_mipmaps = texture_levels > 1;
 
-        if has_mipmaps {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  mipmap_filtering as i32);
-        } else if !is_multisampled {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  filtering as i32);
-        }
+    {
+        ctxt.gl.BindTexture(bind_point, id);
+        let act = ctxt.state.active_texture as usize;
+        ctxt.state.texture_units[act].texture = id;
+    }
 
-        if !has_mipmaps && (ctxt.version >= &Version(Api::Gl, 1, 2) ||
-                            ctxt.version >= &Version(Api::GlEs, 3, 0))
-        {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_BASE_LEVEL, 0);
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAX_LEVEL, 0);
-        }
+    if !is_multisampled {
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, mag_filter as i32);
+    }
 
-        if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY ||
-           bind_point == gl::TEXTURE_CUBE_MAP_ARRAY
-        {
-            let mut data_raw = data_raw;
+    match ty {
+        Dimensions::Texture1d { .. } => (),
+        Dimensions::Texture2dMultisample { .. } => (),
+        Dimensions::Texture2dMultisampleArray { .. } => (),
+        _ => {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+        },
+    };
 
-            let width = match width as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+    match ty {
+        Dimensions::Texture1d { .. } => (),
+        Dimensions::Texture2d { .. } => (),
+        Dimensions::Texture2dMultisample { .. } => (),
+        _ => {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
+        },
+    };
 
-            let height = match height.unwrap() as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+    if has_mipmaps || !is_multisampled {
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER, min_filter as i32);
+    }
 
-            let depth = match depth.or(array_size).unwrap() as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+    if !has_mipmaps && (ctxt.version >= &Version(Api::Gl, 1, 2) ||
+                        ctxt.version >= &Version(Api::GlEs, 3, 0))
+    {
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_BASE_LEVEL, 0);
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAX_LEVEL, 0);
+    }
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
-                ctxt.gl.TexStorage3D(bind_point, texture_levels,
-                                     storage_internal_format.unwrap() as gl::types::GLenum,
-                                     width, height, depth);
-
-                if !data_raw.is_null() {
-                    if is_client_compressed {
-                        ctxt.gl.CompressedTexSubImage3D(bind_point, 0, 0, 0, 0, width, height, depth,
-                                                         teximg_internal_format as u32,
-                                                         data_bufsize as i32, data_raw);
-                    } else {
-                        ctxt.gl.TexSubImage3D(bind_point, 0, 0, 0, 0, width, height, depth,
-                                              client_format, client_type, data_raw);
-                    }
-                }
+    if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY ||
+       bind_point == gl::TEXTURE_CUBE_MAP_ARRAY
+    {
+        let mut data_raw = data_raw;
 
-            } else if is_client_compressed && !data_raw.is_null() {
-                ctxt.gl.CompressedTexImage3D(bind_point, 0, teximg_internal_format as u32,
-                                   width, height, depth, 0, data_bufsize as i32, data_raw);
-            } else {
-                ctxt.gl.TexImage3D(bind_point, 0, teximg_internal_format as i32, width,
-                                   height, depth, 0, client_format as u32, client_type,
-                                   data_raw);
-            }
+        let width = match width as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-        } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY ||
-                  bind_point == gl::TEXTURE_CUBE_MAP
-        {
-            let mut data_raw = data_raw;
+        let height = match height.unwrap() as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-            let width = match width as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+        let depth = match depth.or(array_size).unwrap() as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-            let height = match height.or(array_size).unwrap() as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            ctxt.gl.TexStorage3D(bind_point, texture_levels,
+                                 storage_internal_format.unwrap() as gl::types::GLenum,
+                                 width, height, depth);
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
-                ctxt.gl.TexStorage2D(bind_point, texture_levels,
-                                     storage_internal_format.unwrap() as gl::types::GLenum,
-                                     width, height);
-
-                if !data_raw.is_null() {
-                    if is_client_compressed {
-                        ctxt.gl.CompressedTexSubImage2D(bind_point, 0, 0, 0, width, height,
-                                                         teximg_internal_format as u32,
-                                                         data_bufsize as i32, data_raw);
-                    } else {
-                        ctxt.gl.TexSubImage2D(bind_point, 0, 0, 0, width, height, client_format,
-                                              client_type, data_raw);
-                    }
+            if !data_raw.is_null() {
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage3D(bind_point, 0, 0, 0, 0, width, height, depth,
+                                                     teximg_internal_format as u32,
+                                                     data_bufsize as i32, data_raw);
+                } else {
+                    ctxt.gl.TexSubImage3D(bind_point, 0, 0, 0, 0, width, height, depth,
+                                          client_format, client_type, data_raw);
                 }
-
-            } else if is_client_compressed && !data_raw.is_null() {
-                ctxt.gl.CompressedTexImage2D(bind_point, 0, teximg_internal_format as u32,
-                                   width, height, 0, data_bufsize as i32, data_raw);
-            } else {
-                ctxt.gl.TexImage2D(bind_point, 0, teximg_internal_format as i32, width,
-                                   height, 0, client_format as u32, client_type, data_raw);
             }
 
-        } else if bind_point == gl::TEXTURE_2D_MULTISAMPLE {
-            assert!(data_raw.is_null());
+        } else if is_client_compressed && !data_raw.is_null() {
+            ctxt.gl.CompressedTexImage3D(bind_point, 0, teximg_internal_format as u32,
+                               width, height, depth, 0, data_bufsize as i32, data_raw);
+        } else {
+            ctxt.gl.TexImage3D(bind_point, 0, teximg_internal_format as i32, width,
+                               height, depth, 0, client_format as u32, client_type,
+                               data_raw);
+        }
 
-            let width = match width as gl::types::GLsizei {
-                0 => 1,
-                a => a
-            };
+    } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY ||
+              bind_point == gl::TEXTURE_CUBE_MAP
+    {
+        let mut data_raw = data_raw;
 
-            let height = match height.unwrap() as gl::types::GLsizei {
-                0 => 1,
-                a => a
-            };
+        let width = match width as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
-                ctxt.gl.TexStorage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE,
-                                                samples.unwrap() as gl::types::GLsizei,
-                                                storage_internal_format.unwrap() as gl::types::GLenum,
-                                                width, height, gl::TRUE);
+        let height = match height.or(array_size).unwrap() as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
 
-            } else if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_texture_multisample {
-                ctxt.gl.TexImage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE,
-                                              samples.unwrap() as gl::types::GLsizei,
-                                              teximg_internal_format as gl::types::GLenum,
-                                              width, height, gl::TRUE);
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            ctxt.gl.TexStorage2D(bind_point, texture_levels,
+                                 storage_internal_format.unwrap() as gl::types::GLenum,
+                                 width, height);
 
-            } else {
-                unreachable!();
+            if !data_raw.is_null() {
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage2D(bind_point, 0, 0, 0, width, height,
+                                                     teximg_internal_format as u32,
+                                                     data_bufsize as i32, data_raw);
+                } else {
+                    ctxt.gl.TexSubImage2D(bind_point, 0, 0, 0, width, height, client_format,
+                                          client_type, data_raw);
+                }
             }
 
-        } else if bind_point == gl::TEXTURE_2D_MULTISAMPLE_ARRAY {
-            assert!(data_raw.is_null());
+        } else if is_client_compressed && !data_raw.is_null() {
+            ctxt.gl.CompressedTexImage2D(bind_point, 0, teximg_internal_format as u32,
+                               width, height, 0, data_bufsize as i32, data_raw);
+        } else {
+            ctxt.gl.TexImage2D(bind_point, 0, teximg_internal_format as i32, width,
+                               height, 0, client_format as u32, client_type, data_raw);
+        }
 
-            let width = match width as gl::types::GLsizei {
-                0 => 1,
-                a => a
-            };
+    } else if bind_point == gl::TEXTURE_2D_MULTISAMPLE {
+        assert!(data_raw.is_null());
 
-            let height = match height.unwrap() as gl::types::GLsizei {
-                0 => 1,
-                a => a
-            };
+        let width = match width as gl::types::GLsizei {
+            0 => 1,
+            a => a
+        };
+
+        let height = match height.unwrap() as gl::types::GLsizei {
+            0 => 1,
+            a => a
+        };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
-                ctxt.gl.TexStorage3DMultisample(gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
-                                                samples.unwrap() as gl::types::GLsizei,
-                                                storage_internal_format.unwrap() as gl::types::GLenum,
-                                                width, height, array_size.unwrap() as gl::types::GLsizei,
-                                                gl::TRUE);
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            ctxt.gl.TexStorage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE,
+                                            samples.unwrap() as gl::types::GLsizei,
+                                            storage_internal_format.unwrap() as gl::types::GLenum,
+                                            width, height, gl::TRUE);
 
-            } else if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_texture_multisample {
-                ctxt.gl.TexImage3DMultisample(gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
-                                              samples.unwrap() as gl::types::GLsizei,
-                                              teximg_internal_format as gl::types::GLenum,
-                                              width, height, array_size.unwrap() as gl::types::GLsizei,
-                                              gl::TRUE);
+        } else if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_texture_multisample {
+            ctxt.gl.TexImage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE,
+                                          samples.unwrap() as gl::types::GLsizei,
+                                          teximg_internal_format as gl::types::GLenum,
+                                          width, height, gl::TRUE);
 
-            } else {
-                unreachable!();
-            }
+        } else {
+            unreachable!();
+        }
 
-        } else if bind_point == gl::TEXTURE_1D {
-            let mut data_raw = data_raw;
+    } else if bind_point == gl::TEXTURE_2D_MULTISAMPLE_ARRAY {
+        assert!(data_raw.is_null());
 
-            let width = match width as gl::types::GLsizei {
-                0 => { data_raw = ptr::null(); 1 },
-                a => a
-            };
+        let width = match width as gl::types::GLsizei {
+            0 => 1,
+            a => a
+        };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
-                ctxt.gl.TexStorage1D(bind_point, texture_levels,
-                                     storage_internal_format.unwrap() as gl::types::GLenum,
-                                     width);
-
-                if !data_raw.is_null() {
-                    if is_client_compressed {
-                        ctxt.gl.CompressedTexSubImage1D(bind_point, 0, 0, width,
-                                                         teximg_internal_format as u32,
-                                                         data_bufsize as i32, data_raw);
-                    } else {
-                        ctxt.gl.TexSubImage1D(bind_point, 0, 0, width, client_format,
-                                              client_type, data_raw);
-                    }
-                }
+        let height = match height.unwrap() as gl::types::GLsizei {
+            0 => 1,
+            a => a
+        };
 
-            } else if is_client_compressed && !data_raw.is_null() {
-                ctxt.gl.CompressedTexImage1D(bind_point, 0, teximg_internal_format as u32,
-                                   width, 0, data_bufsize as i32, data_raw);
-            } else {
-                ctxt.gl.TexImage1D(bind_point, 0, teximg_internal_format as i32, width,
-                                   0, client_format as u32, client_type, data_raw);
-            }
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            ctxt.gl.TexStorage3DMultisample(gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
+                                            samples.unwrap() as gl::types::GLsizei,
+                                            storage_internal_format.unwrap() as gl::types::GLenum,
+                                            width, height, array_size.unwrap() as gl::types::GLsizei,
+                                            gl::TRUE);
+
+        } else if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_texture_multisample {
+            ctxt.gl.TexImage3DMultisample(gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
+                                          samples.unwrap() as gl::types::GLsizei,
+                                          teximg_internal_format as gl::types::GLenum,
+                                          width, height, array_size.unwrap() as gl::types::GLsizei,
+                                          gl::TRUE);
 
         } else {
             unreachable!();
         }
 
-        // only generate mipmaps for color textures
-        if should_generate_mipmaps {
-            generate_mipmaps(&ctxt, bind_point);
+    } else if bind_point == gl::TEXTURE_1D {
+        let mut data_raw = data_raw;
+
+        let width = match width as gl::types::GLsizei {
+            0 => { data_raw = ptr::null(); 1 },
+            a => a
+        };
+
+        if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            ctxt.gl.TexStorage1D(bind_point, texture_levels,
+                                 storage_internal_format.unwrap() as gl::types::GLenum,
+                                 width);
+
+            if !data_raw.is_null() {
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage1D(bind_point, 0, 0, width,
+                                                     teximg_internal_format as u32,
+                                                     data_bufsize as i32, data_raw);
+                } else {
+                    ctxt.gl.TexSubImage1D(bind_point, 0, 0, width, client_format,
+                                          client_type, data_raw);
+                }
+            }
+
+        } else if is_client_compressed && !data_raw.is_null() {
+            ctxt.gl.CompressedTexImage1D(bind_point, 0, teximg_internal_format as u32,
+                               width, 0, data_bufsize as i32, data_raw);
+        } else {
+            ctxt.gl.TexImage1D(bind_point, 0, teximg_internal_format as i32, width,
+                               0, client_format as u32, client_type, data_raw);
         }
 
-        id
-    };
+    } else {
+        unreachable!();
+    }
 
-    Ok(TextureAny {
-        context: facade.get_context().clone(),
-        id,
-        requested_format: format,
-        actual_format: Cell::new(None),
-        ty,
-        levels: texture_levels as u32,
-        generate_mipmaps: should_generate_mipmaps,
-        owned: true,
-        memory: None,
-        latest_shader_write: Cell::new(0),
-    })
+    // only generate mipmaps for color textures
+    if should_generate_mipmaps {
+        generate_mipmaps(ctxt, bind_point);
+    }
+}
+
+/// Creates a texture again after a context loss. The name must have been generated in the
+/// current context.
+///
+/// The content of the texture is undefined.
+pub unsafe fn recreate_texture(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint,
+                               recipe: &TextureRecipe)
+{
+    init_texture(ctxt, id, recipe, ptr::null());
+
+    if recipe.swizzle != Swizzle::IDENTITY {
+        apply_swizzle(ctxt, get_bind_point(recipe.ty), recipe.swizzle);
+    }
+}
+
+/// Sets the swizzle mask of the texture bound to `bind_point` on the active texture unit.
+unsafe fn apply_swizzle(ctxt: &mut CommandContext<'_>, bind_point: gl::types::GLenum,
+                        swizzle: Swizzle)
+{
+    // `GL_TEXTURE_SWIZZLE_RGBA` isn't available on OpenGL ES
+    ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_SWIZZLE_R,
+                          swizzle.r.to_glenum() as gl::types::GLint);
+    ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_SWIZZLE_G,
+                          swizzle.g.to_glenum() as gl::types::GLint);
+    ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_SWIZZLE_B,
+                          swizzle.b.to_glenum() as gl::types::GLint);
+    ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_SWIZZLE_A,
+                          swizzle.a.to_glenum() as gl::types::GLint);
 }
 
 /// Builds a new texture reference from an existing, externally created OpenGL texture.
@@ -483,18 +1070,107 @@ pub unsafe fn from_id<F: Facade + ?Sized>(facade: &F,
         let ctxt = facade.get_context().make_current();
         generate_mipmaps(&ctxt, get_bind_point(ty));
     }
-    TextureAny {
+    if owned {
+        let internal_format = image_format::format_request_to_glenum(facade.get_context(), format,
+                                                                     image_format::RequestType::TexStorage);
+        facade.get_context().resource_registry().insert_texture(id,
+            estimate_texture_size(ty, mipmap_levels, internal_format.unwrap_or(gl::RGBA8)),
+            None);
+    }
+    TextureAny {
+        context: facade.get_context().clone(),
+        id,
+        requested_format: format,
+        actual_format: Cell::new(None),
+        ty,
+        levels: mipmap_levels,
+        generate_mipmaps: should_generate_mipmaps,
+        owned,
+        memory: None,
+        latest_shader_write: Cell::new(0),
+        swizzle: Cell::new(Swizzle::IDENTITY),
+        sampler_parameters: Cell::new(None),
+    }
+}
+
+/// Prepares a texture for being sent to another context of the same share group.
+///
+/// Returns the texture if it is backed by external memory, which can't be transferred.
+pub fn into_transfer<T>(mut texture: TextureAny) -> Result<TextureTransfer<T>, TextureAny> {
+    if texture.memory.is_some() {
+        return Err(texture);
+    }
+
+    let fence = {
+        let mut ctxt = texture.context.make_current();
+        unsafe { TransferFence::new(&mut ctxt) }
+    };
+
+    let transfer = TextureTransfer {
+        share_group: texture.context.get_share_group().clone(),
+        id: texture.id,
+        requested_format: texture.requested_format,
+        actual_format: texture.actual_format.get(),
+        ty: texture.ty,
+        levels: texture.levels,
+        generate_mipmaps: texture.generate_mipmaps,
+        owned: texture.owned,
+        swizzle: texture.swizzle.get(),
+        sampler_parameters: texture.sampler_parameters.get(),
+        fence,
+        marker: PhantomData,
+    };
+
+    texture.context.resource_registry().remove(ResourceKind::Texture, texture.id);
+
+    // the destructor purges the FBOs and the bindings of this context, but must not delete
+    // the texture as it now belongs to the transfer
+    texture.owned = false;
+
+    Ok(transfer)
+}
+
+/// Takes ownership of a texture that has been sent by another context with `into_transfer`.
+///
+/// Returns the transfer if the context of `facade` doesn't belong to the same share group.
+pub fn from_transfer<F: Facade + ?Sized, T>(facade: &F, transfer: TextureTransfer<T>)
+                                            -> Result<TextureAny, TextureTransfer<T>>
+{
+    if *facade.get_context().get_share_group() != transfer.share_group {
+        return Err(transfer);
+    }
+
+    let TextureTransfer { id, requested_format, actual_format, ty, levels, generate_mipmaps,
+                          owned, swizzle, sampler_parameters, fence, .. } = transfer;
+
+    {
+        let mut ctxt = facade.get_context().make_current();
+        unsafe { fence.wait_and_drop(&mut ctxt) };
+    }
+
+    if owned {
+        let internal_format = image_format::format_request_to_glenum(facade.get_context(),
+                                                                     requested_format,
+                                                                     image_format::RequestType::TexStorage);
+        // the texture has been created by another context, which didn't keep the recipe
+        facade.get_context().resource_registry().insert_texture(id,
+            estimate_texture_size(ty, levels, internal_format.unwrap_or(gl::RGBA8)), None);
+    }
+
+    Ok(TextureAny {
         context: facade.get_context().clone(),
         id,
-        requested_format: format,
-        actual_format: Cell::new(None),
+        requested_format,
+        actual_format: Cell::new(actual_format),
         ty,
-        levels: mipmap_levels,
-        generate_mipmaps: should_generate_mipmaps,
+        levels,
+        generate_mipmaps,
         owned,
         memory: None,
         latest_shader_write: Cell::new(0),
-    }
+        swizzle: Cell::new(swizzle),
+        sampler_parameters: Cell::new(sampler_parameters),
+    })
 }
 
 /// Builds a new texture reference from an existing texture, externally created by a foreign
@@ -519,12 +1195,8 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
 
     let storage_internal_format = format.to_glenum();
 
-     let (filtering, mipmap_filtering) = match format {
-        TextureFormat::UncompressedIntegral(_) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormat::UncompressedUnsigned(_) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        TextureFormat::StencilFormat(_) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
-        _ => (gl::LINEAR, gl::LINEAR_MIPMAP_LINEAR),
-     };
+    let creation_behavior = get_creation_behavior(TextureFormatRequest::Specific(format),
+                                                  texture_levels > 1);
 
     let is_multisampled = matches!(ty, Dimensions::Texture2dMultisample {..}
                                    | Dimensions::Texture2dMultisampleArray {..});
@@ -547,7 +1219,8 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
 
         if !is_multisampled {
             ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER,
+                                  creation_behavior.magnify_filter.to_glenum() as i32);
         }
 
         match ty {
@@ -568,12 +1241,9 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
             },
         };
 
-        if has_mipmaps {
+        if has_mipmaps || !is_multisampled {
             ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  mipmap_filtering as i32);
-        } else if !is_multisampled {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  filtering as i32);
+                                  creation_behavior.minify_filter.to_glenum() as i32);
         }
 
         if !has_mipmaps && (ctxt.version >= &Version(Api::Gl, 1, 2) ||
@@ -611,6 +1281,10 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
 
         id
     };
+
+    // the memory of the texture can't be imported again
+    facade.get_context().resource_registry().insert_texture(id,
+        estimate_texture_size(ty, mipmap_levels, storage_internal_format), None);
     
     Ok(TextureAny {
         context: facade.get_context().clone(),
@@ -623,6 +1297,139 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
         owned: false,
         memory: Some(memory),
         latest_shader_write: Cell::new(0),
+        swizzle: Cell::new(Swizzle::IDENTITY),
+        sampler_parameters: Cell::new(None),
+    })
+}
+
+/// Returns true if a texture of type `source` can be viewed as a texture of type `view`.
+fn is_view_type_compatible(source: Dimensions, view: TextureViewType) -> bool {
+    match source {
+        Dimensions::Texture1d { .. } | Dimensions::Texture1dArray { .. } => {
+            matches!(view, TextureViewType::Texture1d | TextureViewType::Texture1dArray)
+        },
+        Dimensions::Texture2d { .. } => {
+            matches!(view, TextureViewType::Texture2d | TextureViewType::Texture2dArray)
+        },
+        Dimensions::Texture2dArray { .. } | Dimensions::Cubemap { .. } |
+        Dimensions::CubemapArray { .. } => {
+            matches!(view, TextureViewType::Texture2d | TextureViewType::Texture2dArray |
+                           TextureViewType::Cubemap | TextureViewType::CubemapArray)
+        },
+        Dimensions::Texture2dMultisample { .. } | Dimensions::Texture2dMultisampleArray { .. } => {
+            matches!(view, TextureViewType::Texture2dMultisample |
+                           TextureViewType::Texture2dMultisampleArray)
+        },
+        Dimensions::Texture3d { .. } => view == TextureViewType::Texture3d,
+    }
+}
+
+/// Builds a new texture that shares the storage of `source`.
+///
+/// See `TextureAny::new_view`.
+fn new_view(source: &TextureAny, format: TextureFormat, ty: TextureViewType,
+            levels: Range<u32>, layers: Range<u32>)
+            -> Result<TextureAny, TextureViewCreationError>
+{
+    if !super::is_texture_view_supported(&*source.context) {
+        return Err(TextureViewCreationError::NotSupported);
+    }
+
+    if !is_view_type_compatible(source.ty, ty) {
+        return Err(TextureViewCreationError::IncompatibleType);
+    }
+
+    if levels.start >= levels.end || levels.end > source.levels {
+        return Err(TextureViewCreationError::InvalidLevels);
+    }
+
+    let source_layers = source.get_layer_faces_count(0);
+
+    let num_layers = layers.end.saturating_sub(layers.start);
+    let layers_valid = num_layers >= 1 && layers.end <= source_layers && match ty {
+        TextureViewType::Texture1d | TextureViewType::Texture2d |
+        TextureViewType::Texture2dMultisample | TextureViewType::Texture3d => num_layers == 1,
+        TextureViewType::Cubemap => num_layers == 6,
+        TextureViewType::CubemapArray => num_layers % 6 == 0,
+        TextureViewType::Texture1dArray | TextureViewType::Texture2dArray |
+        TextureViewType::Texture2dMultisampleArray => true,
+    };
+
+    if !layers_valid {
+        return Err(TextureViewCreationError::InvalidLayers);
+    }
+
+    // dimensions of the first mipmap level of the view
+    let width = cmp::max(1, source.get_width() >> levels.start);
+    let height = cmp::max(1, source.get_height().unwrap_or(1) >> levels.start);
+    let depth = cmp::max(1, source.get_depth().unwrap_or(1) >> levels.start);
+    let samples = source.get_samples().unwrap_or(0);
+
+    let dimensions = match ty {
+        TextureViewType::Texture1d => Dimensions::Texture1d { width },
+        TextureViewType::Texture1dArray => {
+            Dimensions::Texture1dArray { width, array_size: num_layers }
+        },
+        TextureViewType::Texture2d => Dimensions::Texture2d { width, height },
+        TextureViewType::Texture2dArray => {
+            Dimensions::Texture2dArray { width, height, array_size: num_layers }
+        },
+        TextureViewType::Texture2dMultisample => {
+            Dimensions::Texture2dMultisample { width, height, samples }
+        },
+        TextureViewType::Texture2dMultisampleArray => {
+            Dimensions::Texture2dMultisampleArray { width, height, array_size: num_layers, samples }
+        },
+        TextureViewType::Texture3d => Dimensions::Texture3d { width, height, depth },
+        TextureViewType::Cubemap | TextureViewType::CubemapArray if width != height => {
+            return Err(TextureViewCreationError::IncompatibleType);
+        },
+        TextureViewType::Cubemap => Dimensions::Cubemap { dimension: width },
+        TextureViewType::CubemapArray => {
+            Dimensions::CubemapArray { dimension: width, array_size: num_layers / 6 }
+        },
+    };
+
+    let mut ctxt = source.context.make_current();
+
+    let id = unsafe {
+        let bind_point = source.bind_to_current(&mut ctxt);
+
+        let mut immutable = 0;
+        ctxt.gl.GetTexParameteriv(bind_point, gl::TEXTURE_IMMUTABLE_FORMAT, &mut immutable);
+        if immutable == 0 {
+            return Err(TextureViewCreationError::MutableStorage);
+        }
+
+        let source_format = get_level_internal_format(&mut ctxt, source, 0);
+        if !image_format::is_view_compatible(source_format, format.to_glenum()) {
+            return Err(TextureViewCreationError::IncompatibleFormat);
+        }
+
+        // the name passed to `glTextureView` must never have been bound
+        let mut id: gl::types::GLuint = 0;
+        ctxt.gl.GenTextures(1, &mut id);
+        ctxt.gl.TextureView(id, get_bind_point(dimensions), source.id, format.to_glenum(),
+                            levels.start, levels.end - levels.start, layers.start, num_layers);
+        id
+    };
+
+    // the storage of a view belongs to the source texture
+    source.context.resource_registry().insert_texture(id, 0, None);
+
+    Ok(TextureAny {
+        context: source.context.clone(),
+        id,
+        requested_format: TextureFormatRequest::Specific(format),
+        actual_format: Cell::new(None),
+        ty: dimensions,
+        levels: levels.end - levels.start,
+        generate_mipmaps: source.generate_mipmaps && levels.end - levels.start > 1,
+        owned: true,
+        memory: None,
+        latest_shader_write: Cell::new(source.latest_shader_write.get()),
+        swizzle: Cell::new(Swizzle::IDENTITY),
+        sampler_parameters: Cell::new(None),
     })
 }
 
@@ -828,6 +1635,136 @@ impl TextureAny {
         self.bind_to_current(&mut ctxt);
         generate_mipmaps(&ctxt, self.get_bind_point());
     }
+
+    /// Builds a new texture that shares the storage of `source`, without copying anything.
+    ///
+    /// The view covers the mipmap levels in `levels` and the layers in `layers` of the source,
+    /// and interprets their content with `format`. For cubemaps and cubemap arrays, each face
+    /// counts as one layer. Writing to the view writes to the source and vice versa, and the
+    /// storage remains valid as long as either of them is alive.
+    ///
+    /// The source must have an immutable storage, which is the case for textures created by
+    /// glium on backends that support texture views. `format` must be identical to the format of
+    /// the source or belong to the same compatibility class, see
+    /// `TextureFormat::is_view_compatible`. For example an sRGB texture can be viewed as linear,
+    /// or a range of layers of a 2D array texture can be viewed as a single 2D texture.
+    #[inline]
+    pub fn new_view(source: &TextureAny, format: TextureFormat, ty: TextureViewType,
+                    levels: Range<u32>, layers: Range<u32>)
+                    -> Result<TextureAny, TextureViewCreationError>
+    {
+        new_view(source, format, ty, levels, layers)
+    }
+
+    /// Prepares the texture for being sent to another context of the same share group, for
+    /// example in order to upload data on a worker thread and use it on the main thread.
+    ///
+    /// The returned object can be sent to another thread, and must be turned back into a
+    /// texture with `TextureAny::from_transfer`. This context no longer tracks the texture.
+    ///
+    /// Returns the texture if it is backed by external memory, which can't be transferred.
+    ///
+    /// # Implementation
+    ///
+    /// Inserts a fence in the commands queue then calls `glFlush`. If fences are not supported,
+    /// calls `glFinish` instead.
+    #[inline]
+    pub fn into_transfer(self) -> Result<TextureTransfer, TextureAny> {
+        into_transfer(self)
+    }
+
+    /// Takes ownership of a texture that has been sent by another context with `into_transfer`.
+    ///
+    /// Returns the transfer if the context of `facade` doesn't belong to the same share group
+    /// as the context that has sent it.
+    ///
+    /// # Implementation
+    ///
+    /// Calls `glWaitSync` on the fence of the transfer, so that the commands that use the texture
+    /// are executed after the ones that have been issued by the other context.
+    #[inline]
+    pub fn from_transfer<F: Facade + ?Sized>(facade: &F, transfer: TextureTransfer)
+                                             -> Result<TextureAny, TextureTransfer>
+    {
+        from_transfer(facade, transfer)
+    }
+
+    /// Returns the number of layers of a mipmap level, as seen by `glCopyImageSubData` and
+    /// `glTextureView`. Each face of a cubemap is a layer, and each slice of a 3D texture is
+    /// a layer.
+    fn get_layer_faces_count(&self, level: u32) -> u32 {
+        match self.ty {
+            Dimensions::Texture3d { depth, .. } => cmp::max(1, depth >> level),
+            Dimensions::Cubemap { .. } => 6,
+            Dimensions::CubemapArray { array_size, .. } => array_size * 6,
+            _ => self.get_array_size().unwrap_or(1),
+        }
+    }
+
+    /// Returns true if the texture was requested with a compressed format.
+    fn is_compressed(&self) -> bool {
+        matches!(self.requested_format,
+                 TextureFormatRequest::AnyCompressed | TextureFormatRequest::AnyCompressedSrgb |
+                 TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
+                 TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)))
+    }
+
+    /// Returns the swizzle mask of the texture.
+    ///
+    /// For textures created with `from_id`, this is `Swizzle::IDENTITY` until `set_swizzle`
+    /// is called, even if the texture was modified externally.
+    #[inline]
+    pub fn get_swizzle(&self) -> Swizzle {
+        self.swizzle.get()
+    }
+
+    /// Changes the swizzle mask of the texture.
+    ///
+    /// The swizzle mask is applied when the texture is sampled from a shader. It allows for
+    /// example to use a single-channel texture as a grayscale image or as an alpha mask without
+    /// modifying the shader. Writing to the texture, reading it back or using it as an image
+    /// unit ignores the swizzle mask.
+    ///
+    /// Returns an error if the backend doesn't support swizzle masks. See
+    /// `is_texture_swizzle_supported`.
+    pub fn set_swizzle(&self, swizzle: Swizzle) -> Result<(), SwizzleNotSupportedError> {
+        if !super::is_texture_swizzle_supported(&*self.context) {
+            return Err(SwizzleNotSupportedError);
+        }
+
+        if self.swizzle.get() == swizzle {
+            return Ok(());
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        unsafe {
+            let bind_point = self.bind_to_current(&mut ctxt);
+            apply_swizzle(&mut ctxt, bind_point, swizzle);
+        }
+
+        if let Some(&mut Resource { recipe: Some(ref mut recipe), .. }) =
+            self.context.resource_registry().textures.get_mut(&self.id)
+        {
+            recipe.swizzle = swizzle;
+        }
+
+        self.swizzle.set(swizzle);
+        Ok(())
+    }
+
+    /// Gives a label to the texture, which is shown by debuggers such as RenderDoc and in
+    /// `Context::get_resources_report`.
+    ///
+    /// Returns an error if the backend doesn't support `GL_KHR_debug`, in which case the label
+    /// is only shown in the resources report.
+    pub fn set_label(&self, label: &str) -> Result<(), LabelNotSupportedError> {
+        self.context.resource_registry().set_label(ResourceKind::Texture, self.id,
+                                                   Some(label.to_owned()));
+
+        let mut ctxt = self.context.make_current();
+        unsafe { debug::set_object_label(&mut ctxt, gl::TEXTURE, self.id, label) }
+    }
 }
 
 impl TextureExt for TextureAny {
@@ -885,9 +1822,20 @@ impl TextureExt for TextureAny {
         }        
     }
 
+    #[inline]
+    fn get_sampler_parameters(&self) -> Option<SamplerBehavior> {
+        self.sampler_parameters.get()
+    }
 
-
-
+    unsafe fn set_sampler_parameters(&self, ctxt: &mut CommandContext<'_>,
+                                     behavior: Option<SamplerBehavior>)
+    {
+        let parameters = behavior.unwrap_or_else(|| {
+            get_creation_behavior(self.requested_format, self.levels > 1)
+        });
+        crate::sampler_object::set_texture_parameters(ctxt, self.get_bind_point(), &parameters);
+        self.sampler_parameters.set(behavior);
+    }
 }
 
 impl GlObject for TextureAny {
@@ -913,6 +1861,8 @@ impl Drop for TextureAny {
         let mut ctxt = self.context.make_current();
 
         // removing FBOs which contain this texture
+        // views have their own id, so FBOs attached to a view are only removed with the view
+        // and not with the texture that owns the storage
         fbo::FramebuffersContainer::purge_texture(&mut ctxt, self.id);
 
         // resetting the bindings
@@ -925,6 +1875,67 @@ impl Drop for TextureAny {
         if self.owned {
             unsafe { ctxt.gl.DeleteTextures(1, [ self.id ].as_ptr()); }
         }
+
+        if self.owned || self.memory.is_some() {
+            self.context.resource_registry().remove(ResourceKind::Texture, self.id);
+        }
+    }
+}
+
+/// What is needed to create a texture again after a context loss.
+pub struct TextureRecipe {
+    ty: Dimensions,
+    levels: gl::types::GLsizei,
+    teximg_internal_format: gl::types::GLenum,
+    storage_internal_format: Option<gl::types::GLenum>,
+    client_format: gl::types::GLenum,
+    client_type: gl::types::GLenum,
+    is_client_compressed: bool,
+    data_bufsize: usize,
+    min_filter: gl::types::GLenum,
+    mag_filter: gl::types::GLenum,
+    generate_mipmaps: bool,
+    swizzle: Swizzle,
+}
+
+/// A texture that is being sent to another context of the same share group.
+///
+/// Obtained with `into_transfer` and turned back into a texture with `from_transfer`. The type
+/// parameter is the type of texture that has been sent, for example `Texture2d`.
+///
+/// If this object is destroyed without being passed to `from_transfer`, the texture is leaked.
+pub struct TextureTransfer<T = TextureAny> {
+    share_group: ShareGroup,
+    id: gl::types::GLuint,
+    requested_format: TextureFormatRequest,
+    actual_format: Option<Result<InternalFormat, GetFormatError>>,
+    ty: Dimensions,
+    levels: u32,
+    generate_mipmaps: bool,
+    owned: bool,
+    swizzle: Swizzle,
+    sampler_parameters: Option<SamplerBehavior>,
+    fence: TransferFence,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> TextureTransfer<T> {
+    /// Returns the group of contexts that can receive this texture.
+    #[inline]
+    pub fn get_share_group(&self) -> &ShareGroup {
+        &self.share_group
+    }
+
+    /// Returns the type and dimensions of the texture.
+    #[inline]
+    pub fn dimensions(&self) -> Dimensions {
+        self.ty
+    }
+}
+
+impl<T> fmt::Debug for TextureTransfer<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "TextureTransfer {{ dimensions: {:?} }}", self.ty)
     }
 }
 
@@ -1129,6 +2140,95 @@ impl<'a> TextureAnyMipmap<'a> {
         self.raw_upload_from_pixel_buffer_impl(source, x, y, z, true);
     }
 
+    /// Copies a region of texels of this mipmap level to another mipmap level, without any
+    /// conversion or filtering. `target_offset` is the position of the region in the destination.
+    ///
+    /// `z` is a range of layers for array textures, where each face of a cubemap counts as
+    /// one layer, or of slices for 3D textures. See `TextureAnyImage::copy_to` for the
+    /// compatibility between formats.
+    ///
+    /// ## Compatibility
+    ///
+    /// If `glCopyImageSubData` is not supported, the layers are copied one by one. See
+    /// `TextureAnyImage::copy_to`.
+    pub fn copy_to(&self, x: Range<u32>, y: Range<u32>, z: Range<u32>,
+                   target: TextureAnyMipmap<'_>, target_offset: (u32, u32, u32))
+                   -> Result<(), CopyError>
+    {
+        if x.start > x.end || y.start > y.end || z.start > z.end {
+            return Err(CopyError::OutOfRange);
+        }
+
+        let mut ctxt = self.texture.context.make_current();
+
+        if !is_copy_image_supported(&ctxt) {
+            drop(ctxt);
+
+            if z.end > self.texture.get_layer_faces_count(self.level) ||
+               target_offset.2 + (z.end - z.start) > target.texture.get_layer_faces_count(target.level)
+            {
+                return Err(CopyError::OutOfRange);
+            }
+
+            let rect = Rect {
+                left: x.start,
+                bottom: y.start,
+                width: x.end - x.start,
+                height: y.end - y.start,
+            };
+
+            for layer in z.clone() {
+                let source = self.get_layer_face_image(layer);
+                let target = target.get_layer_face_image(layer - z.start + target_offset.2);
+                source.copy_to(&rect, target, target_offset.0, target_offset.1)?;
+            }
+
+            return Ok(());
+        }
+
+        unsafe {
+            let source = self.main_image().get_copy_image(&mut ctxt);
+            let target = target.main_image().get_copy_image(&mut ctxt);
+            copy_image_sub_data(&mut ctxt, &source, (x.start, y.start, z.start), &target,
+                                target_offset, (x.end - x.start, y.end - y.start, z.end - z.start))
+        }
+    }
+
+    /// Returns the image corresponding to a layer, where each face of a cubemap counts as one
+    /// layer.
+    fn get_layer_face_image(&self, layer: u32) -> TextureAnyImage<'a> {
+        let (layer, cube_layer) = match self.texture.ty {
+            Dimensions::Cubemap { .. } | Dimensions::CubemapArray { .. } => {
+                let face = match layer % 6 {
+                    0 => CubeLayer::PositiveX,
+                    1 => CubeLayer::NegativeX,
+                    2 => CubeLayer::PositiveY,
+                    3 => CubeLayer::NegativeY,
+                    4 => CubeLayer::PositiveZ,
+                    _ => CubeLayer::NegativeZ,
+                };
+
+                (layer / 6, Some(face))
+            },
+            _ => (layer, None),
+        };
+
+        TextureAnyImage {
+            texture: self.texture,
+            layer,
+            level: self.level,
+            cube_layer,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the first image of this mipmap level.
+    #[inline]
+    fn main_image(&self) -> TextureAnyImage<'a> {
+        self.get_layer_face_image(0)
+    }
+
     fn raw_upload_from_pixel_buffer_impl<P>(&self, source: BufferSlice<'_, [P]>, x: Range<u32>,
                                             y: Range<u32>, z: Range<u32>, inverted: bool)
                                             where P: PixelValue
@@ -1524,6 +2624,60 @@ impl<'a> TextureAnyLayerMipmap<'a> {
     }
 }
 
+/// Destination of a copy of texels, see `TextureAnyImage::copy_to`.
+#[derive(Copy, Clone)]
+pub enum CopyTarget<'a> {
+    /// An image of a texture.
+    Texture(TextureAnyImage<'a>),
+    /// A renderbuffer.
+    RenderBuffer(&'a RenderBufferAny),
+}
+
+impl<'a> From<TextureAnyImage<'a>> for CopyTarget<'a> {
+    #[inline]
+    fn from(image: TextureAnyImage<'a>) -> CopyTarget<'a> {
+        CopyTarget::Texture(image)
+    }
+}
+
+impl<'a> From<&'a RenderBufferAny> for CopyTarget<'a> {
+    #[inline]
+    fn from(renderbuffer: &'a RenderBufferAny) -> CopyTarget<'a> {
+        CopyTarget::RenderBuffer(renderbuffer)
+    }
+}
+
+impl<'a> CopyTarget<'a> {
+    #[inline]
+    fn get_dimensions(&self) -> (u32, u32) {
+        match self {
+            CopyTarget::Texture(image) => (image.width, image.height.unwrap_or(1)),
+            CopyTarget::RenderBuffer(renderbuffer) => renderbuffer.get_dimensions(),
+        }
+    }
+
+    #[inline]
+    fn get_samples(&self) -> Option<u32> {
+        match self {
+            CopyTarget::Texture(image) => image.get_samples(),
+            CopyTarget::RenderBuffer(renderbuffer) => renderbuffer.get_samples(),
+        }
+    }
+
+    #[inline]
+    fn kind(&self) -> TextureKind {
+        self.as_attachment().kind()
+    }
+
+    #[inline]
+    fn as_attachment(&self) -> fbo::RegularAttachment<'a> {
+        match *self {
+            CopyTarget::Texture(image) => fbo::RegularAttachment::Texture(image),
+            CopyTarget::RenderBuffer(renderbuffer) => fbo::RegularAttachment::RenderBuffer(renderbuffer),
+        }
+    }
+}
+
 /// Represents a specific 2D image of a texture. 1D textures are considered as having a height of 1.
 #[derive(Copy, Clone)]
 pub struct TextureAnyImage<'a> {
@@ -1643,4 +2797,159 @@ impl<'a> TextureAnyImage<'a> {
             fbo::FramebuffersContainer::clear_buffer(&mut ctxt, &attachment, data);
         }
     }
+
+    /// Uploads compressed data to the whole image. `format` must be the compressed format of
+    /// the texture.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `format` is not a compressed format.
+    /// - Panics if the length of `data` doesn't match the dimensions of the image.
+    /// - Panics if the texture is not a 2D texture, a 2D textures array, a cubemap or a
+    ///   cubemaps array.
+    pub(crate) fn upload_compressed_data(&self, format: ClientFormatAny, data: &[u8]) {
+        assert!(format.is_compressed());
+
+        let height = self.height.unwrap_or(1);
+        if data.len() != format.get_buffer_size(self.width, Some(height), None, None) {
+            panic!("Texture data size mismatch");
+        }
+
+        let (internal_format, _) = image_format::client_format_to_glenum(&self.texture.context,
+                                                                        format,
+                                                                        self.texture.requested_format,
+                                                                        false).unwrap();
+
+        let mut ctxt = self.texture.context.make_current();
+
+        unsafe {
+            BufferAny::unbind_pixel_unpack(&mut ctxt);
+            let bind_point = self.texture.bind_to_current(&mut ctxt);
+
+            match self.texture.ty {
+                Dimensions::Texture2d { .. } | Dimensions::Cubemap { .. } => {
+                    let target = match self.cube_layer {
+                        Some(face) => {
+                            gl::TEXTURE_CUBE_MAP_POSITIVE_X +
+                                face.get_layer_index() as gl::types::GLenum
+                        },
+                        None => bind_point,
+                    };
+
+                    ctxt.gl.CompressedTexSubImage2D(target, self.level as gl::types::GLint,
+                                                    0, 0, self.width as gl::types::GLsizei,
+                                                    height as gl::types::GLsizei,
+                                                    internal_format,
+                                                    data.len() as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+                },
+
+                Dimensions::Texture2dArray { .. } | Dimensions::CubemapArray { .. } => {
+                    ctxt.gl.CompressedTexSubImage3D(bind_point, self.level as gl::types::GLint,
+                                                    0, 0,
+                                                    self.get_layer_face() as gl::types::GLint,
+                                                    self.width as gl::types::GLsizei,
+                                                    height as gl::types::GLsizei, 1,
+                                                    internal_format,
+                                                    data.len() as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+                },
+
+                _ => panic!("Can't upload compressed data to this kind of texture"),
+            }
+        }
+    }
+
+    /// Returns the index of the image for `glCopyImageSubData`.
+    #[inline]
+    fn get_layer_face(&self) -> u32 {
+        match self.cube_layer {
+            Some(face) => self.layer * 6 + face.get_layer_index() as u32,
+            None => self.layer,
+        }
+    }
+
+    /// Returns the description of the image for `glCopyImageSubData`.
+    unsafe fn get_copy_image(&self, ctxt: &mut CommandContext<'_>) -> CopyImage {
+        CopyImage {
+            id: self.texture.id,
+            target: self.texture.get_bind_point(),
+            level: self.level,
+            format: get_level_internal_format(ctxt, self.texture, self.level),
+            dimensions: (self.width, self.height.unwrap_or(1),
+                         self.texture.get_layer_faces_count(self.level)),
+            samples: self.get_samples(),
+        }
+    }
+
+    /// Copies a rectangle of texels of this image to another image or to a renderbuffer,
+    /// without any conversion or filtering. `left` and `bottom` are the position of the
+    /// rectangle in the destination.
+    ///
+    /// The formats of the source and of the destination must have the same size per texel,
+    /// for example an `R32F` image can be copied to an `RGBA8` image. A block of a compressed
+    /// format can also be copied to or from a texel of an uncompressed format of the same size.
+    /// Multisample images are copied sample by sample.
+    ///
+    /// ## Compatibility
+    ///
+    /// If `glCopyImageSubData` is not supported, images of the same renderable format are
+    /// copied with a framebuffer blit. Other images are read back from the source and uploaded
+    /// to the destination, which is only possible with desktop OpenGL and when the destination is
+    /// a texture. Copies that can't be done this way return `CopyError::NotSupported`.
+    pub fn copy_to<'b, T>(&self, source_rect: &Rect, target: T, left: u32, bottom: u32)
+                          -> Result<(), CopyError> where T: Into<CopyTarget<'b>>
+    {
+        let target = target.into();
+        let mut ctxt = self.texture.context.make_current();
+
+        unsafe {
+            let source = self.get_copy_image(&mut ctxt);
+
+            let (target_image, target_z) = match target {
+                CopyTarget::Texture(image) => (image.get_copy_image(&mut ctxt), image.get_layer_face()),
+                CopyTarget::RenderBuffer(renderbuffer) => {
+                    let (width, height) = renderbuffer.get_dimensions();
+                    let image = CopyImage {
+                        id: renderbuffer.get_id(),
+                        target: gl::RENDERBUFFER,
+                        level: 0,
+                        format: get_renderbuffer_internal_format(&mut ctxt, renderbuffer.get_id()),
+                        dimensions: (width, height, 1),
+                        samples: renderbuffer.get_samples(),
+                    };
+                    (image, 0)
+                },
+            };
+
+            let source_offset = (source_rect.left, source_rect.bottom, self.get_layer_face());
+            let size = (source_rect.width, source_rect.height, 1);
+
+            if is_copy_image_supported(&ctxt) {
+                return copy_image_sub_data(&mut ctxt, &source, source_offset, &target_image,
+                                           (left, bottom, target_z), size);
+            }
+
+            let blocks = check_copy_region(&source, source_offset, &target_image,
+                                           (left, bottom, target_z), size)?;
+
+            // blits convert texels between formats, and `GL_RGB9_E5` is the only uncompressed
+            // format that can't be attached to a framebuffer
+            let blit = source.format == target_image.format && !self.texture.is_compressed() &&
+                       source.format != gl::RGB9_E5;
+
+            match target {
+                CopyTarget::Texture(image) if !blit && is_readback_supported(&ctxt) => {
+                    readback_image(&mut ctxt, self, source.format,
+                                   (source_rect.left, source_rect.bottom), &image,
+                                   target_image.format, (left, bottom), blocks)
+                },
+                _ if source.format != target_image.format => Err(CopyError::NotSupported),
+                _ => {
+                    drop(ctxt);
+                    blit_image(self, source_rect, target, left, bottom)
+                },
+            }
+        }
+    }
 }
@@ -78,6 +78,7 @@ use crate::buffer::Content as BufferContent;
 
 use crate::uniforms::AsUniformValue;
 use crate::uniforms::UniformValue;
+use crate::uniforms::SamplerBehavior;
 
 /// Error that can happen while building the texture part of a buffer texture.
 #[derive(Copy, Clone, Debug)]
@@ -555,6 +556,17 @@ impl<'a> TextureExt for BufferTextureRef<'a> {
         // TODO: Right now this type of texture cannot be used in an image unit
     }
 
+    #[inline]
+    fn get_sampler_parameters(&self) -> Option<SamplerBehavior> {
+        // buffer textures can't be sampled with a sampler
+        None
+    }
+
+    #[inline]
+    unsafe fn set_sampler_parameters(&self, _: &mut CommandContext<'_>, _: Option<SamplerBehavior>) {
+        unreachable!()
+    }
+
 }
 
 ///
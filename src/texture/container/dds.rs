@@ -0,0 +1,147 @@
+//! Parsing of DDS files.
+//!
+//! See https://learn.microsoft.com/en-us/windows/win32/direct3ddds/dx-graphics-dds-pguide for
+//! the specification.
+
+use crate::texture::{CompressedFormat, CompressedSrgbFormat};
+
+use super::{Container, ContainerError, ContainerFormat};
+use super::{check_dimensions, get_level_dimension, read_bytes, read_u32};
+
+const MAGIC: [u8; 4] = *b"DDS ";
+const HEADER_SIZE: u32 = 124;
+
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+#[inline]
+pub fn is_dds(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
+pub fn parse(data: &[u8]) -> Result<Container<'_>, ContainerError> {
+    if !is_dds(data) {
+        return Err(ContainerError::UnknownContainer);
+    }
+
+    if read_u32(data, 4)? != HEADER_SIZE {
+        return Err(ContainerError::InvalidData);
+    }
+
+    let flags = read_u32(data, 8)?;
+    let height = read_u32(data, 12)?;
+    let width = read_u32(data, 16)?;
+    let mipmap_count = read_u32(data, 28)?;
+    let pixel_format_flags = read_u32(data, 80)?;
+    let four_cc = read_u32(data, 84)?;
+    let caps2 = read_u32(data, 112)?;
+
+    check_dimensions(width, height)?;
+
+    if caps2 & DDSCAPS2_VOLUME != 0 {
+        return Err(ContainerError::UnsupportedFeature);
+    }
+
+    // uncompressed data is described by bit masks instead of a FourCC
+    if pixel_format_flags & DDPF_FOURCC == 0 {
+        return Err(ContainerError::UnsupportedFormat);
+    }
+
+    let (format, array_size, cubemap, data_offset) = if four_cc == u32::from_le_bytes(*b"DX10") {
+        let dxgi_format = read_u32(data, 128)?;
+        let resource_dimension = read_u32(data, 132)?;
+        let misc_flag = read_u32(data, 136)?;
+        let array_size = read_u32(data, 140)?;
+
+        if resource_dimension != D3D10_RESOURCE_DIMENSION_TEXTURE2D {
+            return Err(ContainerError::UnsupportedFeature);
+        }
+
+        let format = get_dxgi_format(dxgi_format).ok_or(ContainerError::UnsupportedFormat)?;
+        let array_size = match array_size {
+            0 => return Err(ContainerError::InvalidData),
+            1 => None,
+            n => Some(n),
+        };
+
+        (format, array_size, misc_flag & DDS_RESOURCE_MISC_TEXTURECUBE != 0, 148)
+
+    } else {
+        let format = get_four_cc_format(four_cc).ok_or(ContainerError::UnsupportedFormat)?;
+        let cubemap = caps2 & DDSCAPS2_CUBEMAP != 0;
+
+        // legacy cubemaps can omit some of the faces
+        if cubemap && caps2 & DDSCAPS2_CUBEMAP_ALLFACES != DDSCAPS2_CUBEMAP_ALLFACES {
+            return Err(ContainerError::UnsupportedFeature);
+        }
+
+        (format, None, cubemap, 128)
+    };
+
+    let level_count = if flags & DDSD_MIPMAPCOUNT != 0 { mipmap_count.max(1) } else { 1 };
+    if level_count > 32 {
+        return Err(ContainerError::InvalidData);
+    }
+
+    let images = array_size.unwrap_or(1) as u64 * if cubemap { 6 } else { 1 };
+
+    // the file contains the whole mipmaps chain of each face of each layer, while the
+    // container stores the images level by level
+    let mut levels = vec![Vec::new(); level_count as usize];
+    let mut offset = data_offset;
+    for _ in 0 .. images {
+        for (level, level_images) in levels.iter_mut().enumerate() {
+            let size = format.get_image_size(get_level_dimension(width, level as u32),
+                                             get_level_dimension(height, level as u32))? as u64;
+            level_images.push(read_bytes(data, offset, size)?);
+            offset = offset.checked_add(size).ok_or(ContainerError::InvalidData)?;
+        }
+    }
+
+    Container::new(format, width, height, array_size, cubemap, levels)
+}
+
+/// Returns the format corresponding to the FourCC of a legacy header.
+fn get_four_cc_format(four_cc: u32) -> Option<ContainerFormat> {
+    use self::ContainerFormat::Compressed;
+
+    Some(match &four_cc.to_le_bytes() {
+        b"DXT1" => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        b"DXT3" => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        b"DXT5" => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        b"ATI1" | b"BC4U" => Compressed(CompressedFormat::RgtcFormatU),
+        b"BC4S" => Compressed(CompressedFormat::RgtcFormatI),
+        b"ATI2" | b"BC5U" => Compressed(CompressedFormat::RgtcFormatUU),
+        b"BC5S" => Compressed(CompressedFormat::RgtcFormatII),
+        _ => return None,
+    })
+}
+
+/// Returns the format corresponding to a `DXGI_FORMAT` of a DX10 header.
+fn get_dxgi_format(dxgi_format: u32) -> Option<ContainerFormat> {
+    use self::ContainerFormat::{Compressed, CompressedSrgb};
+
+    Some(match dxgi_format {
+        71 => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        72 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha),
+        74 => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        75 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt3Alpha),
+        77 => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        78 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt5Alpha),
+        80 => Compressed(CompressedFormat::RgtcFormatU),
+        81 => Compressed(CompressedFormat::RgtcFormatI),
+        83 => Compressed(CompressedFormat::RgtcFormatUU),
+        84 => Compressed(CompressedFormat::RgtcFormatII),
+        95 => Compressed(CompressedFormat::BptcUnsignedFloat3),
+        96 => Compressed(CompressedFormat::BptcSignedFloat3),
+        98 => Compressed(CompressedFormat::BptcUnorm4),
+        99 => CompressedSrgb(CompressedSrgbFormat::Bptc),
+        _ => return None,
+    })
+}
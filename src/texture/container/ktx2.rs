@@ -0,0 +1,134 @@
+//! Parsing of KTX2 files.
+//!
+//! See https://registry.khronos.org/KTX/specs/2.0/ktxspec.v2.html for the specification.
+
+use crate::texture::{CompressedFormat, CompressedSrgbFormat};
+
+use super::{Container, ContainerError, ContainerFormat};
+use super::{check_dimensions, read_bytes, read_u32, read_u64};
+
+const IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Offset of the level index, which follows the header and the index of the other sections.
+const LEVEL_INDEX_OFFSET: usize = 80;
+
+#[inline]
+pub fn is_ktx2(data: &[u8]) -> bool {
+    data.starts_with(&IDENTIFIER)
+}
+
+pub fn parse(data: &[u8]) -> Result<Container<'_>, ContainerError> {
+    if !is_ktx2(data) {
+        return Err(ContainerError::UnknownContainer);
+    }
+
+    let vk_format = read_u32(data, 12)?;
+    let width = read_u32(data, 20)?;
+    let height = read_u32(data, 24)?;
+    let depth = read_u32(data, 28)?;
+    let layer_count = read_u32(data, 32)?;
+    let face_count = read_u32(data, 36)?;
+    let level_count = read_u32(data, 40)?;
+    let supercompression_scheme = read_u32(data, 44)?;
+
+    // 1D textures, 3D textures and supercompressed data are not supported
+    if height == 0 || depth != 0 || supercompression_scheme != 0 {
+        return Err(ContainerError::UnsupportedFeature);
+    }
+
+    check_dimensions(width, height)?;
+
+    if face_count != 1 && face_count != 6 {
+        return Err(ContainerError::InvalidData);
+    }
+
+    let format = get_format(vk_format).ok_or(ContainerError::UnsupportedFormat)?;
+
+    // a level count of 0 asks the loader to generate the mipmaps, which is not possible with
+    // compressed formats
+    let level_count = level_count.max(1);
+    if level_count > 32 {
+        return Err(ContainerError::InvalidData);
+    }
+
+    let images_per_level = layer_count.max(1) as u64 * face_count as u64;
+
+    let mut levels = Vec::with_capacity(level_count as usize);
+    for level in 0 .. level_count as usize {
+        let index = LEVEL_INDEX_OFFSET + level * 24;
+        let level_data = read_bytes(data, read_u64(data, index)?, read_u64(data, index + 8)?)?;
+
+        if level_data.is_empty() || level_data.len() as u64 % images_per_level != 0 {
+            return Err(ContainerError::InvalidData);
+        }
+
+        let image_size = (level_data.len() as u64 / images_per_level) as usize;
+        levels.push(level_data.chunks(image_size).collect());
+    }
+
+    let array_size = if layer_count == 0 { None } else { Some(layer_count) };
+    Container::new(format, width, height, array_size, face_count == 6, levels)
+}
+
+/// Returns the format corresponding to a `VkFormat`.
+fn get_format(vk_format: u32) -> Option<ContainerFormat> {
+    use self::ContainerFormat::{Compressed, CompressedSrgb};
+
+    Some(match vk_format {
+        131 => Compressed(CompressedFormat::S3tcDxt1NoAlpha),
+        132 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1NoAlpha),
+        133 => Compressed(CompressedFormat::S3tcDxt1Alpha),
+        134 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha),
+        135 => Compressed(CompressedFormat::S3tcDxt3Alpha),
+        136 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt3Alpha),
+        137 => Compressed(CompressedFormat::S3tcDxt5Alpha),
+        138 => CompressedSrgb(CompressedSrgbFormat::S3tcDxt5Alpha),
+        139 => Compressed(CompressedFormat::RgtcFormatU),
+        140 => Compressed(CompressedFormat::RgtcFormatI),
+        141 => Compressed(CompressedFormat::RgtcFormatUU),
+        142 => Compressed(CompressedFormat::RgtcFormatII),
+        143 => Compressed(CompressedFormat::BptcUnsignedFloat3),
+        144 => Compressed(CompressedFormat::BptcSignedFloat3),
+        145 => Compressed(CompressedFormat::BptcUnorm4),
+        146 => CompressedSrgb(CompressedSrgbFormat::Bptc),
+        147 => Compressed(CompressedFormat::Etc2Rgb8),
+        148 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgb8),
+        149 => Compressed(CompressedFormat::Etc2Rgb8A1),
+        150 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgb8A1),
+        151 => Compressed(CompressedFormat::Etc2Rgba8),
+        152 => CompressedSrgb(CompressedSrgbFormat::Etc2Rgba8),
+        153 => Compressed(CompressedFormat::EacR11U),
+        154 => Compressed(CompressedFormat::EacR11I),
+        155 => Compressed(CompressedFormat::EacRg11UU),
+        156 => Compressed(CompressedFormat::EacRg11II),
+        157 => Compressed(CompressedFormat::Astc4x4),
+        158 => CompressedSrgb(CompressedSrgbFormat::Astc4x4),
+        159 => Compressed(CompressedFormat::Astc5x4),
+        160 => CompressedSrgb(CompressedSrgbFormat::Astc5x4),
+        161 => Compressed(CompressedFormat::Astc5x5),
+        162 => CompressedSrgb(CompressedSrgbFormat::Astc5x5),
+        163 => Compressed(CompressedFormat::Astc6x5),
+        164 => CompressedSrgb(CompressedSrgbFormat::Astc6x5),
+        165 => Compressed(CompressedFormat::Astc6x6),
+        166 => CompressedSrgb(CompressedSrgbFormat::Astc6x6),
+        167 => Compressed(CompressedFormat::Astc8x5),
+        168 => CompressedSrgb(CompressedSrgbFormat::Astc8x5),
+        169 => Compressed(CompressedFormat::Astc8x6),
+        170 => CompressedSrgb(CompressedSrgbFormat::Astc8x6),
+        171 => Compressed(CompressedFormat::Astc8x8),
+        172 => CompressedSrgb(CompressedSrgbFormat::Astc8x8),
+        173 => Compressed(CompressedFormat::Astc10x5),
+        174 => CompressedSrgb(CompressedSrgbFormat::Astc10x5),
+        175 => Compressed(CompressedFormat::Astc10x6),
+        176 => CompressedSrgb(CompressedSrgbFormat::Astc10x6),
+        177 => Compressed(CompressedFormat::Astc10x8),
+        178 => CompressedSrgb(CompressedSrgbFormat::Astc10x8),
+        179 => Compressed(CompressedFormat::Astc10x10),
+        180 => CompressedSrgb(CompressedSrgbFormat::Astc10x10),
+        181 => Compressed(CompressedFormat::Astc12x10),
+        182 => CompressedSrgb(CompressedSrgbFormat::Astc12x10),
+        183 => Compressed(CompressedFormat::Astc12x12),
+        184 => CompressedSrgb(CompressedSrgbFormat::Astc12x12),
+        _ => return None,
+    })
+}
@@ -0,0 +1,433 @@
+/*!
+Loading of compressed textures stored in KTX2 or DDS files.
+
+These containers store the images of a texture already compressed in a format that the GPU
+can use directly, including all the mipmaps, array layers and cubemap faces. Glium only reads
+the layout of the file; decoding the content of a PNG or JPEG is out of scope and is handled by
+the `image` crate.
+
+## Example
+
+```no_run
+# fn example(display: glium::Display) {
+let data = std::fs::read("texture.ktx2").unwrap();
+let texture = glium::texture::container::load(&display, &data).unwrap();
+# }
+```
+
+The images are uploaded in the order in which they are stored in the file, which is usually
+top-down. Since OpenGL expects the first row to be the bottom of the texture, you may have to
+flip the texture coordinates vertically.
+
+*/
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::backend::Facade;
+use crate::image_format::ClientFormatAny;
+use crate::texture::{CompressedFormat, CompressedSrgbFormat, CompressedMipmapsOption};
+use crate::texture::{CubeLayer, TextureAny, TextureCreationError};
+use crate::texture::{CompressedTexture2d, CompressedSrgbTexture2d};
+use crate::texture::{CompressedTexture2dArray, CompressedSrgbTexture2dArray};
+use crate::texture::{CompressedCubemap, CompressedSrgbCubemap};
+use crate::texture::{CompressedCubemapArray, CompressedSrgbCubemapArray};
+
+mod dds;
+mod ktx2;
+
+/// Maximum width and height of the images of a container. This is far above the maximum size
+/// of a texture of current hardware, and ensures that the size of an image can be computed
+/// without overflowing.
+const MAX_DIMENSION: u32 = 1 << 16;
+
+/// Order of the faces of a cubemap in both containers.
+const CUBE_FACES: [CubeLayer; 6] = [
+    CubeLayer::PositiveX, CubeLayer::NegativeX,
+    CubeLayer::PositiveY, CubeLayer::NegativeY,
+    CubeLayer::PositiveZ, CubeLayer::NegativeZ,
+];
+
+/// Error that can happen when parsing or loading a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerError {
+    /// The data is neither a KTX2 nor a DDS file.
+    UnknownContainer,
+
+    /// The file is truncated or its header is invalid.
+    InvalidData,
+
+    /// The images are not stored in a compressed format that glium knows about.
+    UnsupportedFormat,
+
+    /// The file uses a feature that is not supported, for example 3D textures or
+    /// supercompression.
+    UnsupportedFeature,
+
+    /// Error while creating the texture.
+    TextureCreationError(TextureCreationError),
+}
+
+impl From<TextureCreationError> for ContainerError {
+    #[inline]
+    fn from(err: TextureCreationError) -> ContainerError {
+        ContainerError::TextureCreationError(err)
+    }
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ContainerError::*;
+        let desc = match self {
+            UnknownContainer => "The data is neither a KTX2 nor a DDS file",
+            InvalidData => "The file is truncated or its header is invalid",
+            UnsupportedFormat => "The format of the images is not supported",
+            UnsupportedFeature => "The file uses a feature that is not supported",
+            TextureCreationError(_) => "Error while creating the texture",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for ContainerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::ContainerError::*;
+        match *self {
+            TextureCreationError(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Format of the images of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerFormat {
+    /// The images contain linear data.
+    Compressed(CompressedFormat),
+    /// The images contain sRGB data.
+    CompressedSrgb(CompressedSrgbFormat),
+}
+
+impl ContainerFormat {
+    #[inline]
+    fn to_client_format(self) -> ClientFormatAny {
+        match self {
+            ContainerFormat::Compressed(format) => ClientFormatAny::CompressedFormat(format),
+            ContainerFormat::CompressedSrgb(format) => {
+                ClientFormatAny::CompressedSrgbFormat(format)
+            },
+        }
+    }
+
+    /// Returns the size in bytes of an image of the given dimensions.
+    ///
+    /// The dimensions must have been checked with `check_dimensions`.
+    fn get_image_size(self, width: u32, height: u32) -> Result<usize, ContainerError> {
+        let format = self.to_client_format();
+
+        // the size is computed from a row and a column of blocks, so that a size that doesn't
+        // fit in a `usize` is detected instead of wrapping around
+        let block_size = format.get_buffer_size(1, Some(1), None, None);
+        let row_size = format.get_buffer_size(width, Some(1), None, None);
+        let rows = format.get_buffer_size(1, Some(height), None, None) / block_size;
+
+        row_size.checked_mul(rows).ok_or(ContainerError::InvalidData)
+    }
+}
+
+/// The images of a KTX2 or DDS file.
+///
+/// The images are not copied and borrow the content of the file.
+#[derive(Debug, Clone)]
+pub struct Container<'a> {
+    format: ContainerFormat,
+    width: u32,
+    height: u32,
+    array_size: Option<u32>,
+    cubemap: bool,
+
+    /// The images of each mipmap level, ordered by layer then by face.
+    levels: Vec<Vec<&'a [u8]>>,
+}
+
+impl<'a> Container<'a> {
+    /// Parses a KTX2 or DDS file. The kind of container is determined from the first bytes of
+    /// the data.
+    pub fn parse(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        if ktx2::is_ktx2(data) {
+            ktx2::parse(data)
+        } else if dds::is_dds(data) {
+            dds::parse(data)
+        } else {
+            Err(ContainerError::UnknownContainer)
+        }
+    }
+
+    /// Parses a KTX2 file.
+    #[inline]
+    pub fn parse_ktx2(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        ktx2::parse(data)
+    }
+
+    /// Parses a DDS file.
+    #[inline]
+    pub fn parse_dds(data: &'a [u8]) -> Result<Container<'a>, ContainerError> {
+        dds::parse(data)
+    }
+
+    /// Builds a container after checking that its images are consistent with its dimensions.
+    fn new(format: ContainerFormat, width: u32, height: u32, array_size: Option<u32>,
+           cubemap: bool, levels: Vec<Vec<&'a [u8]>>) -> Result<Container<'a>, ContainerError>
+    {
+        check_dimensions(width, height)?;
+
+        if width == 0 || height == 0 || array_size == Some(0) || levels.is_empty() ||
+           levels.len() as u32 > get_max_levels(width, height) || (cubemap && width != height)
+        {
+            return Err(ContainerError::InvalidData);
+        }
+
+        let images = array_size.unwrap_or(1) as usize * if cubemap { 6 } else { 1 };
+
+        for (level, level_images) in levels.iter().enumerate() {
+            let size = format.get_image_size(get_level_dimension(width, level as u32),
+                                             get_level_dimension(height, level as u32))?;
+
+            if level_images.len() != images || level_images.iter().any(|i| i.len() != size) {
+                return Err(ContainerError::InvalidData);
+            }
+        }
+
+        Ok(Container { format, width, height, array_size, cubemap, levels })
+    }
+
+    /// Returns the format of the images.
+    #[inline]
+    pub fn get_format(&self) -> ContainerFormat {
+        self.format
+    }
+
+    /// Returns the width of the top-level images.
+    #[inline]
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the top-level images.
+    #[inline]
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the number of layers if the file contains an array texture.
+    #[inline]
+    pub fn get_array_size(&self) -> Option<u32> {
+        self.array_size
+    }
+
+    /// Returns true if the file contains a cubemap or an array of cubemaps.
+    #[inline]
+    pub fn is_cubemap(&self) -> bool {
+        self.cubemap
+    }
+
+    /// Returns the number of mipmap levels stored in the file, including the top level.
+    #[inline]
+    pub fn get_mipmap_levels(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    /// Returns the compressed data of an image, or `None` if out of range.
+    ///
+    /// `face` must be `Some` if and only if the file contains a cubemap.
+    pub fn get_image(&self, level: u32, layer: u32, face: Option<CubeLayer>)
+                     -> Option<&'a [u8]>
+    {
+        if layer >= self.array_size.unwrap_or(1) || face.is_some() != self.cubemap {
+            return None;
+        }
+
+        let index = match face {
+            Some(face) => layer as usize * 6 + face.get_layer_index(),
+            None => layer as usize,
+        };
+
+        self.levels.get(level as usize).map(|images| images[index])
+    }
+
+    /// Creates a texture that matches the content of the file and uploads all the images to it.
+    ///
+    /// If the file contains only some of the mipmap levels, the texture is created with exactly
+    /// that number of levels.
+    pub fn load<F: ?Sized>(&self, facade: &F) -> Result<ContainerTexture, ContainerError>
+                           where F: Facade
+    {
+        let mipmaps = match self.levels.len() as u32 {
+            1 => CompressedMipmapsOption::NoMipmap,
+            n if n == get_max_levels(self.width, self.height) => {
+                CompressedMipmapsOption::EmptyMipmaps
+            },
+            n => CompressedMipmapsOption::EmptyMipmapsMax(n - 1),
+        };
+
+        let (width, height) = (self.width, self.height);
+
+        let texture = match (self.format, self.array_size, self.cubemap) {
+            (ContainerFormat::Compressed(f), None, false) => {
+                ContainerTexture::Texture2d(
+                    CompressedTexture2d::empty_with_format(facade, f, mipmaps, width, height)?)
+            },
+            (ContainerFormat::CompressedSrgb(f), None, false) => {
+                ContainerTexture::SrgbTexture2d(
+                    CompressedSrgbTexture2d::empty_with_format(facade, f, mipmaps, width,
+                                                               height)?)
+            },
+            (ContainerFormat::Compressed(f), Some(layers), false) => {
+                ContainerTexture::Texture2dArray(
+                    CompressedTexture2dArray::empty_with_format(facade, f, mipmaps, width,
+                                                                height, layers)?)
+            },
+            (ContainerFormat::CompressedSrgb(f), Some(layers), false) => {
+                ContainerTexture::SrgbTexture2dArray(
+                    CompressedSrgbTexture2dArray::empty_with_format(facade, f, mipmaps, width,
+                                                                    height, layers)?)
+            },
+            (ContainerFormat::Compressed(f), None, true) => {
+                ContainerTexture::Cubemap(
+                    CompressedCubemap::empty_with_format(facade, f, mipmaps, width)?)
+            },
+            (ContainerFormat::CompressedSrgb(f), None, true) => {
+                ContainerTexture::SrgbCubemap(
+                    CompressedSrgbCubemap::empty_with_format(facade, f, mipmaps, width)?)
+            },
+            (ContainerFormat::Compressed(f), Some(layers), true) => {
+                ContainerTexture::CubemapArray(
+                    CompressedCubemapArray::empty_with_format(facade, f, mipmaps, width,
+                                                              layers)?)
+            },
+            (ContainerFormat::CompressedSrgb(f), Some(layers), true) => {
+                ContainerTexture::SrgbCubemapArray(
+                    CompressedSrgbCubemapArray::empty_with_format(facade, f, mipmaps, width,
+                                                                  layers)?)
+            },
+        };
+
+        let client_format = self.format.to_client_format();
+        let faces = if self.cubemap { 6 } else { 1 };
+
+        for (level, images) in self.levels.iter().enumerate() {
+            let mipmap = texture.mipmap(level as u32).unwrap();
+
+            for (index, data) in images.iter().enumerate() {
+                let face = if self.cubemap { Some(CUBE_FACES[index % faces]) } else { None };
+                let image = mipmap.layer((index / faces) as u32).unwrap()
+                                  .into_image(face).unwrap();
+                image.upload_compressed_data(client_format, data);
+            }
+        }
+
+        Ok(texture)
+    }
+}
+
+/// A texture created from a container.
+///
+/// The kind of texture depends on the content of the file. All the variants dereference to
+/// `TextureAny`.
+pub enum ContainerTexture {
+    /// A 2D texture with linear data.
+    Texture2d(CompressedTexture2d),
+    /// A 2D texture with sRGB data.
+    SrgbTexture2d(CompressedSrgbTexture2d),
+    /// An array of 2D textures with linear data.
+    Texture2dArray(CompressedTexture2dArray),
+    /// An array of 2D textures with sRGB data.
+    SrgbTexture2dArray(CompressedSrgbTexture2dArray),
+    /// A cubemap with linear data.
+    Cubemap(CompressedCubemap),
+    /// A cubemap with sRGB data.
+    SrgbCubemap(CompressedSrgbCubemap),
+    /// An array of cubemaps with linear data.
+    CubemapArray(CompressedCubemapArray),
+    /// An array of cubemaps with sRGB data.
+    SrgbCubemapArray(CompressedSrgbCubemapArray),
+}
+
+impl Deref for ContainerTexture {
+    type Target = TextureAny;
+
+    #[inline]
+    fn deref(&self) -> &TextureAny {
+        match *self {
+            ContainerTexture::Texture2d(ref t) => t,
+            ContainerTexture::SrgbTexture2d(ref t) => t,
+            ContainerTexture::Texture2dArray(ref t) => t,
+            ContainerTexture::SrgbTexture2dArray(ref t) => t,
+            ContainerTexture::Cubemap(ref t) => t,
+            ContainerTexture::SrgbCubemap(ref t) => t,
+            ContainerTexture::CubemapArray(ref t) => t,
+            ContainerTexture::SrgbCubemapArray(ref t) => t,
+        }
+    }
+}
+
+impl fmt::Debug for ContainerTexture {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(fmt)
+    }
+}
+
+/// Parses a KTX2 or DDS file and creates the texture that it contains.
+///
+/// This is a shortcut for `Container::parse(data)?.load(facade)`.
+#[inline]
+pub fn load<F: ?Sized>(facade: &F, data: &[u8]) -> Result<ContainerTexture, ContainerError>
+                       where F: Facade
+{
+    Container::parse(data)?.load(facade)
+}
+
+/// Returns the number of levels of a full mipmaps chain.
+#[inline]
+fn get_max_levels(width: u32, height: u32) -> u32 {
+    32 - width.max(height).leading_zeros()
+}
+
+/// Returns an error if the dimensions of the top-level images are larger than `MAX_DIMENSION`.
+#[inline]
+fn check_dimensions(width: u32, height: u32) -> Result<(), ContainerError> {
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        return Err(ContainerError::InvalidData);
+    }
+
+    Ok(())
+}
+
+/// Returns the dimension of a mipmap level.
+#[inline]
+fn get_level_dimension(dimension: u32, level: u32) -> u32 {
+    (dimension >> level).max(1)
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ContainerError> {
+    match data.get(offset .. offset + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(ContainerError::InvalidData),
+    }
+}
+
+#[inline]
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, ContainerError> {
+    Ok(read_u32(data, offset)? as u64 | (read_u32(data, offset + 4)? as u64) << 32)
+}
+
+/// Returns the `len` bytes of `data` that start at `offset`.
+#[inline]
+fn read_bytes(data: &[u8], offset: u64, len: u64) -> Result<&[u8], ContainerError> {
+    match offset.checked_add(len) {
+        Some(end) if end <= data.len() as u64 => Ok(&data[offset as usize .. end as usize]),
+        _ => Err(ContainerError::InvalidData),
+    }
+}
@@ -123,7 +123,10 @@ pub use crate::image_format::{UncompressedFloatFormat, UncompressedIntFormat, Un
 pub use crate::image_format::{CompressedFormat, DepthFormat, DepthStencilFormat, StencilFormat};
 pub use crate::image_format::{CompressedSrgbFormat, SrgbFormat};
 pub use self::any::{TextureAny, TextureAnyMipmap, TextureAnyLayer, TextureAnyLayerMipmap};
-pub use self::any::{TextureAnyImage, Dimensions};
+pub use self::any::{TextureAnyImage, Dimensions, TextureViewType};
+pub use self::any::{Swizzle, SwizzleComponent, SwizzleNotSupportedError};
+pub use self::any::CopyTarget;
+pub use self::any::TextureTransfer;
 pub use self::bindless::{ResidentTexture, TextureHandle, BindlessTexturesNotSupportedError};
 pub use self::get_format::{InternalFormat, InternalFormatType, GetFormatError};
 pub use self::pixel::PixelValue;
@@ -132,12 +135,15 @@ pub use self::ty_support::{is_texture_3d_supported, is_texture_1d_array_supporte
 pub use self::ty_support::{is_texture_2d_array_supported, is_texture_2d_multisample_supported};
 pub use self::ty_support::{is_texture_2d_multisample_array_supported, is_cubemaps_supported};
 pub use self::ty_support::is_cubemap_arrays_supported;
+pub use self::ty_support::{is_texture_swizzle_supported, is_texture_view_supported};
 pub use self::texture_import::ExternalTilingMode;
 pub use self::texture_import::ImportParameters;
 pub use self::texture_import::TextureImportError;
+pub(crate) use self::any::{TextureRecipe, recreate_texture};
 
 pub mod bindless;
 pub mod buffer_texture;
+pub mod container;
 pub mod pixel_buffer;
 
 mod any;
@@ -824,6 +830,90 @@ impl fmt::Display for TextureCreationError {
 
 impl Error for TextureCreationError {}
 
+/// Error that can happen when creating a texture view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureViewCreationError {
+    /// Texture views are not supported by the backend.
+    NotSupported,
+
+    /// The storage of the source texture is not immutable.
+    MutableStorage,
+
+    /// The format of the view is not compatible with the format of the source texture.
+    IncompatibleFormat,
+
+    /// The source texture can't be viewed with the requested type or dimensions.
+    IncompatibleType,
+
+    /// The range of mipmap levels is empty or outside of the source texture.
+    InvalidLevels,
+
+    /// The range of layers is outside of the source texture or doesn't match the type of the view.
+    InvalidLayers,
+}
+
+impl fmt::Display for TextureViewCreationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::TextureViewCreationError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Texture views are not supported by the backend",
+            MutableStorage =>
+                "The storage of the source texture is not immutable",
+            IncompatibleFormat =>
+                "The format of the view is not compatible with the format of the source texture",
+            IncompatibleType =>
+                "The source texture can't be viewed with the requested type or dimensions",
+            InvalidLevels =>
+                "The range of mipmap levels is empty or outside of the source texture",
+            InvalidLayers =>
+                "The range of layers is outside of the source texture or doesn't match the type \
+                 of the view",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for TextureViewCreationError {}
+
+/// Error that can happen when copying texels between images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyError {
+    /// The backend can't copy between these images.
+    ///
+    /// Without `glCopyImageSubData`, copies between different or compressed formats must be
+    /// done by reading back the texels, which requires desktop OpenGL and a texture as
+    /// destination.
+    NotSupported,
+
+    /// The formats or the numbers of samples of the source and of the destination are not
+    /// compatible.
+    IncompatibleFormats,
+
+    /// The region to copy is outside of the source or of the destination, or isn't aligned to the
+    /// blocks of a compressed format.
+    OutOfRange,
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::CopyError::*;
+        let desc = match *self {
+            NotSupported =>
+                "The backend can't copy between these images",
+            IncompatibleFormats =>
+                "The formats or the numbers of samples of the source and of the destination are \
+                 not compatible",
+            OutOfRange =>
+                "The region to copy is outside of the source or of the destination, or isn't \
+                 aligned to the blocks of a compressed format",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for CopyError {}
+
 impl From<FormatNotSupportedError> for TextureCreationError {
     #[inline]
     fn from(_: FormatNotSupportedError) -> TextureCreationError {
@@ -80,3 +80,23 @@ pub fn is_cubemap_arrays_supported<C: ?Sized>(context: &C) -> bool where C: Capa
     context.get_extensions().gl_ext_texture_cube_map_array ||
     context.get_extensions().gl_oes_texture_cube_map_array
 }
+
+/// Returns true is texture swizzle masks are supported.
+///
+/// See `TextureAny::set_swizzle`.
+#[inline]
+pub fn is_texture_swizzle_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 3, 3) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+    context.get_extensions().gl_arb_texture_swizzle ||
+    context.get_extensions().gl_ext_texture_swizzle
+}
+
+/// Returns true is texture views are supported.
+///
+/// See `TextureAny::new_view`.
+#[inline]
+pub fn is_texture_view_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 3) ||
+    context.get_extensions().gl_arb_texture_view
+}
@@ -0,0 +1,1322 @@
+/*!
+Recording and replaying of the OpenGL commands issued by a context.
+
+When a context is built with `Context::new_with_trace` (or with the `with_trace` constructors of
+the glutin backends), every OpenGL command that glium executes is written to the trace, along with
+the data that is uploaded to buffers and textures. The end of each frame is marked in the trace
+when the buffers are swapped.
+
+A `Replayer` reads back a trace and executes its commands on another backend, which lets you
+reproduce a rendering issue offline.
+
+```no_run
+# fn example<B: glium::backend::Backend + 'static>(backend: B) {
+use std::fs::File;
+
+let trace = File::open("frame.trace").unwrap();
+let mut replayer = unsafe { glium::trace::Replayer::new(backend, trace) }.unwrap();
+unsafe { replayer.replay_all() }.unwrap();
+# }
+```
+
+# Limitations
+
+ - Object names are not remapped. The replay must happen on a newly-created context, and fails
+   with `ReplayError::ObjectNameMismatch` if the implementation hands out different names than
+   the ones that were recorded.
+ - Queries (`glGet*` and `glIs*`) are not replayed, and neither are the commands whose pointer
+   arguments point to memory of unknown size. See `Replayer::skipped_commands`.
+ - Writes to mapped buffers are captured when the written range is flushed with
+   `glFlushMappedBufferRange`. glium always maps buffers with `GL_MAP_FLUSH_EXPLICIT_BIT`,
+   including the persistent mappings of `BufferMode::Persistent`, which are never coherent. Writes
+   to a buffer that is mapped by other code with `GL_MAP_COHERENT_BIT` or without
+   `GL_MAP_FLUSH_EXPLICIT_BIT` are missing from the trace.
+
+# Features
+
+Only available if the `trace` feature is enabled.
+
+*/
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+use std::io::{self, BufWriter, Read, Write};
+use std::mem;
+use std::os::raw;
+use std::ptr;
+use std::slice;
+
+use crate::backend::Backend;
+use crate::gl;
+use crate::SwapBuffersError;
+
+/// Bytes at the start of every trace.
+const MAGIC: &[u8; 8] = b"GLMTRACE";
+
+/// Version of the format of the traces.
+const FORMAT_VERSION: u32 = 1;
+
+/// Value of an argument or of the return value of a command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    /// The command doesn't return anything.
+    Void,
+    /// A signed integer.
+    Int(i64),
+    /// An unsigned integer.
+    UInt(u64),
+    /// A floating-point number.
+    Float(f64),
+    /// A pointer to memory that is read by the implementation, or an offset within a buffer.
+    Ptr(usize),
+    /// A pointer to memory that is written by the implementation, or an offset within a buffer.
+    MutPtr(usize),
+}
+
+impl Value {
+    fn as_i64(&self) -> i64 {
+        match *self {
+            Value::Void => 0,
+            Value::Int(v) => v,
+            Value::UInt(v) => v as i64,
+            Value::Float(v) => v as i64,
+            Value::Ptr(v) | Value::MutPtr(v) => v as i64,
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        match *self {
+            Value::Void => 0,
+            Value::Int(v) => v as u64,
+            Value::UInt(v) => v,
+            Value::Float(v) => v as u64,
+            Value::Ptr(v) | Value::MutPtr(v) => v as u64,
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match *self {
+            Value::Float(v) => v,
+            Value::Int(v) => v as f64,
+            Value::UInt(v) => v as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn as_ptr(&self) -> usize {
+        self.as_u64() as usize
+    }
+}
+
+/// Conversion between the types of the arguments of OpenGL commands and `Value`.
+pub(crate) trait TraceValue: Sized {
+    /// Turns the argument into a value that can be written in a trace.
+    fn to_value(&self) -> Value;
+
+    /// Turns back a value read from a trace into an argument.
+    fn from_value(value: Value) -> Self;
+}
+
+macro_rules! impl_trace_value {
+    ($variant:ident, $getter:ident, $($ty:ty),+) => {
+        $(
+            impl TraceValue for $ty {
+                #[inline]
+                fn to_value(&self) -> Value {
+                    Value::$variant(*self as _)
+                }
+
+                #[inline]
+                fn from_value(value: Value) -> $ty {
+                    value.$getter() as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_trace_value!(Int, as_i64, i8, i16, i32, i64, isize);
+impl_trace_value!(UInt, as_u64, u8, u16, u32, u64, usize);
+impl_trace_value!(Float, as_f64, f32, f64);
+
+impl TraceValue for () {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Void
+    }
+
+    #[inline]
+    fn from_value(_: Value) {
+    }
+}
+
+impl<T> TraceValue for *const T {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Ptr(*self as usize)
+    }
+
+    #[inline]
+    fn from_value(value: Value) -> *const T {
+        value.as_ptr() as *const T
+    }
+}
+
+impl<T> TraceValue for *mut T {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::MutPtr(*self as usize)
+    }
+
+    #[inline]
+    fn from_value(value: Value) -> *mut T {
+        value.as_ptr() as *mut T
+    }
+}
+
+// callbacks can't be replayed, so they are recorded as plain addresses and replayed as `None`
+impl TraceValue for gl::types::GLDEBUGPROC {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Ptr(self.map(|f| f as usize).unwrap_or(0))
+    }
+
+    #[inline]
+    fn from_value(_: Value) -> gl::types::GLDEBUGPROC {
+        None
+    }
+}
+
+impl TraceValue for gl::types::GLDEBUGPROCAMD {
+    #[inline]
+    fn to_value(&self) -> Value {
+        Value::Ptr(self.map(|f| f as usize).unwrap_or(0))
+    }
+
+    #[inline]
+    fn from_value(_: Value) -> gl::types::GLDEBUGPROCAMD {
+        None
+    }
+}
+
+/// How the data attached to an argument must be interpreted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PayloadKind {
+    /// Memory that the argument points to.
+    Data,
+    /// List of NUL-terminated strings. The argument points to an array of pointers to them.
+    Strings,
+    /// Values written by the implementation in the memory that the argument points to.
+    Output,
+    /// Content of the flushed range of a mapped buffer. Attached to the offset argument.
+    MappedWrite,
+    /// The argument points to memory whose size is unknown. The command can't be replayed.
+    Opaque,
+}
+
+impl PayloadKind {
+    fn from_u8(value: u8) -> Option<PayloadKind> {
+        match value {
+            0 => Some(PayloadKind::Data),
+            1 => Some(PayloadKind::Strings),
+            2 => Some(PayloadKind::Output),
+            3 => Some(PayloadKind::MappedWrite),
+            4 => Some(PayloadKind::Opaque),
+            _ => None,
+        }
+    }
+}
+
+/// Data attached to an argument of a command.
+#[derive(Clone, Debug, PartialEq)]
+struct Payload {
+    arg: u8,
+    kind: PayloadKind,
+    data: Vec<u8>,
+}
+
+/// A command and its arguments, as found in a trace.
+#[derive(Clone, Debug, PartialEq)]
+struct Call {
+    name: String,
+    args: Vec<Value>,
+    ret: Value,
+    payloads: Vec<Payload>,
+}
+
+impl Call {
+    fn payload(&self, arg: usize, kind: PayloadKind) -> Option<&Payload> {
+        self.payloads.iter().find(|p| p.arg as usize == arg && p.kind == kind)
+    }
+}
+
+/// An element of a trace.
+#[derive(Clone, Debug, PartialEq)]
+enum Record {
+    Call(Call),
+    EndOfFrame,
+}
+
+/// Records the OpenGL commands that are executed into a trace.
+pub(crate) struct Recorder {
+    state: RefCell<RecorderState>,
+}
+
+struct RecorderState {
+    writer: BufWriter<Box<dyn Write>>,
+    bindings: Bindings,
+    /// Pointer to the start of the mapped range of each mapped buffer.
+    mappings: HashMap<gl::types::GLuint, usize>,
+    /// The first error that happened while writing. Nothing is written anymore after an error.
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    /// Builds a new recorder that writes to the given trace.
+    pub fn new<W>(trace: W) -> Recorder where W: Write + 'static {
+        let mut writer = BufWriter::new(Box::new(trace) as Box<dyn Write>);
+        let error = write_header(&mut writer).err();
+
+        Recorder {
+            state: RefCell::new(RecorderState {
+                writer,
+                bindings: Bindings::new(),
+                mappings: HashMap::new(),
+                error,
+            }),
+        }
+    }
+
+    /// Records a command that has just been executed.
+    pub fn record(&self, name: &'static str, args: &[Value], ret: Value) {
+        let mut state = self.state.borrow_mut();
+        if state.error.is_some() {
+            return;
+        }
+
+        let mut call = Call { name: name.to_owned(), args: args.to_vec(), ret, payloads: Vec::new() };
+        unsafe { state.capture(&mut call) };
+
+        state.bindings.update(&call);
+        match name {
+            "MapBufferRange" | "MapBufferRangeEXT" => {
+                let buffer = state.bindings.buffer(call.args[0].as_u64() as gl::types::GLenum);
+                state.mappings.insert(buffer, ret.as_ptr());
+            },
+            "MapNamedBufferRange" | "MapNamedBufferRangeEXT" => {
+                state.mappings.insert(call.args[0].as_u64() as gl::types::GLuint, ret.as_ptr());
+            },
+            "UnmapBuffer" | "UnmapBufferARB" | "UnmapBufferOES" => {
+                let buffer = state.bindings.buffer(call.args[0].as_u64() as gl::types::GLenum);
+                state.mappings.remove(&buffer);
+            },
+            "UnmapNamedBuffer" | "UnmapNamedBufferEXT" => {
+                state.mappings.remove(&(call.args[0].as_u64() as gl::types::GLuint));
+            },
+            _ => ()
+        }
+
+        if let Err(err) = write_record(&mut state.writer, &Record::Call(call)) {
+            state.error = Some(err);
+        }
+    }
+
+    /// Marks the end of the current frame.
+    pub fn end_frame(&self) {
+        let mut state = self.state.borrow_mut();
+        if state.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = write_record(&mut state.writer, &Record::EndOfFrame) {
+            state.error = Some(err);
+        }
+    }
+
+    /// Flushes the trace. Returns the first error that happened while writing, if any.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut state = self.state.borrow_mut();
+        if let Some(err) = state.error.take() {
+            return Err(err);
+        }
+
+        state.writer.flush()
+    }
+}
+
+impl RecorderState {
+    /// Attaches to the call the memory that its pointer arguments point to.
+    ///
+    /// # Safety
+    ///
+    /// Must be called right after the command has been executed, while the memory that the
+    /// arguments point to is still alive.
+    unsafe fn capture(&mut self, call: &mut Call) {
+        let name = call.name.as_str();
+        let args = &mut call.args;
+        let payloads = &mut call.payloads;
+
+        // true if the pointer arguments of the command are offsets within a buffer
+        let mut offsets = name.starts_with("Draw") || name.starts_with("MultiDraw") ||
+                          (name.starts_with("VertexAttrib") && name.contains("Pointer")) ||
+                          is_sync_command(name);
+
+        if let Some((dimensions, format, ty, pixels)) = texture_upload_args(name) {
+            if self.bindings.buffer(gl::PIXEL_UNPACK_BUFFER) != 0 {
+                offsets = true;
+            } else if let Some(pixel_size) = pixel_size(args[format].as_u64() as gl::types::GLenum,
+                                                        args[ty].as_u64() as gl::types::GLenum)
+            {
+                let size = upload_size(dimensions.iter().map(|d| d.map(|d| args[d].as_i64())),
+                                       pixel_size, self.bindings.unpack_alignment);
+                payloads.push(data(pixels, read_bytes(args[pixels].as_ptr(), size)));
+            }
+
+        } else if let Some((size, data_arg)) = compressed_upload_args(name) {
+            if self.bindings.buffer(gl::PIXEL_UNPACK_BUFFER) != 0 {
+                offsets = true;
+            } else {
+                let size = args[size].as_i64() as usize;
+                payloads.push(data(data_arg, read_bytes(args[data_arg].as_ptr(), size)));
+            }
+
+        } else if let Some((value, count, size)) = uniform_array_args(name) {
+            let size = args[count].as_i64() as usize * size;
+            payloads.push(data(value, read_bytes(args[value].as_ptr(), size)));
+
+        } else if let Some((value, pname)) = parameter_array_args(name) {
+            let size = match args[pname].as_u64() as gl::types::GLenum {
+                gl::TEXTURE_BORDER_COLOR | gl::TEXTURE_SWIZZLE_RGBA => 16,
+                _ => 4,
+            };
+            payloads.push(data(value, read_bytes(args[value].as_ptr(), size)));
+
+        } else if let Some((value, format, ty)) = clear_data_args(name) {
+            if let Some(size) = pixel_size(args[format].as_u64() as gl::types::GLenum,
+                                           args[ty].as_u64() as gl::types::GLenum)
+            {
+                payloads.push(data(value, read_bytes(args[value].as_ptr(), size)));
+            }
+
+        } else if (name.starts_with("Gen") || name.starts_with("Create")) &&
+                  args.len() >= 2 && matches!(args[args.len() - 1], Value::MutPtr(_))
+        {
+            let names = args.len() - 1;
+            let size = args[names - 1].as_i64() as usize * mem::size_of::<gl::types::GLuint>();
+            payloads.push(Payload {
+                arg: names as u8,
+                kind: PayloadKind::Output,
+                data: read_bytes(args[names].as_ptr(), size),
+            });
+
+        } else if name.starts_with("Delete") && args.len() == 2 {
+            let size = args[0].as_i64() as usize * mem::size_of::<gl::types::GLuint>();
+            payloads.push(data(1, read_bytes(args[1].as_ptr(), size)));
+
+        } else {
+            match name {
+                "BufferData" | "BufferDataARB" | "BufferStorage" | "BufferStorageEXT" |
+                "NamedBufferData" | "NamedBufferDataEXT" | "NamedBufferStorage" |
+                "NamedBufferStorageEXT" => {
+                    let size = args[1].as_i64() as usize;
+                    payloads.push(data(2, read_bytes(args[2].as_ptr(), size)));
+                },
+
+                "BufferSubData" | "BufferSubDataARB" | "NamedBufferSubData" |
+                "NamedBufferSubDataEXT" => {
+                    let size = args[2].as_i64() as usize;
+                    payloads.push(data(3, read_bytes(args[3].as_ptr(), size)));
+                },
+
+                "FlushMappedBufferRange" | "FlushMappedBufferRangeEXT" |
+                "FlushMappedNamedBufferRange" | "FlushMappedNamedBufferRangeEXT" => {
+                    let buffer = if name.contains("Named") {
+                        args[0].as_u64() as gl::types::GLuint
+                    } else {
+                        self.bindings.buffer(args[0].as_u64() as gl::types::GLenum)
+                    };
+
+                    if let Some(&mapping) = self.mappings.get(&buffer) {
+                        let offset = args[1].as_i64() as usize;
+                        let size = args[2].as_i64() as usize;
+                        payloads.push(Payload {
+                            arg: 1,
+                            kind: PayloadKind::MappedWrite,
+                            data: read_bytes(mapping + offset, size),
+                        });
+                    }
+                },
+
+                "ReadPixels" | "ReadnPixels" => {
+                    offsets = self.bindings.buffer(gl::PIXEL_PACK_BUFFER) != 0;
+                },
+
+                "ShaderSource" | "ShaderSourceARB" => {
+                    let count = args[1].as_i64() as usize;
+                    let strings = args[2].as_ptr() as *const *const raw::c_char;
+                    let lengths = args[3].as_ptr() as *const gl::types::GLint;
+
+                    let mut sources = Vec::new();
+                    for i in 0 .. count {
+                        let length = if lengths.is_null() { -1 } else { *lengths.add(i) as i64 };
+                        sources.extend(read_string(*strings.add(i) as usize, length));
+                        sources.push(0);
+                    }
+
+                    payloads.push(Payload { arg: 2, kind: PayloadKind::Strings, data: sources });
+                    // the strings are now NUL-terminated
+                    args[3] = Value::Ptr(0);
+                },
+
+                "TransformFeedbackVaryings" | "TransformFeedbackVaryingsEXT" => {
+                    let count = args[1].as_i64() as usize;
+                    let strings = args[2].as_ptr() as *const *const raw::c_char;
+
+                    let mut varyings = Vec::new();
+                    for i in 0 .. count {
+                        varyings.extend(read_string(*strings.add(i) as usize, -1));
+                        varyings.push(0);
+                    }
+
+                    payloads.push(Payload { arg: 2, kind: PayloadKind::Strings, data: varyings });
+                },
+
+                "BindAttribLocation" | "BindAttribLocationARB" | "BindFragDataLocation" |
+                "BindFragDataLocationEXT" => {
+                    payloads.push(data(2, read_c_string(args[2].as_ptr())));
+                },
+
+                "BindFragDataLocationIndexed" => {
+                    payloads.push(data(3, read_c_string(args[3].as_ptr())));
+                },
+
+                "SpecializeShader" | "SpecializeShaderARB" => {
+                    let size = args[2].as_i64() as usize * mem::size_of::<gl::types::GLuint>();
+                    payloads.push(data(1, read_c_string(args[1].as_ptr())));
+                    payloads.push(data(3, read_bytes(args[3].as_ptr(), size)));
+                    payloads.push(data(4, read_bytes(args[4].as_ptr(), size)));
+                },
+
+                "ShaderBinary" => {
+                    let size = args[0].as_i64() as usize * mem::size_of::<gl::types::GLuint>();
+                    payloads.push(data(1, read_bytes(args[1].as_ptr(), size)));
+                    payloads.push(data(3, read_bytes(args[3].as_ptr(), args[4].as_i64() as usize)));
+                },
+
+                "ProgramBinary" | "ProgramBinaryOES" => {
+                    payloads.push(data(2, read_bytes(args[2].as_ptr(), args[3].as_i64() as usize)));
+                },
+
+                "InsertEventMarkerEXT" | "PushGroupMarkerEXT" | "StringMarkerGREMEDY" => {
+                    // a length of zero means that the string is NUL-terminated
+                    let length = match args[0].as_i64() { 0 => -1, l => l };
+                    payloads.push(data(1, read_string(args[1].as_ptr(), length)));
+                },
+
+                "PushDebugGroup" | "PushDebugGroupKHR" => {
+                    payloads.push(data(3, read_string(args[3].as_ptr(), args[2].as_i64())));
+                },
+
+                "ObjectLabel" | "ObjectLabelKHR" => {
+                    payloads.push(data(3, read_string(args[3].as_ptr(), args[2].as_i64())));
+                },
+
+                "DebugMessageInsert" | "DebugMessageInsertARB" | "DebugMessageInsertKHR" => {
+                    payloads.push(data(5, read_string(args[5].as_ptr(), args[4].as_i64())));
+                },
+
+                "DebugMessageControl" | "DebugMessageControlARB" | "DebugMessageControlKHR" => {
+                    let size = args[3].as_i64() as usize * mem::size_of::<gl::types::GLuint>();
+                    payloads.push(data(4, read_bytes(args[4].as_ptr(), size)));
+                },
+
+                "DrawBuffers" | "DrawBuffersARB" | "DrawBuffersATI" | "DrawBuffersEXT" => {
+                    let size = args[0].as_i64() as usize * mem::size_of::<gl::types::GLenum>();
+                    payloads.push(data(1, read_bytes(args[1].as_ptr(), size)));
+                },
+
+                "NamedFramebufferDrawBuffers" | "FramebufferDrawBuffersEXT" |
+                "InvalidateFramebuffer" | "InvalidateNamedFramebufferData" |
+                "InvalidateSubFramebuffer" | "DiscardFramebufferEXT" |
+                "UniformSubroutinesuiv" => {
+                    let size = args[1].as_i64() as usize * mem::size_of::<gl::types::GLenum>();
+                    payloads.push(data(2, read_bytes(args[2].as_ptr(), size)));
+                },
+
+                "ClearBufferfv" | "ClearBufferiv" | "ClearBufferuiv" => {
+                    let size = if args[0].as_u64() as gl::types::GLenum == gl::COLOR { 16 } else { 4 };
+                    payloads.push(data(2, read_bytes(args[2].as_ptr(), size)));
+                },
+
+                "ClearNamedFramebufferfv" | "ClearNamedFramebufferiv" |
+                "ClearNamedFramebufferuiv" => {
+                    let size = if args[1].as_u64() as gl::types::GLenum == gl::COLOR { 16 } else { 4 };
+                    payloads.push(data(3, read_bytes(args[3].as_ptr(), size)));
+                },
+
+                "ViewportArrayv" | "ScissorArrayv" | "DepthRangeArrayv" => {
+                    let size = args[1].as_i64() as usize * 16;
+                    payloads.push(data(2, read_bytes(args[2].as_ptr(), size)));
+                },
+
+                "ViewportIndexedfv" | "ScissorIndexedv" => {
+                    payloads.push(data(1, read_bytes(args[1].as_ptr(), 16)));
+                },
+
+                _ => ()
+            }
+        }
+
+        // pointers that we don't know how to capture make the command impossible to replay
+        if !offsets && !is_query(name) {
+            for (arg, value) in args.iter().enumerate() {
+                let captured = payloads.iter().any(|p| p.arg as usize == arg);
+                match *value {
+                    Value::Ptr(p) | Value::MutPtr(p) if p != 0 && !captured => {
+                        payloads.push(Payload {
+                            arg: arg as u8,
+                            kind: PayloadKind::Opaque,
+                            data: Vec::new(),
+                        });
+                    },
+                    _ => ()
+                }
+            }
+        }
+
+        // null pointers are not captured
+        payloads.retain(|p| p.kind != PayloadKind::Data || args[p.arg as usize].as_ptr() != 0);
+    }
+}
+
+/// Builds a payload that contains the memory that an argument points to.
+fn data(arg: usize, data: Vec<u8>) -> Payload {
+    Payload { arg: arg as u8, kind: PayloadKind::Data, data }
+}
+
+/// Copies `size` bytes starting at `ptr`.
+unsafe fn read_bytes(ptr: usize, size: usize) -> Vec<u8> {
+    if ptr == 0 || size == 0 {
+        return Vec::new();
+    }
+
+    slice::from_raw_parts(ptr as *const u8, size).to_vec()
+}
+
+/// Copies a NUL-terminated string, including its NUL terminator.
+unsafe fn read_c_string(ptr: usize) -> Vec<u8> {
+    if ptr == 0 {
+        return Vec::new();
+    }
+
+    CStr::from_ptr(ptr as *const raw::c_char).to_bytes_with_nul().to_vec()
+}
+
+/// Copies a string of the given length, or a NUL-terminated string if the length is negative.
+unsafe fn read_string(ptr: usize, length: i64) -> Vec<u8> {
+    if length < 0 {
+        let mut string = read_c_string(ptr);
+        string.pop();
+        string
+    } else {
+        read_bytes(ptr, length as usize)
+    }
+}
+
+/// Returns true for the commands that only retrieve information from the implementation.
+fn is_query(name: &str) -> bool {
+    name.starts_with("Get") ||
+        (name.starts_with("Is") && name[2..].starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// Returns true for the commands whose first argument is a sync object.
+fn is_sync_command(name: &str) -> bool {
+    matches!(name, "ClientWaitSync" | "ClientWaitSyncAPPLE" | "WaitSync" | "WaitSyncAPPLE" |
+                   "DeleteSync" | "DeleteSyncAPPLE")
+}
+
+/// For the commands that upload uncompressed texture data, returns the indices of the width,
+/// height and depth, format, type and pixels arguments.
+fn texture_upload_args(name: &str) -> Option<([Option<usize>; 3], usize, usize, usize)> {
+    Some(match name {
+        "TexImage1D" => ([Some(3), None, None], 5, 6, 7),
+        "TexImage2D" => ([Some(3), Some(4), None], 6, 7, 8),
+        "TexImage3D" | "TexImage3DEXT" => ([Some(3), Some(4), Some(5)], 7, 8, 9),
+        "TexSubImage1D" | "TexSubImage1DEXT" | "TextureSubImage1D" => ([Some(3), None, None], 4, 5, 6),
+        "TexSubImage2D" | "TexSubImage2DEXT" | "TextureSubImage2D" => ([Some(4), Some(5), None], 6, 7, 8),
+        "TexSubImage3D" | "TexSubImage3DEXT" | "TextureSubImage3D" => ([Some(5), Some(6), Some(7)], 8, 9, 10),
+        "TextureSubImage1DEXT" => ([Some(4), None, None], 5, 6, 7),
+        "TextureSubImage2DEXT" => ([Some(5), Some(6), None], 7, 8, 9),
+        "TextureSubImage3DEXT" => ([Some(6), Some(7), Some(8)], 9, 10, 11),
+        _ => return None,
+    })
+}
+
+/// For the commands that upload compressed texture data, returns the indices of the size and
+/// data arguments.
+fn compressed_upload_args(name: &str) -> Option<(usize, usize)> {
+    Some(match name {
+        "CompressedTexImage1D" | "CompressedTexImage1DARB" => (5, 6),
+        "CompressedTexImage2D" | "CompressedTexImage2DARB" => (6, 7),
+        "CompressedTexImage3D" | "CompressedTexImage3DARB" => (7, 8),
+        "CompressedTexSubImage1D" | "CompressedTexSubImage1DARB" | "CompressedTextureSubImage1D" => (5, 6),
+        "CompressedTexSubImage2D" | "CompressedTexSubImage2DARB" | "CompressedTextureSubImage2D" => (7, 8),
+        "CompressedTexSubImage3D" | "CompressedTexSubImage3DARB" | "CompressedTextureSubImage3D" => (9, 10),
+        "CompressedTextureSubImage1DEXT" => (6, 7),
+        "CompressedTextureSubImage2DEXT" => (8, 9),
+        "CompressedTextureSubImage3DEXT" => (10, 11),
+        _ => return None,
+    })
+}
+
+/// For the `glUniform*v` family of commands, returns the indices of the values and count
+/// arguments, and the size in bytes of each element.
+fn uniform_array_args(name: &str) -> Option<(usize, usize, usize)> {
+    let (rest, first) = if let Some(rest) = name.strip_prefix("ProgramUniform") {
+        (rest, 1)
+    } else if let Some(rest) = name.strip_prefix("Uniform") {
+        (rest, 0)
+    } else {
+        return None;
+    };
+
+    let rest = rest.trim_end_matches("ARB").trim_end_matches("EXT").strip_suffix('v')?;
+
+    if rest == "Handleui64" {
+        return Some((first + 2, first + 1, 8));
+    }
+
+    if let Some(matrix) = rest.strip_prefix("Matrix") {
+        let (dimensions, size) = if let Some(d) = matrix.strip_suffix('f') {
+            (d, 4)
+        } else {
+            (matrix.strip_suffix('d')?, 8)
+        };
+
+        let components = match dimensions {
+            "2" => 4, "3" => 9, "4" => 16,
+            "2x3" | "3x2" => 6, "2x4" | "4x2" => 8, "3x4" | "4x3" => 12,
+            _ => return None,
+        };
+
+        // the matrix commands have a `transpose` argument before the values
+        Some((first + 3, first + 1, components * size))
+
+    } else {
+        let components = rest.chars().next()?.to_digit(10)? as usize;
+        let size = match &rest[1..] {
+            "f" | "i" | "ui" => 4,
+            "d" | "i64" | "ui64" => 8,
+            _ => return None,
+        };
+
+        Some((first + 2, first + 1, components * size))
+    }
+}
+
+/// For the commands that set a texture or sampler parameter from an array, returns the indices
+/// of the values and parameter name arguments.
+fn parameter_array_args(name: &str) -> Option<(usize, usize)> {
+    if !name.trim_end_matches("EXT").ends_with('v') {
+        return None;
+    }
+
+    if name.starts_with("TexParameter") || name.starts_with("SamplerParameter") {
+        Some((2, 1))
+    } else if name.starts_with("TextureParameter") && name.ends_with("EXT") {
+        Some((3, 2))
+    } else if name.starts_with("TextureParameter") {
+        Some((2, 1))
+    } else {
+        None
+    }
+}
+
+/// For the commands that clear a buffer or a texture with a value, returns the indices of the
+/// value, format and type arguments.
+fn clear_data_args(name: &str) -> Option<(usize, usize, usize)> {
+    Some(match name {
+        "ClearBufferData" | "ClearNamedBufferData" | "ClearNamedBufferDataEXT" => (4, 2, 3),
+        "ClearBufferSubData" | "ClearNamedBufferSubData" => (6, 4, 5),
+        "ClearNamedBufferSubDataEXT" => (6, 2, 3),
+        "ClearTexImage" => (4, 2, 3),
+        "ClearTexSubImage" => (10, 8, 9),
+        _ => return None,
+    })
+}
+
+/// Returns the size in bytes of a pixel of client data.
+fn pixel_size(format: gl::types::GLenum, ty: gl::types::GLenum) -> Option<usize> {
+    let packed = match ty {
+        gl::UNSIGNED_BYTE_3_3_2 | gl::UNSIGNED_BYTE_2_3_3_REV => Some(1),
+        gl::UNSIGNED_SHORT_5_6_5 | gl::UNSIGNED_SHORT_5_6_5_REV | gl::UNSIGNED_SHORT_4_4_4_4 |
+        gl::UNSIGNED_SHORT_4_4_4_4_REV | gl::UNSIGNED_SHORT_5_5_5_1 |
+        gl::UNSIGNED_SHORT_1_5_5_5_REV => Some(2),
+        gl::UNSIGNED_INT_8_8_8_8 | gl::UNSIGNED_INT_8_8_8_8_REV | gl::UNSIGNED_INT_10_10_10_2 |
+        gl::UNSIGNED_INT_2_10_10_10_REV | gl::UNSIGNED_INT_24_8 |
+        gl::UNSIGNED_INT_10F_11F_11F_REV | gl::UNSIGNED_INT_5_9_9_9_REV => Some(4),
+        gl::FLOAT_32_UNSIGNED_INT_24_8_REV => Some(8),
+        _ => None,
+    };
+
+    if packed.is_some() {
+        return packed;
+    }
+
+    let components = match format {
+        gl::RED | gl::GREEN | gl::BLUE | gl::ALPHA | gl::RED_INTEGER | gl::GREEN_INTEGER |
+        gl::BLUE_INTEGER | gl::ALPHA_INTEGER | gl::LUMINANCE | gl::DEPTH_COMPONENT |
+        gl::STENCIL_INDEX => 1,
+        gl::RG | gl::RG_INTEGER | gl::LUMINANCE_ALPHA | gl::DEPTH_STENCIL => 2,
+        gl::RGB | gl::BGR | gl::RGB_INTEGER | gl::BGR_INTEGER => 3,
+        gl::RGBA | gl::BGRA | gl::RGBA_INTEGER | gl::BGRA_INTEGER => 4,
+        _ => return None,
+    };
+
+    let size = match ty {
+        gl::UNSIGNED_BYTE | gl::BYTE => 1,
+        gl::UNSIGNED_SHORT | gl::SHORT | gl::HALF_FLOAT => 2,
+        gl::UNSIGNED_INT | gl::INT | gl::FLOAT => 4,
+        _ => return None,
+    };
+
+    Some(components * size)
+}
+
+/// Returns the number of bytes that the implementation reads when uploading pixels with the
+/// given dimensions.
+fn upload_size<I>(dimensions: I, pixel_size: usize, alignment: usize) -> usize
+    where I: Iterator<Item = Option<i64>>
+{
+    let mut dimensions = dimensions.map(|d| d.unwrap_or(1).max(0) as usize);
+    let width = dimensions.next().unwrap_or(1);
+    let rows = dimensions.product::<usize>();
+
+    if width == 0 || rows == 0 {
+        return 0;
+    }
+
+    let row_size = width * pixel_size;
+    let stride = (row_size + alignment - 1) / alignment * alignment;
+    stride * (rows - 1) + row_size
+}
+
+/// Tracks the buffer bindings and the pixel storage state, in order to know how the pointer
+/// arguments of commands must be interpreted.
+struct Bindings {
+    /// Buffer bound to each target. The element array buffer is part of the vertex array state,
+    /// so the key also contains the vertex array for this target.
+    buffers: HashMap<(gl::types::GLenum, gl::types::GLuint), gl::types::GLuint>,
+    vertex_array: gl::types::GLuint,
+    unpack_alignment: usize,
+}
+
+impl Bindings {
+    fn new() -> Bindings {
+        Bindings {
+            buffers: HashMap::new(),
+            vertex_array: 0,
+            unpack_alignment: 4,
+        }
+    }
+
+    fn key(&self, target: gl::types::GLenum) -> (gl::types::GLenum, gl::types::GLuint) {
+        if target == gl::ELEMENT_ARRAY_BUFFER {
+            (target, self.vertex_array)
+        } else {
+            (target, 0)
+        }
+    }
+
+    /// Returns the buffer bound to the given target.
+    fn buffer(&self, target: gl::types::GLenum) -> gl::types::GLuint {
+        self.buffers.get(&self.key(target)).cloned().unwrap_or(0)
+    }
+
+    /// Updates the state after a command has been executed.
+    fn update(&mut self, call: &Call) {
+        let args = &call.args;
+
+        match call.name.as_str() {
+            "BindBuffer" | "BindBufferARB" => {
+                let key = self.key(args[0].as_u64() as gl::types::GLenum);
+                self.buffers.insert(key, args[1].as_u64() as gl::types::GLuint);
+            },
+            "BindBufferBase" | "BindBufferBaseEXT" | "BindBufferRange" | "BindBufferRangeEXT" => {
+                let key = self.key(args[0].as_u64() as gl::types::GLenum);
+                self.buffers.insert(key, args[2].as_u64() as gl::types::GLuint);
+            },
+            "BindVertexArray" | "BindVertexArrayOES" | "BindVertexArrayAPPLE" => {
+                self.vertex_array = args[0].as_u64() as gl::types::GLuint;
+            },
+            "PixelStorei" if args[0].as_u64() as gl::types::GLenum == gl::UNPACK_ALIGNMENT => {
+                self.unpack_alignment = args[1].as_i64().max(1) as usize;
+            },
+            "DeleteBuffers" | "DeleteBuffersARB" => {
+                if let Some(payload) = call.payload(1, PayloadKind::Data) {
+                    let deleted = read_names(&payload.data);
+                    self.buffers.retain(|_, b| !deleted.contains(b));
+                }
+            },
+            _ => ()
+        }
+    }
+}
+
+/// Interprets data as a list of object names.
+fn read_names(data: &[u8]) -> Vec<gl::types::GLuint> {
+    data.chunks(4)
+        .filter(|c| c.len() == 4)
+        .map(|c| gl::types::GLuint::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn write_header<W: Write>(writer: &mut W) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())
+}
+
+fn write_value<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+    let (tag, bits) = match *value {
+        Value::Void => (0u8, 0u64),
+        Value::Int(v) => (1, v as u64),
+        Value::UInt(v) => (2, v),
+        Value::Float(v) => (3, v.to_bits()),
+        Value::Ptr(v) => (4, v as u64),
+        Value::MutPtr(v) => (5, v as u64),
+    };
+
+    writer.write_all(&[tag])?;
+    writer.write_all(&bits.to_le_bytes())
+}
+
+fn write_record<W: Write>(writer: &mut W, record: &Record) -> io::Result<()> {
+    let call = match record {
+        Record::EndOfFrame => return writer.write_all(&[1]),
+        Record::Call(call) => call,
+    };
+
+    writer.write_all(&[0])?;
+    writer.write_all(&(call.name.len() as u16).to_le_bytes())?;
+    writer.write_all(call.name.as_bytes())?;
+    writer.write_all(&[call.args.len() as u8])?;
+    for arg in &call.args {
+        write_value(writer, arg)?;
+    }
+    write_value(writer, &call.ret)?;
+    writer.write_all(&[call.payloads.len() as u8])?;
+    for payload in &call.payloads {
+        writer.write_all(&[payload.arg, payload.kind as u8])?;
+        writer.write_all(&(payload.data.len() as u32).to_le_bytes())?;
+        writer.write_all(&payload.data)?;
+    }
+
+    Ok(())
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], ReplayError> {
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf).map_err(ReplayError::from_read)?;
+    Ok(buf)
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<(), ReplayError> {
+    if &read_array::<_, 8>(reader)? != MAGIC {
+        return Err(ReplayError::InvalidTrace);
+    }
+
+    if u32::from_le_bytes(read_array(reader)?) != FORMAT_VERSION {
+        return Err(ReplayError::InvalidTrace);
+    }
+
+    Ok(())
+}
+
+fn read_value<R: Read>(reader: &mut R) -> Result<Value, ReplayError> {
+    let [tag] = read_array::<_, 1>(reader)?;
+    let bits = u64::from_le_bytes(read_array(reader)?);
+
+    Ok(match tag {
+        0 => Value::Void,
+        1 => Value::Int(bits as i64),
+        2 => Value::UInt(bits),
+        3 => Value::Float(f64::from_bits(bits)),
+        4 => Value::Ptr(bits as usize),
+        5 => Value::MutPtr(bits as usize),
+        _ => return Err(ReplayError::InvalidTrace),
+    })
+}
+
+/// Reads the next record. Returns `None` at the end of the trace.
+fn read_record<R: Read>(reader: &mut R) -> Result<Option<Record>, ReplayError> {
+    let mut tag = [0];
+    loop {
+        match reader.read(&mut tag) {
+            Ok(0) => return Ok(None),
+            Ok(_) => break,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(ReplayError::Io(err)),
+        }
+    }
+
+    match tag[0] {
+        0 => (),
+        1 => return Ok(Some(Record::EndOfFrame)),
+        _ => return Err(ReplayError::InvalidTrace),
+    }
+
+    let name_len = u16::from_le_bytes(read_array(reader)?) as usize;
+    let mut name = vec![0; name_len];
+    reader.read_exact(&mut name).map_err(ReplayError::from_read)?;
+    let name = String::from_utf8(name).map_err(|_| ReplayError::InvalidTrace)?;
+
+    let [num_args] = read_array::<_, 1>(reader)?;
+    let args = (0 .. num_args).map(|_| read_value(reader)).collect::<Result<Vec<_>, _>>()?;
+    let ret = read_value(reader)?;
+
+    let [num_payloads] = read_array::<_, 1>(reader)?;
+    let mut payloads = Vec::with_capacity(num_payloads as usize);
+    for _ in 0 .. num_payloads {
+        let [arg, kind] = read_array::<_, 2>(reader)?;
+        let kind = PayloadKind::from_u8(kind).ok_or(ReplayError::InvalidTrace)?;
+        if arg as usize >= args.len() {
+            return Err(ReplayError::InvalidTrace);
+        }
+
+        let len = u32::from_le_bytes(read_array(reader)?) as usize;
+        let mut data = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut data).map_err(ReplayError::Io)?;
+        if data.len() != len {
+            return Err(ReplayError::InvalidTrace);
+        }
+
+        payloads.push(Payload { arg, kind, data });
+    }
+
+    Ok(Some(Record::Call(Call { name, args, ret, payloads })))
+}
+
+/// Error that can happen while replaying a trace.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Error while reading the trace.
+    Io(io::Error),
+
+    /// The trace is corrupted, or has been recorded by an incompatible version of glium.
+    InvalidTrace,
+
+    /// A command of the trace isn't supported by the backend.
+    UnsupportedCommand(String),
+
+    /// The backend has returned different object names than the ones that were recorded.
+    ///
+    /// This happens if the context isn't newly-created, or if the implementation allocates its
+    /// names differently from the one that recorded the trace.
+    ObjectNameMismatch(String),
+
+    /// Error while swapping the buffers at the end of a frame.
+    SwapBuffers(SwapBuffersError),
+}
+
+impl ReplayError {
+    fn from_read(err: io::Error) -> ReplayError {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            ReplayError::InvalidTrace
+        } else {
+            ReplayError::Io(err)
+        }
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        use self::ReplayError::*;
+        match self {
+            Io(err) => write!(fmt, "Error while reading the trace: {}", err),
+            InvalidTrace => fmt.write_str("The trace is corrupted or has an incompatible format"),
+            UnsupportedCommand(name) =>
+                write!(fmt, "The command `gl{}` isn't supported by the backend", name),
+            ObjectNameMismatch(name) =>
+                write!(fmt, "The command `gl{}` returned different object names than the ones \
+                             that were recorded", name),
+            SwapBuffers(err) => write!(fmt, "Error while swapping buffers: {}", err),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ReplayError::Io(ref err) => Some(err),
+            ReplayError::SwapBuffers(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    #[inline]
+    fn from(err: io::Error) -> ReplayError {
+        ReplayError::Io(err)
+    }
+}
+
+/// Executes the commands of a trace on a backend.
+pub struct Replayer<R> {
+    trace: R,
+    backend: Box<dyn Backend>,
+    gl: gl::Gl,
+    bindings: Bindings,
+    /// Pointer to the start and length of the mapped range of each mapped buffer.
+    mappings: HashMap<gl::types::GLuint, (usize, usize)>,
+    /// Sync objects of the replay, indexed by the ones of the trace.
+    syncs: HashMap<usize, usize>,
+    skipped_commands: usize,
+}
+
+impl<R> Replayer<R> where R: Read {
+    /// Reads the header of the trace and prepares the replay on the given backend.
+    ///
+    /// # Safety
+    ///
+    /// The OpenGL context of the backend must be newly-created, and must not be used by anything
+    /// else during the replay.
+    pub unsafe fn new<B>(backend: B, mut trace: R) -> Result<Replayer<R>, ReplayError>
+        where B: Backend + 'static
+    {
+        read_header(&mut trace)?;
+
+        backend.make_current();
+        let gl = gl::Gl::load_with(|symbol| backend.get_proc_address(symbol) as *const _);
+
+        Ok(Replayer {
+            trace,
+            backend: Box::new(backend),
+            gl,
+            bindings: Bindings::new(),
+            mappings: HashMap::new(),
+            syncs: HashMap::new(),
+            skipped_commands: 0,
+        })
+    }
+
+    /// Returns the backend that the trace is replayed on.
+    #[inline]
+    pub fn get_backend(&self) -> &dyn Backend {
+        &*self.backend
+    }
+
+    /// Returns the number of commands that couldn't be replayed so far.
+    ///
+    /// These are queries, and commands whose arguments point to memory of unknown size.
+    #[inline]
+    pub fn skipped_commands(&self) -> usize {
+        self.skipped_commands
+    }
+
+    /// Replays the commands of the next frame, then swaps the buffers of the backend.
+    ///
+    /// Returns `false` if the end of the trace has been reached.
+    ///
+    /// # Safety
+    ///
+    /// The trace must come from a trusted source. The sizes passed to the commands are the ones
+    /// that were recorded, and are not checked against the data stored in the trace. A corrupted
+    /// or malicious trace can make OpenGL read or write out of bounds.
+    pub unsafe fn replay_frame(&mut self) -> Result<bool, ReplayError> {
+        if !self.backend.is_current() {
+            self.backend.make_current();
+        }
+
+        loop {
+            match read_record(&mut self.trace)? {
+                None => return Ok(false),
+                Some(Record::EndOfFrame) => {
+                    self.backend.swap_buffers().map_err(ReplayError::SwapBuffers)?;
+                    return Ok(true);
+                },
+                Some(Record::Call(call)) => self.replay_call(&call)?,
+            }
+        }
+    }
+
+    /// Replays all the remaining frames of the trace.
+    ///
+    /// # Safety
+    ///
+    /// Same as `replay_frame`.
+    pub unsafe fn replay_all(&mut self) -> Result<(), ReplayError> {
+        while self.replay_frame()? {}
+        Ok(())
+    }
+
+    unsafe fn replay_call(&mut self, call: &Call) -> Result<(), ReplayError> {
+        let name = call.name.as_str();
+
+        if is_query(name) || name.starts_with("DebugMessageCallback") ||
+           call.payloads.iter().any(|p| p.kind == PayloadKind::Opaque)
+        {
+            self.skipped_commands += 1;
+            self.bindings.update(call);
+            return Ok(());
+        }
+
+        let mut args = call.args.clone();
+        // memory that the arguments point to, which must stay alive during the call
+        let mut strings: Vec<Vec<*const raw::c_char>> = Vec::new();
+        let mut outputs: Vec<(&Payload, Vec<u8>)> = Vec::new();
+
+        for payload in &call.payloads {
+            let arg = payload.arg as usize;
+
+            match payload.kind {
+                PayloadKind::Data => {
+                    args[arg] = Value::Ptr(payload.data.as_ptr() as usize);
+                },
+                PayloadKind::Strings => {
+                    let pointers = payload.data.split_inclusive(|&b| b == 0)
+                                               .map(|s| s.as_ptr() as *const raw::c_char)
+                                               .collect::<Vec<_>>();
+                    args[arg] = Value::Ptr(pointers.as_ptr() as usize);
+                    strings.push(pointers);
+                },
+                PayloadKind::Output => {
+                    outputs.push((payload, vec![0; payload.data.len()]));
+                },
+                PayloadKind::MappedWrite => {
+                    let buffer = if name.contains("Named") {
+                        args[0].as_u64() as gl::types::GLuint
+                    } else {
+                        self.bindings.buffer(args[0].as_u64() as gl::types::GLenum)
+                    };
+
+                    if let Some(&(mapping, length)) = self.mappings.get(&buffer) {
+                        let offset = args[1].as_i64();
+                        if offset < 0 || (offset as u64).checked_add(payload.data.len() as u64)
+                                                        .map_or(true, |end| end > length as u64)
+                        {
+                            return Err(ReplayError::InvalidTrace);
+                        }
+
+                        ptr::copy_nonoverlapping(payload.data.as_ptr(),
+                                                 (mapping + offset as usize) as *mut u8,
+                                                 payload.data.len());
+                    }
+                },
+                PayloadKind::Opaque => unreachable!(),
+            }
+        }
+
+        for &mut (payload, ref mut output) in outputs.iter_mut() {
+            args[payload.arg as usize] = Value::MutPtr(output.as_mut_ptr() as usize);
+        }
+
+        if is_sync_command(name) {
+            if let Some(&sync) = self.syncs.get(&args[0].as_ptr()) {
+                args[0] = Value::Ptr(sync);
+            }
+        }
+
+        let ret = gl::replay_command(&self.gl, name, &args)
+                        .ok_or_else(|| ReplayError::UnsupportedCommand(call.name.clone()))?;
+        drop(strings);
+
+        if outputs.iter().any(|&(payload, ref output)| &payload.data != output) {
+            return Err(ReplayError::ObjectNameMismatch(call.name.clone()));
+        }
+
+        match name {
+            "FenceSync" | "FenceSyncAPPLE" => {
+                self.syncs.insert(call.ret.as_ptr(), ret.as_ptr());
+            },
+            "DeleteSync" | "DeleteSyncAPPLE" => {
+                self.syncs.remove(&call.args[0].as_ptr());
+            },
+            "MapBufferRange" | "MapBufferRangeEXT" => {
+                let buffer = self.bindings.buffer(args[0].as_u64() as gl::types::GLenum);
+                self.mappings.insert(buffer, (ret.as_ptr(), args[2].as_i64() as usize));
+            },
+            "MapNamedBufferRange" | "MapNamedBufferRangeEXT" => {
+                self.mappings.insert(args[0].as_u64() as gl::types::GLuint,
+                                     (ret.as_ptr(), args[2].as_i64() as usize));
+            },
+            "UnmapBuffer" | "UnmapBufferARB" | "UnmapBufferOES" => {
+                let buffer = self.bindings.buffer(args[0].as_u64() as gl::types::GLenum);
+                self.mappings.remove(&buffer);
+            },
+            "UnmapNamedBuffer" | "UnmapNamedBufferEXT" => {
+                self.mappings.remove(&(args[0].as_u64() as gl::types::GLuint));
+            },
+            _ if name.starts_with("Create") => {
+                if let (Value::UInt(recorded), Value::UInt(replayed)) = (call.ret, ret) {
+                    if recorded != replayed {
+                        return Err(ReplayError::ObjectNameMismatch(call.name.clone()));
+                    }
+                }
+            },
+            _ => ()
+        }
+
+        self.bindings.update(call);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Call, Payload, PayloadKind, Record, Value};
+
+    #[test]
+    fn record_roundtrip() {
+        let records = vec![
+            Record::Call(Call {
+                name: "BufferData".to_owned(),
+                args: vec![Value::UInt(34962), Value::Int(4), Value::Ptr(0xdead), Value::UInt(35044)],
+                ret: Value::Void,
+                payloads: vec![Payload { arg: 2, kind: PayloadKind::Data, data: vec![1, 2, 3, 4] }],
+            }),
+            Record::EndOfFrame,
+            Record::Call(Call {
+                name: "ClearColor".to_owned(),
+                args: vec![Value::Float(0.5), Value::Float(0.0), Value::Float(1.0), Value::Float(1.0)],
+                ret: Value::Void,
+                payloads: vec![],
+            }),
+        ];
+
+        let mut trace = Vec::new();
+        super::write_header(&mut trace).unwrap();
+        for record in &records {
+            super::write_record(&mut trace, record).unwrap();
+        }
+
+        let mut reader = &trace[..];
+        super::read_header(&mut reader).unwrap();
+        for record in &records {
+            assert_eq!(super::read_record(&mut reader).unwrap().as_ref(), Some(record));
+        }
+        assert!(super::read_record(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn uniform_array_args() {
+        assert_eq!(super::uniform_array_args("Uniform4fv"), Some((2, 1, 16)));
+        assert_eq!(super::uniform_array_args("Uniform2uivARB"), Some((2, 1, 8)));
+        assert_eq!(super::uniform_array_args("ProgramUniform3dv"), Some((3, 2, 24)));
+        assert_eq!(super::uniform_array_args("UniformMatrix4fv"), Some((3, 1, 64)));
+        assert_eq!(super::uniform_array_args("UniformMatrix2x3dv"), Some((3, 1, 48)));
+        assert_eq!(super::uniform_array_args("UniformBlockBinding"), None);
+        assert_eq!(super::uniform_array_args("Uniform1i"), None);
+    }
+
+    #[test]
+    fn upload_size() {
+        let dims = |w, h| vec![Some(w), Some(h), None].into_iter();
+        assert_eq!(super::upload_size(dims(4, 4), 4, 4), 64);
+        assert_eq!(super::upload_size(dims(3, 2), 1, 4), 7);
+        assert_eq!(super::upload_size(dims(3, 2), 1, 1), 6);
+        assert_eq!(super::upload_size(dims(0, 2), 4, 4), 0);
+    }
+}
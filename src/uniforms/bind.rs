@@ -5,6 +5,7 @@ Handles binding uniforms to the OpenGL state machine.
 */
 use crate::gl;
 
+use std::cmp;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
 
@@ -203,10 +204,26 @@ fn bind_uniform_block<'a, P>(ctxt: &mut context::CommandContext<'_>, value: &Uni
                 }
             }
 
+            let alignment = cmp::max(ctxt.capabilities.uniform_buffer_offset_alignment, 1) as usize;
+            if buffer.get_offset_bytes() % alignment != 0 {
+                return Err(DrawError::UniformBlockOffsetNotAligned {
+                    name: name.to_owned(),
+                    alignment,
+                });
+            }
+
+            let min_size = block.layout.get_min_size();
+            if buffer.get_size() < min_size {
+                return Err(DrawError::UniformBlockBufferTooSmall {
+                    name: name.to_owned(),
+                    expected: min_size,
+                    obtained: buffer.get_size(),
+                });
+            }
+
             let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
             buffer_bind_points.set_used(bind_point);
 
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let block_id = block.id as gl::types::GLuint;
 
@@ -696,20 +713,27 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
 }
 
 fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
-                              texture: &T, sampler: Option<SamplerBehavior>,
+                              texture: &T, sampler_behavior: Option<SamplerBehavior>,
                               location: gl::types::GLint, program: &P,
                               texture_bind_points: &mut Bitsfield)
                               -> Result<(), DrawError> where P: ProgramExt, T: TextureExt
 {
     texture.prepare_for_access(ctxt, crate::TextureAccess::TextureFetch);
     
-    let sampler = if let Some(sampler) = sampler {
-        Some(crate::sampler_object::get_sampler(ctxt, &sampler)?)
-    } else {
-        None
+    // without sampler objects, the parameters are stored in the texture itself
+    let samplers_supported = crate::sampler_object::is_supported(ctxt);
+    let sampler = match sampler_behavior {
+        Some(ref behavior) if samplers_supported => crate::sampler_object::get_sampler(ctxt, behavior)?,
+        _ => 0,
     };
+    let update_parameters = !samplers_supported && texture.get_sampler_parameters() != sampler_behavior;
 
-    let sampler = sampler.unwrap_or(0);
+    // the parameters of a texture can't be changed while it is already used by this draw call
+    if update_parameters && ctxt.state.texture_units.iter().enumerate().any(|(unit, content)| {
+        content.texture == texture.get_texture_id() && texture_bind_points.is_used(unit as u16)
+    }) {
+        return Err(DrawError::SamplerBehaviorsConflict);
+    }
 
     // finding an appropriate texture unit
     let texture_unit =
@@ -749,7 +773,8 @@ fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
 
     // TODO: do better
     if ctxt.state.texture_units[texture_unit as usize].texture != texture.get_texture_id() ||
-       ctxt.state.texture_units[texture_unit as usize].sampler != sampler
+       ctxt.state.texture_units[texture_unit as usize].sampler != sampler ||
+       update_parameters
     {
         // TODO: what if it's not supported?
         if ctxt.state.active_texture != texture_unit as gl::types::GLenum {
@@ -759,6 +784,10 @@ fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
 
         texture.bind_to_current(ctxt);
 
+        if update_parameters {
+            unsafe { texture.set_sampler_parameters(ctxt, sampler_behavior); }
+        }
+
         if ctxt.state.texture_units[texture_unit as usize].sampler != sampler {
             assert!(ctxt.version >= &Version(Api::Gl, 3, 3) ||
                     ctxt.version >= &Version(Api::GlEs, 3, 0) ||
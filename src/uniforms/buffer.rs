@@ -245,3 +245,18 @@ impl<'a, T: ?Sized> AsUniformValue for &'a UniformBuffer<T> where T: UniformBloc
         UniformValue::Block(self.buffer.as_slice_any(), f::<T>)
     }
 }
+
+impl<'a, T: ?Sized> AsUniformValue for BufferSlice<'a, T> where T: UniformBlock + Content {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        #[inline]
+        fn f<T: ?Sized>(block: &program::UniformBlock)
+                        -> Result<(), LayoutMismatchError> where T: UniformBlock + Content
+        {
+            // the offset and the size of the slice are checked when it is bound
+            T::matches(&block.layout, 0)
+        }
+
+        UniformValue::Block(self.as_slice_any(), f::<T>)
+    }
+}
@@ -0,0 +1,158 @@
+/// Objects whose alignment and size inside of a uniform block or a SSBO are known at compile
+/// time for the `std140` and `std430` layouts.
+///
+/// The `UniformBlock` derive uses this trait to check at compile time that a Rust struct has the
+/// same layout as a GLSL block.
+///
+/// # Safety
+///
+/// The constants must follow the layout rules of the OpenGL specifications (section 7.6.2.2
+/// of OpenGL 4.5).
+pub unsafe trait StdLayout {
+    /// Alignment of the type with the `std140` layout.
+    const STD140_ALIGNMENT: usize;
+    /// Size of the type with the `std140` layout.
+    const STD140_SIZE: usize;
+    /// Alignment of the type with the `std430` layout.
+    const STD430_ALIGNMENT: usize;
+    /// Size of the type with the `std430` layout.
+    const STD430_SIZE: usize;
+}
+
+/// Rounds `offset` up to a multiple of `alignment`.
+#[inline]
+pub(crate) const fn align_up(offset: usize, alignment: usize) -> usize {
+    (offset + alignment - 1) / alignment * alignment
+}
+
+/// Returns the alignment of the elements of an array whose content has the given alignment.
+#[inline]
+pub(crate) const fn std140_array_alignment(alignment: usize) -> usize {
+    // with std140, the alignment of arrays is rounded up to the one of a vec4
+    if alignment > 16 { alignment } else { 16 }
+}
+
+macro_rules! impl_std_layout_scalar {
+    ($ty:ty, $size:expr) => (
+        unsafe impl StdLayout for $ty {
+            const STD140_ALIGNMENT: usize = $size;
+            const STD140_SIZE: usize = $size;
+            const STD430_ALIGNMENT: usize = $size;
+            const STD430_SIZE: usize = $size;
+        }
+    );
+}
+
+macro_rules! impl_std_layout_vector {
+    ($ty:ty, $scalar_size:expr, $components:expr) => (
+        unsafe impl StdLayout for $ty {
+            // three-components vectors are aligned like four-components vectors
+            const STD140_ALIGNMENT: usize = $scalar_size * if $components == 2 { 2 } else { 4 };
+            const STD140_SIZE: usize = $scalar_size * $components;
+            const STD430_ALIGNMENT: usize = Self::STD140_ALIGNMENT;
+            const STD430_SIZE: usize = Self::STD140_SIZE;
+        }
+    );
+}
+
+macro_rules! impl_std_layout_matrix {
+    ($ty:ty, $column:ty, $columns:expr) => (
+        // matrices are laid out like arrays of column vectors
+        unsafe impl StdLayout for $ty {
+            const STD140_ALIGNMENT: usize =
+                std140_array_alignment(<$column as StdLayout>::STD140_ALIGNMENT);
+            const STD140_SIZE: usize =
+                $columns * align_up(<$column as StdLayout>::STD140_SIZE, Self::STD140_ALIGNMENT);
+            const STD430_ALIGNMENT: usize = <$column as StdLayout>::STD430_ALIGNMENT;
+            const STD430_SIZE: usize =
+                $columns * align_up(<$column as StdLayout>::STD430_SIZE, Self::STD430_ALIGNMENT);
+        }
+    );
+}
+
+// glsl booleans are 4 bytes large
+impl_std_layout_scalar!(bool, 4);
+impl_std_layout_scalar!(i32, 4);
+impl_std_layout_scalar!(u32, 4);
+impl_std_layout_scalar!(f32, 4);
+impl_std_layout_scalar!(f64, 8);
+impl_std_layout_scalar!(i64, 8);
+impl_std_layout_scalar!(u64, 8);
+
+impl_std_layout_vector!([bool; 2], 4, 2);
+impl_std_layout_vector!((bool, bool), 4, 2);
+impl_std_layout_vector!([bool; 3], 4, 3);
+impl_std_layout_vector!((bool, bool, bool), 4, 3);
+impl_std_layout_vector!([bool; 4], 4, 4);
+impl_std_layout_vector!((bool, bool, bool, bool), 4, 4);
+impl_std_layout_vector!([i32; 2], 4, 2);
+impl_std_layout_vector!((i32, i32), 4, 2);
+impl_std_layout_vector!([i32; 3], 4, 3);
+impl_std_layout_vector!((i32, i32, i32), 4, 3);
+impl_std_layout_vector!([i32; 4], 4, 4);
+impl_std_layout_vector!((i32, i32, i32, i32), 4, 4);
+impl_std_layout_vector!([u32; 2], 4, 2);
+impl_std_layout_vector!((u32, u32), 4, 2);
+impl_std_layout_vector!([u32; 3], 4, 3);
+impl_std_layout_vector!((u32, u32, u32), 4, 3);
+impl_std_layout_vector!([u32; 4], 4, 4);
+impl_std_layout_vector!((u32, u32, u32, u32), 4, 4);
+impl_std_layout_vector!([f32; 2], 4, 2);
+impl_std_layout_vector!((f32, f32), 4, 2);
+impl_std_layout_vector!([f32; 3], 4, 3);
+impl_std_layout_vector!((f32, f32, f32), 4, 3);
+impl_std_layout_vector!([f32; 4], 4, 4);
+impl_std_layout_vector!((f32, f32, f32, f32), 4, 4);
+impl_std_layout_vector!([f64; 2], 8, 2);
+impl_std_layout_vector!((f64, f64), 8, 2);
+impl_std_layout_vector!([f64; 3], 8, 3);
+impl_std_layout_vector!((f64, f64, f64), 8, 3);
+impl_std_layout_vector!([f64; 4], 8, 4);
+impl_std_layout_vector!((f64, f64, f64, f64), 8, 4);
+impl_std_layout_vector!([i64; 2], 8, 2);
+impl_std_layout_vector!((i64, i64), 8, 2);
+impl_std_layout_vector!([i64; 3], 8, 3);
+impl_std_layout_vector!((i64, i64, i64), 8, 3);
+impl_std_layout_vector!([i64; 4], 8, 4);
+impl_std_layout_vector!((i64, i64, i64, i64), 8, 4);
+impl_std_layout_vector!([u64; 2], 8, 2);
+impl_std_layout_vector!((u64, u64), 8, 2);
+impl_std_layout_vector!([u64; 3], 8, 3);
+impl_std_layout_vector!((u64, u64, u64), 8, 3);
+impl_std_layout_vector!([u64; 4], 8, 4);
+impl_std_layout_vector!((u64, u64, u64, u64), 8, 4);
+
+impl_std_layout_matrix!([[f32; 2]; 2], [f32; 2], 2);
+impl_std_layout_matrix!([[f32; 3]; 3], [f32; 3], 3);
+impl_std_layout_matrix!([[f32; 4]; 4], [f32; 4], 4);
+impl_std_layout_matrix!([[f64; 2]; 2], [f64; 2], 2);
+impl_std_layout_matrix!([[f64; 3]; 3], [f64; 3], 3);
+impl_std_layout_matrix!([[f64; 4]; 4], [f64; 4], 4);
+
+#[cfg(test)]
+mod tests {
+    use super::StdLayout;
+
+    #[test]
+    fn vectors() {
+        assert_eq!(<[f32; 3] as StdLayout>::STD140_ALIGNMENT, 16);
+        assert_eq!(<[f32; 3] as StdLayout>::STD140_SIZE, 12);
+        assert_eq!(<[f64; 2] as StdLayout>::STD430_ALIGNMENT, 16);
+    }
+
+    #[test]
+    fn matrices() {
+        assert_eq!(<[[f32; 2]; 2] as StdLayout>::STD140_SIZE, 32);
+        assert_eq!(<[[f32; 2]; 2] as StdLayout>::STD430_SIZE, 16);
+        assert_eq!(<[[f32; 3]; 3] as StdLayout>::STD140_SIZE, 48);
+        assert_eq!(<[[f32; 3]; 3] as StdLayout>::STD430_SIZE, 48);
+        assert_eq!(<[[f32; 4]; 4] as StdLayout>::STD430_SIZE, 64);
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(<[f32; 5] as StdLayout>::STD140_SIZE, 80);
+        assert_eq!(<[f32; 5] as StdLayout>::STD430_SIZE, 20);
+        assert_eq!(<[[f32; 3]; 6] as StdLayout>::STD430_SIZE, 96);
+    }
+}
@@ -19,6 +19,9 @@ let uniforms = uniform! {
 
 Each field must implement the `UniformValue` trait for this to work.
 
+If the `derive` feature is enabled, you can also use `#[derive(Uniforms)]` on a struct whose
+fields implement `AsUniformValue`, and `#[derive(UniformBlock)]` on the content of blocks.
+
 ## Samplers
 
 In order to customize the way a texture is being sampled, you must use a `Sampler`.
@@ -127,6 +130,7 @@ pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySampler
 pub use self::sampler::{Sampler, SamplerBehavior};
 pub use self::image_unit::{ImageUnitAccess, ImageUnitFormat, ImageUnitError};
 pub use self::image_unit::{ImageUnit, ImageUnitBehavior};
+pub use self::layout::StdLayout;
 pub use self::uniforms::{EmptyUniforms, UniformsStorage};
 pub use self::value::{UniformValue, UniformType};
 
@@ -141,6 +145,7 @@ use crate::program::BlockLayout;
 mod bind;
 mod buffer;
 mod image_unit;
+mod layout;
 mod sampler;
 mod uniforms;
 mod value;
@@ -390,6 +395,14 @@ macro_rules! impl_uniform_block_array {
                 }
             }
         }
+
+        unsafe impl<T> StdLayout for [T; $len] where T: StdLayout {
+            const STD140_ALIGNMENT: usize = layout::std140_array_alignment(T::STD140_ALIGNMENT);
+            const STD140_SIZE: usize =
+                $len * layout::align_up(T::STD140_SIZE, Self::STD140_ALIGNMENT);
+            const STD430_ALIGNMENT: usize = T::STD430_ALIGNMENT;
+            const STD430_SIZE: usize = $len * layout::align_up(T::STD430_SIZE, T::STD430_ALIGNMENT);
+        }
     );
 }
 
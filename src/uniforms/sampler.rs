@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use crate::ToGlEnum;
 use crate::gl;
 
@@ -15,7 +17,9 @@ pub enum SamplerWrapFunction {
     /// Samples at coord `x + 1` map to coord `1`.
     Clamp,
 
-    /// Use texture border.
+    /// Samples outside of the texture return the border color of the sampler.
+    ///
+    /// See `SamplerBehavior::border_color`.
     BorderClamp,
 
     /// Same as Mirror, but only for one repetition,
@@ -179,6 +183,31 @@ impl<'t, T: 't> Sampler<'t, T> {
         self.1.max_anisotropy = level;
         self
     }
+
+    /// Changes the color returned for samples outside of the texture when the wrap function
+    /// is `BorderClamp`.
+    pub fn border_color(mut self, color: [f32; 4]) -> Sampler<'t, T> {
+        self.1.border_color = color;
+        self
+    }
+
+    /// Changes the minimum level of detail that can be selected.
+    pub fn min_lod(mut self, lod: f32) -> Sampler<'t, T> {
+        self.1.min_lod = lod;
+        self
+    }
+
+    /// Changes the maximum level of detail that can be selected.
+    pub fn max_lod(mut self, lod: f32) -> Sampler<'t, T> {
+        self.1.max_lod = lod;
+        self
+    }
+
+    /// Changes the bias added to the level of detail before selecting a mipmap.
+    pub fn lod_bias(mut self, bias: f32) -> Sampler<'t, T> {
+        self.1.lod_bias = bias;
+        self
+    }
 }
 
 impl<'t, T: 't> Copy for Sampler<'t, T> {}
@@ -190,8 +219,7 @@ impl<'t, T: 't> Clone for Sampler<'t, T> {
 }
 
 /// Behavior of a sampler.
-// TODO: GL_TEXTURE_BORDER_COLOR, GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD, GL_TEXTURE_LOD_BIAS
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy)]
 pub struct SamplerBehavior {
     /// Functions to use for the X, Y, and Z coordinates.
     pub wrap_function: (SamplerWrapFunction, SamplerWrapFunction, SamplerWrapFunction),
@@ -215,6 +243,73 @@ pub struct SamplerBehavior {
     /// If you set the value to a value higher than what the hardware supports, it will
     /// be clamped.
     pub max_anisotropy: u16,
+
+    /// Color of the samples outside of the texture when the wrap function is `BorderClamp`.
+    /// Default value is `[0.0, 0.0, 0.0, 0.0]`.
+    ///
+    /// For depth textures, the first component is used as the depth value. For example a
+    /// shadow map sampled with a depth comparison usually needs a border of `1.0`.
+    ///
+    /// ## Compatibility
+    ///
+    /// Ignored on OpenGL ES before 3.2 if neither `GL_EXT_texture_border_clamp` nor
+    /// `GL_OES_texture_border_clamp` are available.
+    pub border_color: [f32; 4],
+
+    /// Minimum level of detail that can be selected. Default value is `-1000.0`.
+    pub min_lod: f32,
+
+    /// Maximum level of detail that can be selected. Default value is `1000.0`.
+    ///
+    /// This can be used to prevent the sampler from using mipmaps that haven't been loaded yet.
+    pub max_lod: f32,
+
+    /// Bias added to the level of detail before selecting a mipmap. Default value is `0.0`.
+    ///
+    /// ## Compatibility
+    ///
+    /// Ignored on OpenGL ES, which doesn't support this parameter.
+    pub lod_bias: f32,
+}
+
+impl SamplerBehavior {
+    /// Returns the floating-point fields as bits, so that they can be compared and hashed.
+    #[inline]
+    fn float_bits(&self) -> [u32; 7] {
+        [
+            self.border_color[0].to_bits(), self.border_color[1].to_bits(),
+            self.border_color[2].to_bits(), self.border_color[3].to_bits(),
+            self.min_lod.to_bits(), self.max_lod.to_bits(), self.lod_bias.to_bits(),
+        ]
+    }
+}
+
+// `SamplerBehavior` is used as the key of the sampler objects cache, so floats are compared
+// bitwise in order for `Eq` and `Hash` to be consistent
+impl PartialEq for SamplerBehavior {
+    #[inline]
+    fn eq(&self, other: &SamplerBehavior) -> bool {
+        self.wrap_function == other.wrap_function &&
+        self.minify_filter == other.minify_filter &&
+        self.magnify_filter == other.magnify_filter &&
+        self.depth_texture_comparison == other.depth_texture_comparison &&
+        self.max_anisotropy == other.max_anisotropy &&
+        self.float_bits() == other.float_bits()
+    }
+}
+
+impl Eq for SamplerBehavior {}
+
+impl Hash for SamplerBehavior {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.wrap_function.hash(state);
+        self.minify_filter.hash(state);
+        self.magnify_filter.hash(state);
+        self.depth_texture_comparison.hash(state);
+        self.max_anisotropy.hash(state);
+        self.float_bits().hash(state);
+    }
 }
 
 impl Default for SamplerBehavior {
@@ -230,6 +325,10 @@ impl Default for SamplerBehavior {
             magnify_filter: MagnifySamplerFilter::Linear,
             depth_texture_comparison: None,
             max_anisotropy: 1,
+            border_color: [0.0, 0.0, 0.0, 0.0],
+            min_lod: -1000.0,
+            max_lod: 1000.0,
+            lod_bias: 0.0,
         }
     }
 }
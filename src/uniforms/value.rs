@@ -138,6 +138,38 @@ pub enum UniformType {
     AtomicCounterUint,
 }
 
+impl UniformType {
+    /// Returns the number of bytes that a value of this type occupies in a buffer, without the
+    /// padding between the columns of matrices.
+    pub(crate) fn get_size_bytes(&self) -> usize {
+        use self::UniformType::*;
+        match *self {
+            Float | Int | UnsignedInt | Bool | AtomicCounterUint => 4,
+            FloatVec2 | IntVec2 | UnsignedIntVec2 | BoolVec2 => 8,
+            FloatVec3 | IntVec3 | UnsignedIntVec3 | BoolVec3 => 12,
+            FloatVec4 | IntVec4 | UnsignedIntVec4 | BoolVec4 => 16,
+            Double | Int64 | UnsignedInt64 => 8,
+            DoubleVec2 | Int64Vec2 | UnsignedInt64Vec2 => 16,
+            DoubleVec3 | Int64Vec3 | UnsignedInt64Vec3 => 24,
+            DoubleVec4 | Int64Vec4 | UnsignedInt64Vec4 => 32,
+            FloatMat2 => 16,
+            FloatMat3 => 36,
+            FloatMat4 => 64,
+            FloatMat2x3 | FloatMat3x2 => 24,
+            FloatMat2x4 | FloatMat4x2 => 32,
+            FloatMat3x4 | FloatMat4x3 => 48,
+            DoubleMat2 => 32,
+            DoubleMat3 => 72,
+            DoubleMat4 => 128,
+            DoubleMat2x3 | DoubleMat3x2 => 48,
+            DoubleMat2x4 | DoubleMat4x2 => 64,
+            DoubleMat3x4 | DoubleMat4x3 => 96,
+            // samplers and images can only be stored in a buffer as bindless handles
+            _ => 8,
+        }
+    }
+}
+
 /// Represents a value to bind to a uniform.
 #[allow(missing_docs)]
 #[derive(Copy)]
@@ -77,6 +77,14 @@ pub struct VertexBufferSlice<'b, T> where T: Copy {
 }
 
 impl<'b, T: 'b> VertexBufferSlice<'b, T> where T: Copy + Content {
+    /// Builds a vertex buffer slice from a buffer slice and the layout of its elements.
+    #[inline]
+    pub(crate) fn from_buffer_slice(buffer: BufferSlice<'b, [T]>, bindings: &'b VertexFormat)
+                                    -> VertexBufferSlice<'b, T>
+    {
+        VertexBufferSlice { buffer, bindings }
+    }
+
     /// Creates a marker that instructs glium to use multiple instances.
     ///
     /// Instead of calling `surface.draw(&vertex_buffer.slice(...).unwrap(), ...)`
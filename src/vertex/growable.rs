@@ -0,0 +1,103 @@
+use std::ops::{Deref, DerefMut};
+use crate::utils::range::RangeArgument;
+
+use crate::buffer::{BufferMode, BufferType, GrowableBuffer};
+use crate::vertex::{Vertex, VertexBufferSlice, VerticesSource};
+use crate::vertex::buffer::CreationError;
+use crate::vertex::format::VertexFormat;
+
+use crate::backend::Facade;
+
+/// A list of vertices whose length can change, similar to a `Vec`.
+///
+/// The vertices are stored in the graphics card's memory. When the capacity is exceeded, the
+/// vertices are moved to a larger buffer by the GPU. See `GrowableBuffer` for the methods that
+/// modify the content.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {}
+/// # fn example(display: glium::Display, program: glium::Program) {
+/// # use glium::Surface;
+/// #[derive(Copy, Clone)]
+/// struct Vertex { position: [f32; 2] }
+/// implement_vertex!(Vertex, position);
+///
+/// let mut lines = glium::vertex::GrowableVertexBuffer::new(&display).unwrap();
+/// lines.push(Vertex { position: [-0.5, 0.0] });
+/// lines.push(Vertex { position: [0.5, 0.0] });
+///
+/// let mut target = display.draw();
+/// target.draw(&lines, &glium::index::NoIndices(glium::index::PrimitiveType::LinesList),
+///             &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GrowableVertexBuffer<T> where T: Vertex {
+    buffer: GrowableBuffer<T>,
+    bindings: VertexFormat,
+}
+
+impl<T> GrowableVertexBuffer<T> where T: Vertex {
+    /// Builds a new empty vertex buffer.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F) -> Result<GrowableVertexBuffer<T>, CreationError>
+                          where F: Facade
+    {
+        GrowableVertexBuffer::with_capacity(facade, 0)
+    }
+
+    /// Builds a new empty vertex buffer that can hold `capacity` vertices without reallocating.
+    pub fn with_capacity<F: ?Sized>(facade: &F, capacity: usize)
+                                    -> Result<GrowableVertexBuffer<T>, CreationError>
+                                    where F: Facade
+    {
+        if !T::is_supported(facade) {
+            return Err(CreationError::FormatNotSupported);
+        }
+
+        Ok(GrowableVertexBuffer {
+            buffer: GrowableBuffer::with_capacity(facade, BufferType::ArrayBuffer, capacity,
+                                                  BufferMode::Dynamic)?,
+            bindings: <T as Vertex>::build_bindings(),
+        })
+    }
+
+    /// Returns the layout of the vertices.
+    #[inline]
+    pub fn get_bindings(&self) -> &VertexFormat {
+        &self.bindings
+    }
+
+    /// Returns a slice of the vertices, or `None` if out of range.
+    #[inline]
+    pub fn slice<R: RangeArgument<usize>>(&self, range: R) -> Option<VertexBufferSlice<'_, T>> {
+        self.buffer.slice(range).map(|b| VertexBufferSlice::from_buffer_slice(b, &self.bindings))
+    }
+}
+
+impl<T> Deref for GrowableVertexBuffer<T> where T: Vertex {
+    type Target = GrowableBuffer<T>;
+
+    #[inline]
+    fn deref(&self) -> &GrowableBuffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T> DerefMut for GrowableVertexBuffer<T> where T: Vertex {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut GrowableBuffer<T> {
+        &mut self.buffer
+    }
+}
+
+impl<'a, T> From<&'a GrowableVertexBuffer<T>> for VerticesSource<'a> where T: Vertex {
+    #[inline]
+    fn from(this: &'a GrowableVertexBuffer<T>) -> VerticesSource<'a> {
+        VerticesSource::VertexBuffer(this.buffer.as_slice().as_slice_any(), &this.bindings, false)
+    }
+}
@@ -132,6 +132,7 @@ use std::option::IntoIter;
 
 pub use self::buffer::{VertexBuffer, VertexBufferAny};
 pub use self::buffer::VertexBufferSlice;
+pub use self::growable::GrowableVertexBuffer;
 pub use self::buffer::CreationError as BufferCreationError;
 pub use self::format::{AttributeType, VertexFormat};
 pub use self::transform_feedback::{is_transform_feedback_supported, TransformFeedbackSession};
@@ -141,6 +142,7 @@ use crate::CapabilitiesSource;
 
 mod buffer;
 mod format;
+mod growable;
 mod transform_feedback;
 
 /// Describes the source to use for the vertices when drawing.
@@ -93,3 +93,101 @@ fn blit_color_and_depth_buffer() {
     // todo: how to check dst_tex_depth? There is no .read() on a DepthTexture2d...
     display.assert_no_error(None);
 }
+
+#[test]
+fn copy_texture_image() {
+    let display = support::build_display();
+
+    let texture = support::build_unicolor_texture2d(&display, 0.0, 1.0, 0.0);
+
+    let target = support::build_renderable_texture(&display);
+    target.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let src_rect = Rect {
+        left: 0,
+        bottom: 0,
+        width: 2,
+        height: 2,
+    };
+
+    let source = texture.main_level().first_layer().into_image(None).unwrap();
+    let dest = target.main_level().first_layer().into_image(None).unwrap();
+    source.copy_to(&src_rect, dest, 1, 1).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+
+    assert_eq!(data[1][1], (0, 255, 0, 255));
+    assert_eq!(data[2][2], (0, 255, 0, 255));
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+    assert_eq!(data[3][3], (0, 0, 0, 0));
+
+    let out_of_range = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    assert_eq!(source.copy_to(&out_of_range, dest, 1023, 0),
+               Err(glium::texture::CopyError::OutOfRange));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_compressed_texture_image() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedFormat::S3tcDxt1NoAlpha;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // 2x2 blocks of 8 bytes filled with their index
+    let data = (0 .. 4).flat_map(|block| vec![block as u8 + 1; 8]).collect::<Vec<_>>();
+    let texture = glium::texture::CompressedTexture2d::with_compressed_data(&display, &data, 8, 8,
+                        format, glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+    let target = glium::texture::CompressedTexture2d::with_compressed_data(&display, &[0; 32],
+                        8, 8, format, glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    let source = texture.main_level().first_layer().into_image(None).unwrap();
+    let dest = target.main_level().first_layer().into_image(None).unwrap();
+    let rect = Rect { left: 4, bottom: 0, width: 4, height: 4 };
+    match source.copy_to(&rect, dest, 0, 4) {
+        Err(glium::texture::CopyError::NotSupported) => return,
+        result => result.unwrap(),
+    }
+
+    let misaligned = Rect { left: 2, bottom: 0, width: 4, height: 4 };
+    assert_eq!(source.copy_to(&misaligned, dest, 0, 0),
+               Err(glium::texture::CopyError::OutOfRange));
+
+    if let Some((_, data)) = target.read_compressed_data() {
+        let mut expected = vec![0; 32];
+        expected[16 .. 24].copy_from_slice(&[2; 8]);
+        assert_eq!(data, expected);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_texture_image_without_conversion() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::with_format(&display, vec![vec![1.0f32]],
+                        glium::texture::UncompressedFloatFormat::F32,
+                        glium::texture::MipmapsOption::NoMipmap).unwrap();
+    let target = glium::Texture2d::with_format(&display, vec![vec![(0u8, 0u8, 0u8, 0u8)]],
+                        glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                        glium::texture::MipmapsOption::NoMipmap).unwrap();
+
+    let source = texture.main_level().first_layer().into_image(None).unwrap();
+    let dest = target.main_level().first_layer().into_image(None).unwrap();
+    let rect = Rect { left: 0, bottom: 0, width: 1, height: 1 };
+    match source.copy_to(&rect, dest, 0, 0) {
+        Err(glium::texture::CopyError::NotSupported) => return,
+        result => result.unwrap(),
+    }
+
+    // the bits of `1.0f32` are copied as they are
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    let bytes = 1.0f32.to_ne_bytes();
+    assert_eq!(data[0][0], (bytes[0], bytes[1], bytes[2], bytes[3]));
+
+    display.assert_no_error(None);
+}
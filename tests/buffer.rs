@@ -976,3 +976,212 @@ fn copy_to_slice() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn buffer_transfer_same_share_group() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    let transfer = buffer.into_transfer();
+    assert_eq!(transfer.get_size(), 12);
+    assert_eq!(transfer.get_share_group(), display.get_share_group());
+
+    // the transfer is meant to be sent to another thread
+    let transfer = std::thread::spawn(move || transfer).join().unwrap();
+
+    let buffer = glium::buffer::Buffer::from_transfer(&display, transfer).unwrap();
+
+    let data = match buffer.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, [1, 2, 3]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_transfer_shared_context() {
+    let display = support::build_display();
+    let shared = match support::build_shared_context(&display) {
+        Some(c) => c,
+        None => return
+    };
+
+    let buffer = glium::buffer::Buffer::new(&shared, &[1u32, 2, 3][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    let transfer = buffer.into_transfer();
+    let buffer = glium::buffer::Buffer::from_transfer(&display, transfer).unwrap();
+
+    let data = match buffer.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, [1, 2, 3]);
+
+    display.assert_no_error(None);
+    shared.assert_no_error(None);
+}
+
+#[test]
+fn buffer_transfer_other_share_group() {
+    let display = support::build_display();
+    let other = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    let transfer = buffer.into_transfer();
+    let transfer = match glium::buffer::Buffer::from_transfer(&other, transfer) {
+        Err(transfer) => transfer,
+        Ok(_) => panic!()
+    };
+
+    let buffer = glium::buffer::Buffer::from_transfer(&display, transfer).unwrap();
+
+    let data = match buffer.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, [1, 2, 3]);
+
+    display.assert_no_error(None);
+    other.assert_no_error(None);
+}
+
+#[test]
+fn read_async() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    let pending = match buffer.read_async() {
+        Ok(p) => p,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    // the content of the buffer must not be affected by later writes
+    buffer.write(&[5, 6, 7, 8]);
+
+    assert_eq!(pending.wait().unwrap(), [1, 2, 3, 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_async_slice_poll() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    let mut pending = match buffer.slice(1 .. 3).unwrap().read_async() {
+        Ok(p) => p,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data = loop {
+        match pending.try_get() {
+            Ok(data) => break data.unwrap(),
+            Err(p) => pending = p,
+        }
+    };
+
+    assert_eq!(data, [2, 3]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    buffer.clear();
+
+    match buffer.read() {
+        Ok(data) => assert_eq!(data, [0, 0, 0, 0]),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn fill_slice() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1.0f32, 2.0, 3.0, 4.0][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Persistent).unwrap();
+
+    buffer.slice(1 .. 3).unwrap().fill(-0.5);
+
+    match buffer.read() {
+        Ok(data) => assert_eq!(data, [1.0, -0.5, -0.5, 4.0]),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn fill_vec4() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::<[(u32, u32, u32, u32)]>::empty_array(&display,
+                                            glium::buffer::BufferType::ArrayBuffer, 3,
+                                            BufferMode::Default).unwrap();
+
+    buffer.fill((1, 2, 3, 4));
+
+    match buffer.read() {
+        Ok(data) => assert_eq!(data, [(1, 2, 3, 4); 3]),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn fill_unaligned_pattern() {
+    let display = support::build_display();
+
+    // there is no format of three one-byte components, so the data is uploaded by the CPU
+    let buffer = glium::buffer::Buffer::<[(u8, u8, u8)]>::empty_array(&display,
+                                            glium::buffer::BufferType::ArrayBuffer, 5,
+                                            BufferMode::Default).unwrap();
+
+    buffer.fill((7, 8, 9));
+
+    match buffer.read() {
+        Ok(data) => assert_eq!(data, [(7, 8, 9); 5]),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
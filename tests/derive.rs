@@ -0,0 +1,161 @@
+#![cfg(feature = "derive")]
+
+use glium::program::BlockLayout;
+use glium::uniforms::{StdLayout, UniformBlock, UniformType, UniformValue, Uniforms};
+use glium::vertex::{Attribute, AttributeType, Vertex};
+
+#[test]
+fn vertex_bindings() {
+    #[derive(Copy, Clone, glium::Vertex)]
+    struct Color {
+        #[glium(normalize)]
+        color: [u8; 4],
+    }
+
+    #[derive(Copy, Clone, glium::Vertex)]
+    #[repr(C)]
+    struct MyVertex<T: Copy + Attribute> {
+        position: [f32; 3],
+        #[glium(name = "texcoords", location = 2)]
+        tex_coords: T,
+        #[glium(skip)]
+        _padding: u32,
+        #[glium(flatten)]
+        color: Color,
+    }
+
+    let bindings = <MyVertex<[f32; 2]> as Vertex>::build_bindings();
+    assert_eq!(bindings.len(), 3);
+
+    assert_eq!(bindings[0].0, "position");
+    assert_eq!(bindings[0].1, 0);
+    assert_eq!(bindings[0].2, -1);
+    assert_eq!(bindings[0].3, AttributeType::F32F32F32);
+    assert!(!bindings[0].4);
+
+    assert_eq!(bindings[1].0, "texcoords");
+    assert_eq!(bindings[1].1, 12);
+    assert_eq!(bindings[1].2, 2);
+    assert_eq!(bindings[1].3, AttributeType::F32F32);
+
+    assert_eq!(bindings[2].0, "color");
+    assert_eq!(bindings[2].1, 24);
+    assert_eq!(bindings[2].3, AttributeType::U8U8U8U8);
+    assert!(bindings[2].4);
+}
+
+#[test]
+fn uniform_block_layout() {
+    #[derive(Copy, Clone, glium::UniformBlock)]
+    #[glium(layout = "std140")]
+    #[repr(C)]
+    struct Light {
+        position: [f32; 3],
+        intensity: f32,
+        color: [f32; 3],
+        #[glium(skip)]
+        _padding: f32,
+    }
+
+    #[derive(Copy, Clone, glium::UniformBlock)]
+    #[glium(layout = "std140")]
+    #[repr(C)]
+    struct Lights {
+        #[glium(name = "lightsCount")]
+        count: u32,
+        #[glium(skip)]
+        _padding: [u32; 3],
+        lights: [Light; 5],
+    }
+
+    assert_eq!(<Light as StdLayout>::STD140_ALIGNMENT, 16);
+    assert_eq!(<Light as StdLayout>::STD140_SIZE, 32);
+    assert_eq!(<Lights as StdLayout>::STD140_SIZE, 16 + 5 * 32);
+
+    let layout = <Lights as UniformBlock>::build_layout(0);
+    let members = match &layout {
+        BlockLayout::Struct { members } => members,
+        _ => panic!(),
+    };
+
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[0], ("lightsCount".to_owned(), BlockLayout::BasicType {
+        ty: UniformType::UnsignedInt,
+        offset_in_buffer: 0,
+    }));
+
+    match &members[1] {
+        (name, BlockLayout::Array { content, length: 5 }) if name == "lights" => {
+            assert_eq!(**content, <Light as UniformBlock>::build_layout(16));
+        },
+        _ => panic!(),
+    }
+
+    assert!(<Lights as UniformBlock>::matches(&layout, 0).is_ok());
+    assert!(<Lights as UniformBlock>::matches(&layout, 4).is_err());
+}
+
+#[test]
+fn uniform_block_unsized() {
+    #[derive(glium::UniformBlock)]
+    #[glium(layout = "std430")]
+    #[repr(C)]
+    struct Data {
+        scale: f32,
+        #[glium(skip)]
+        _padding: [f32; 3],
+        values: [[f32; 4]],
+    }
+
+    let layout = <Data as UniformBlock>::build_layout(0);
+    assert_eq!(layout, BlockLayout::Struct {
+        members: vec![
+            ("scale".to_owned(), BlockLayout::BasicType {
+                ty: UniformType::Float,
+                offset_in_buffer: 0,
+            }),
+            ("values".to_owned(), BlockLayout::DynamicSizedArray {
+                content: Box::new(BlockLayout::BasicType {
+                    ty: UniformType::FloatVec4,
+                    offset_in_buffer: 16,
+                }),
+            }),
+        ],
+    });
+}
+
+#[test]
+fn uniforms_visit() {
+    #[derive(glium::Uniforms)]
+    struct Camera {
+        view: [[f32; 4]; 4],
+    }
+
+    #[derive(glium::Uniforms)]
+    struct MyUniforms {
+        #[glium(flatten)]
+        camera: Camera,
+        #[glium(name = "lightsCount")]
+        count: u32,
+        #[glium(skip)]
+        _frame: u64,
+    }
+
+    let uniforms = MyUniforms {
+        camera: Camera { view: [[0.0; 4]; 4] },
+        count: 3,
+        _frame: 0,
+    };
+
+    let mut names = Vec::new();
+    uniforms.visit_values(|name, value| {
+        match (name, value) {
+            ("view", UniformValue::Mat4(_)) => (),
+            ("lightsCount", UniformValue::UnsignedInt(3)) => (),
+            _ => panic!("unexpected uniform {}", name),
+        }
+        names.push(name.to_owned());
+    });
+
+    assert_eq!(names, vec!["view", "lightsCount"]);
+}
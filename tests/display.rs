@@ -149,3 +149,216 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+#[test]
+fn resource_recovery_enabled() {
+    let display = support::build_display();
+
+    let lost = std::rc::Rc::new(std::cell::Cell::new(false));
+    {
+        let lost = lost.clone();
+        display.enable_resource_recovery(move |_| lost.set(true));
+    }
+
+    // resources must keep working normally while they are tracked
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    drop((vb, ib, program, texture));
+
+    assert!(!lost.get());
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(not(feature = "test_headless"))]
+fn resource_recovery_after_context_loss() {
+    use glium::backend::{LostResource, RecoveryStatus, ResourceKind};
+    use glium::GlObject;
+
+    let display = support::build_display();
+
+    let lost = std::rc::Rc::new(std::cell::RefCell::new(None));
+    {
+        let lost = lost.clone();
+        display.enable_resource_recovery(move |resources: &[LostResource]| {
+            *lost.borrow_mut() = Some(resources.to_vec());
+        });
+    }
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+
+    display.mark_context_lost();
+    support::rebuild_display(&display);
+    assert!(!display.is_context_lost());
+
+    // only the content of the texture is unknown to glium
+    let lost = lost.borrow_mut().take().unwrap();
+    assert_eq!(lost, vec![LostResource {
+        kind: ResourceKind::Texture,
+        id: texture.get_id(),
+        status: RecoveryStatus::ContentLost,
+    }]);
+
+    assert_eq!(buffer.read().unwrap(), [1, 2, 3, 4]);
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn resources_report() {
+    use glium::backend::ResourceKind;
+
+    let display = support::build_display();
+    let before = display.get_resources_report();
+
+    let buffer = glium::buffer::Buffer::<[u8]>::empty_unsized(&display,
+                                                    glium::buffer::BufferType::ArrayBuffer, 256,
+                                                    glium::buffer::BufferMode::Default).unwrap();
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                                glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                glium::texture::MipmapsOption::NoMipmap,
+                                                16, 16).unwrap();
+
+    let report = display.get_resources_report();
+    assert_eq!(report.get_count_of(ResourceKind::Buffer),
+               before.get_count_of(ResourceKind::Buffer) + 1);
+    assert_eq!(report.get_count_of(ResourceKind::Texture),
+               before.get_count_of(ResourceKind::Texture) + 1);
+    assert_eq!(report.get_total_size_of(ResourceKind::Buffer),
+               before.get_total_size_of(ResourceKind::Buffer) + 256);
+    assert_eq!(report.get_total_size_of(ResourceKind::Texture),
+               before.get_total_size_of(ResourceKind::Texture) + 16 * 16 * 4);
+
+    drop((buffer, texture));
+
+    let after = display.get_resources_report();
+    assert_eq!(after.get_count_of(ResourceKind::Buffer), before.get_count_of(ResourceKind::Buffer));
+    assert_eq!(after.get_count_of(ResourceKind::Texture), before.get_count_of(ResourceKind::Texture));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn object_labels() {
+    use glium::backend::ResourceKind;
+
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::<[u8]>::empty_unsized(&display,
+                                                    glium::buffer::BufferType::ArrayBuffer, 16,
+                                                    glium::buffer::BufferMode::Default).unwrap();
+    let texture = support::build_renderable_texture(&display);
+
+    match (buffer.set_label("my buffer"), texture.set_label("my texture")) {
+        (Ok(()), Ok(())) => (),
+        (Err(_), Err(_)) => (),
+        _ => panic!("labels are supported for some objects only"),
+    }
+
+    let report = display.get_resources_report();
+    assert!(report.resources.iter().any(|r| r.kind == ResourceKind::Buffer &&
+                                             r.label.as_ref().map(|l| &l[..]) == Some("my buffer")));
+    assert!(report.resources.iter().any(|r| r.kind == ResourceKind::Texture &&
+                                             r.label.as_ref().map(|l| &l[..]) == Some("my texture")));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_framebuffer_and_timestamp_labels() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        p => p.unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                                            [("color", &texture)].iter().cloned())
+                                                            .unwrap();
+
+    if framebuffer.set_label("my framebuffer").is_err() {
+        return;
+    }
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    if let Some(query) = glium::debug::TimestampQuery::new(&display) {
+        query.set_label("my query").unwrap();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn debug_group() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    {
+        let _outer = glium::debug::DebugGroup::new(&display, "outer");
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        let _inner = glium::debug::DebugGroup::new(&display, "inner");
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                  &Default::default()).unwrap();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
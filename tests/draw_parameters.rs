@@ -31,6 +31,63 @@ fn color_mask() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn logic_op_xor() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        logic_op: Some(glium::draw_parameters::LogicOp::Xor),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 1.0, 0.0, 1.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::LogicOpNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 0));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_mask_and_sample_shading() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        sample_mask: Some(0x1),
+        min_sample_shading: Some(1.0),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::SampleMaskNotSupported) => return,
+        Err(glium::DrawError::SampleShadingNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    // the texture isn't multisampled, so the sample mask doesn't discard anything
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn viewport_too_large() {
     let display = support::build_display();
@@ -217,6 +274,78 @@ fn viewport() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn viewport_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 410
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 410
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        Some("
+            #version 410
+
+            layout(triangles) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; ++i) {
+                    gl_Position = gl_in[i].gl_Position;
+                    gl_ViewportIndex = 1;
+                    EmitVertex();
+                }
+                EndPrimitive();
+            }
+        "))
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let viewports = [
+        glium::Rect { left: 0, bottom: 0, width: 512, height: 1024 },
+        glium::Rect { left: 512, bottom: 0, width: 512, height: 1024 },
+    ];
+
+    let params = glium::DrawParameters {
+        viewports: &viewports,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::ViewportArrayNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+    assert_eq!(data[0][511], (0, 0, 0, 0));
+    assert_eq!(data[0][512], (255, 0, 0, 255));
+    assert_eq!(data[1023][1023], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn dont_draw_primitives() {
     let display = support::build_display();
@@ -913,6 +1042,130 @@ fn provoking_vertex_first() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn clip_depth_zero_to_one() {
+    let display = support::build_display();
+
+    // the left half of the quad has a negative depth, the right half a positive depth
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: (f32, f32, f32),
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: (-1.0, 1.0, -0.5) },
+            Vertex { position: (0.0, 1.0, -0.5) },
+            Vertex { position: (-1.0, -1.0, -0.5) },
+            Vertex { position: (0.0, -1.0, -0.5) },
+            Vertex { position: (0.0, 1.0, 0.5) },
+            Vertex { position: (1.0, 1.0, 0.5) },
+            Vertex { position: (0.0, -1.0, 0.5) },
+            Vertex { position: (1.0, -1.0, 0.5) },
+        ]).unwrap()
+    };
+
+    let index_buffer = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                               &[0u16, 1, 2, 2, 1, 3, 4, 5, 6, 6, 5, 7]).unwrap();
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let res = texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+        &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth: glium::Depth {
+                clip_depth: glium::draw_parameters::ClipDepthMode::ZeroToOne,
+                .. Default::default()
+            },
+            .. Default::default()
+        });
+
+    match res {
+        Ok(_) => (),
+        Err(glium::DrawError::ClipControlNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[512][256], (0, 0, 0, 0));
+    assert_eq!(data[512][768], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_bounds_invalid() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    let res = texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth: glium::Depth {
+                bounds: Some((0.8, 0.2)),
+                .. Default::default()
+            },
+            .. Default::default()
+        });
+
+    match res {
+        Err(glium::DrawError::InvalidDepthBounds) => (),
+        e => panic!("{:?}", e),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_bounds_nan() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    let res = texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth: glium::Depth {
+                bounds: Some((f32::NAN, 0.5)),
+                .. Default::default()
+            },
+            .. Default::default()
+        });
+
+    match res {
+        Err(glium::DrawError::InvalidDepthBounds) => (),
+        e => panic!("{:?}", e),
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn depth_clamp_all() {
     let display = support::build_display();
@@ -254,6 +254,96 @@ fn multioutput() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn multioutput_per_draw_buffer_blend_and_color_mask() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(0.0, 1.0, 0.0, 1.0);
+                color2 = vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color1.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color2.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    // the first attachment is additively blended, the second one keeps its red channel
+    let additive = glium::Blend {
+        color: glium::BlendingFunction::Addition {
+            source: glium::LinearBlendingFactor::One,
+            destination: glium::LinearBlendingFactor::One,
+        },
+        alpha: glium::BlendingFunction::AlwaysReplace,
+        constant_value: (0.0, 0.0, 0.0, 0.0),
+    };
+
+    let params = glium::DrawParameters {
+        draw_buffers_blend: &[additive],
+        draw_buffers_color_mask: &[(true, true, true, true), (false, true, true, true)],
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::IndexedBlendingNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[0][0], (255, 255, 0, 255));
+    assert_eq!(read_back1[127][127], (255, 255, 0, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[0][0], (255, 255, 0, 255));
+    assert_eq!(read_back2[127][127], (255, 255, 0, 255));
+
+    // drawing again without per-draw-buffer parameters must overwrite both attachments
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[0][0], (0, 255, 0, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn array_level() {
     let display = support::build_display();
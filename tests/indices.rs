@@ -331,6 +331,50 @@ fn indexbuffer_slice_draw() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn growable_buffers_draw() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let mut vb = glium::vertex::GrowableVertexBuffer::new(&display).unwrap();
+    let mut indices = index::GrowableIndexBuffer::new(&display, PrimitiveType::TrianglesList)
+                                                 .unwrap();
+
+    // the first triangle is uploaded before the buffers grow
+    vb.extend_from_slice(&[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] },
+    ]);
+    indices.extend_from_slice(&[0u16, 1, 2]);
+
+    vb.push(Vertex { position: [1.0, -1.0] });
+    for &i in &[2u16, 1, 3] {
+        indices.push(i);
+    }
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    // only the first triangle remains
+    indices.truncate(3);
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn multidraw_array() {
     let display = support::build_display();
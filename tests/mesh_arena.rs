@@ -0,0 +1,175 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::buffer::{CopyError, DefragmentError, MeshArena};
+use glium::index::PrimitiveType;
+
+mod support;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Vertex {
+    position: [f32; 2],
+}
+
+implement_vertex!(Vertex, position);
+
+fn quad(x: f32, y: f32, size: f32) -> [Vertex; 4] {
+    [
+        Vertex { position: [x, y + size] }, Vertex { position: [x + size, y + size] },
+        Vertex { position: [x, y] }, Vertex { position: [x + size, y] },
+    ]
+}
+
+#[test]
+fn insert_remove() {
+    let display = support::build_display();
+
+    let mut arena = MeshArena::<Vertex, u16>::new(&display, 1024).unwrap();
+    assert!(arena.is_empty());
+
+    let a = arena.insert(&quad(0.0, 0.0, 1.0), PrimitiveType::TriangleStrip, &[0, 1, 2, 3]).unwrap();
+    let b = arena.insert(&quad(1.0, 1.0, 1.0), PrimitiveType::TriangleStrip, &[3, 2, 1]).unwrap();
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.get_free_space(), 1024 - 2 * 4 * 8 - 7 * 2);
+
+    assert_eq!(arena.vertices(b).unwrap().len(), 4);
+    assert_eq!(arena.indices(b).unwrap().len(), 3);
+
+    assert!(arena.remove(a));
+    assert!(!arena.remove(a));
+    assert!(arena.vertices(a).is_none());
+    assert!(arena.indices(a).is_none());
+    assert_eq!(arena.len(), 1);
+
+    // the slot of `a` is reused, but the old identifier stays invalid
+    let c = arena.insert(&quad(2.0, 2.0, 1.0), PrimitiveType::TriangleStrip, &[0]).unwrap();
+    assert!(c != a);
+    assert!(arena.vertices(a).is_none());
+
+    assert!(arena.remove(b));
+    assert!(arena.remove(c));
+    assert_eq!(arena.get_free_space(), 1024);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn full() {
+    let display = support::build_display();
+
+    let mut arena = MeshArena::<Vertex, u16>::new(&display, 64).unwrap();
+    arena.insert(&quad(0.0, 0.0, 1.0), PrimitiveType::TriangleStrip, &[0, 1, 2, 3]).unwrap();
+
+    let result = arena.insert(&quad(0.0, 0.0, 1.0), PrimitiveType::TriangleStrip, &[0, 1, 2, 3]);
+    assert_eq!(result, Err(glium::buffer::ArenaFullError));
+
+    // the failed insertion doesn't leak space
+    assert_eq!(arena.get_free_space(), 64 - 4 * 8 - 4 * 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn defragment() {
+    let display = support::build_display();
+
+    let mut arena = MeshArena::<Vertex, u16>::new(&display, 256).unwrap();
+
+    let meshes: Vec<_> = (0 .. 5).map(|i| {
+        arena.insert(&quad(i as f32, 0.0, 1.0), PrimitiveType::TriangleStrip, &[0, 1, 2, 3])
+             .unwrap()
+    }).collect();
+
+    arena.remove(meshes[1]);
+    arena.remove(meshes[3]);
+
+    match arena.defragment() {
+        Ok(()) => (),
+        Err(DefragmentError::CopyError(CopyError::NotSupported)) => return,
+        Err(e) => panic!("{}", e)
+    };
+
+    for &i in &[0, 2, 4] {
+        let vertices = match arena.vertices(meshes[i]).unwrap().read() {
+            Ok(r) => r,
+            Err(glium::buffer::ReadError::NotSupported) => return,
+            e => e.unwrap()
+        };
+        assert_eq!(vertices, quad(i as f32, 0.0, 1.0));
+        assert_eq!(arena.indices(meshes[i]).unwrap().read().unwrap(), [0, 1, 2, 3]);
+    }
+
+    // the free space is now contiguous
+    let big = [Vertex { position: [0.0, 0.0] }; 12];
+    arena.insert(&big, PrimitiveType::Points, &[]).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw() {
+    let display = support::build_display();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        }).unwrap();
+
+    let mut arena = MeshArena::<Vertex, u16>::new(&display, 1024).unwrap();
+
+    // each mesh covers one half of the target
+    let left = arena.insert(&quad(-1.0, -1.0, 2.0)[..], PrimitiveType::TrianglesList, &[0, 2, 3]);
+    let right = arena.insert(&quad(-1.0, -1.0, 2.0)[..], PrimitiveType::TrianglesList, &[0, 1, 3]);
+    let (left, right) = (left.unwrap(), right.unwrap());
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    for &mesh in &[left, right] {
+        texture.as_surface().draw(arena.vertices(mesh).unwrap(), &arena.indices(mesh).unwrap(),
+                                  &program, &uniform!{}, &Default::default()).unwrap();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
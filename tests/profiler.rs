@@ -0,0 +1,125 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::profiler::{FrameTimings, GpuProfiler};
+
+use std::mem;
+use std::thread;
+use std::time::Duration;
+
+mod support;
+
+/// Waits until the timings of the ended frames are available.
+fn wait_for_frames(profiler: &GpuProfiler) -> Vec<FrameTimings> {
+    for _ in 0 .. 1000 {
+        let frames = profiler.collect_frames();
+        if !frames.is_empty() {
+            return frames;
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    panic!("The timings of the frame are never available");
+}
+
+#[test]
+fn profiler_nested_scopes() {
+    let display = support::build_display();
+
+    let profiler = match GpuProfiler::new(&display) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    profiler.begin_frame();
+    {
+        let _outer = profiler.scope("outer");
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        {
+            let _inner = profiler.scope("inner");
+            texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                      &Default::default()).unwrap();
+        }
+    }
+    {
+        let _second = profiler.scope("second");
+    }
+    profiler.end_frame();
+
+    display.finish();
+
+    let frames = wait_for_frames(&profiler);
+
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].index, 0);
+    assert_eq!(frames[0].scopes.len(), 2);
+    assert_eq!(frames[0].scopes[0].name, "outer");
+    assert_eq!(frames[0].scopes[0].children.len(), 1);
+    assert_eq!(frames[0].scopes[0].children[0].name, "inner");
+    assert_eq!(frames[0].scopes[1].name, "second");
+    assert!(frames[0].scopes[0].duration <= frames[0].duration);
+
+    // frames are only returned once
+    assert!(profiler.collect_frames().is_empty());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn profiler_unterminated_scopes() {
+    let display = support::build_display();
+
+    let profiler = match GpuProfiler::new(&display) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+
+    profiler.begin_frame();
+    {
+        // dropping the outer scope first closes the inner one as well
+        let outer = profiler.scope("outer");
+        let inner = profiler.scope("inner");
+        mem::drop(outer);
+        let _after = profiler.scope("after");
+        mem::drop(inner);
+    }
+    {
+        // a leaked scope is skipped, and the scopes nested in it are moved to its parent
+        let leaked = profiler.scope("leaked");
+        {
+            let _nested = profiler.scope("nested");
+        }
+        mem::forget(leaked);
+    }
+    profiler.end_frame();
+
+    display.finish();
+    let frames = wait_for_frames(&profiler);
+
+    let names = frames[0].scopes.iter().map(|s| &s.name[..]).collect::<Vec<_>>();
+    assert_eq!(names, ["outer", "after", "nested"]);
+    assert_eq!(frames[0].scopes[0].children.len(), 1);
+    assert_eq!(frames[0].scopes[0].children[0].name, "inner");
+    assert!(frames[0].scopes[1].children.is_empty());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn profiler_scope_outside_frame() {
+    let display = support::build_display();
+
+    let profiler = match GpuProfiler::new(&display) {
+        Ok(p) => p,
+        Err(_) => panic!(),
+    };
+
+    let _scope = profiler.scope("scope");
+}
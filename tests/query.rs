@@ -309,3 +309,63 @@ fn query_to_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn pipeline_statistics() {
+    let display = support::build_display();
+
+    let primitives = match glium::draw_parameters::PrimitivesSubmittedQuery::new(&display) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+    let fragments = glium::draw_parameters::FragmentShaderInvocationsQuery::new(&display)
+                                                                                .unwrap();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let queries = [(&primitives).into(), (&fragments).into()];
+        let params = glium::DrawParameters {
+            pipeline_statistics_queries: &queries,
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    assert_eq!(primitives.get(), 2);
+    assert!(fragments.get() >= 1);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline_statistics_same_statistic_twice() {
+    let display = support::build_display();
+
+    let query1 = match glium::draw_parameters::VertexShaderInvocationsQuery::new(&display) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+    let query2 = glium::draw_parameters::VertexShaderInvocationsQuery::new(&display).unwrap();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    let queries = [(&query1).into(), (&query2).into()];
+    let params = glium::DrawParameters {
+        pipeline_statistics_queries: &queries,
+        .. Default::default()
+    };
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::WrongQueryOperation) => (),
+        e => panic!("{:?}", e)
+    }
+
+    display.assert_no_error(None);
+}
@@ -79,3 +79,151 @@ fn magnify_nearest_filtering() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn border_clamp_color() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D texture;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, vec2(2.0, 2.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (0, 0, 0)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data).unwrap();
+
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .wrap_function(glium::uniforms::SamplerWrapFunction::BorderClamp)
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                        .border_color([1.0, 0.0, 0.0, 1.0])
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sampler_parameters_reset() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D texture;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, vec2(0.51, 0.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data).unwrap();
+    let output = support::build_renderable_texture(&display);
+
+    let uniforms = uniform! {
+        texture: texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+    };
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 255, 255, 255));
+
+    // without a sampler, the texture must be sampled with its linear filtering again
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert!(data[0][0].0 > 64 && data[0][0].0 < 192);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sampler_behaviors_conflict() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D nearest;
+                uniform sampler2D linear;
+
+                void main() {
+                    gl_FragColor = texture2D(nearest, vec2(0.51, 0.0)) -
+                                   texture2D(linear, vec2(0.51, 0.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data).unwrap();
+
+    let uniforms = uniform! {
+        nearest: texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest),
+        linear: texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear),
+    };
+
+    // without sampler objects, a texture can only be sampled with one behavior per draw call
+    let output = support::build_renderable_texture(&display);
+    match output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Ok(_) => (),
+        Err(glium::DrawError::SamplerBehaviorsConflict) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert!(data[0][0].0 > 64 && data[0][0].0 < 192);
+
+    display.assert_no_error(None);
+}
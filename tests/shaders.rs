@@ -77,6 +77,111 @@ fn program_compilation_error() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn spirv_duplicate_specialization_constant() {
+    use glium::program::{ProgramCreationInput, SpecializationConstant, SpirvEntryPoint, SpirvProgram};
+
+    let display = support::build_display();
+
+    let constants = [
+        (0, SpecializationConstant::Bool(true)),
+        (0, SpecializationConstant::Float(2.0)),
+    ];
+    let entry_point = SpirvEntryPoint { binary: &[], entry_point: "main" };
+
+    let program = glium::Program::new(&display,
+        ProgramCreationInput::SpirV(SpirvProgram::from_vs_and_fs(entry_point, entry_point)
+            .specialization_constants(&constants)));
+
+    match program {
+        Err(glium::program::ProgramCreationError::SpecializationError(..)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+/// A SPIR-V module with a vertex shader `vmain` that puts a point at the center of the screen,
+/// and a fragment shader `fmain` that writes `vec4(RED, 0.0, 0.0, 1.0)`, where `RED` is the float
+/// specialization constant 0 whose default value is `0.0`.
+const SPECIALIZED_SPIRV: &[u32] = &[
+    0x07230203, 0x00010000, 0, 17, 0,
+    0x00020011, 1,                                      // OpCapability Shader
+    0x0003000E, 0, 1,                                   // OpMemoryModel Logical GLSL450
+    0x0006000F, 0, 12, 0x69616d76, 0x0000006e, 10,      // OpEntryPoint Vertex %12 "vmain" %10
+    0x0006000F, 4, 13, 0x69616d66, 0x0000006e, 11,      // OpEntryPoint Fragment %13 "fmain" %11
+    0x00030010, 13, 8,                                  // OpExecutionMode %13 OriginLowerLeft
+    0x00040047, 8, 1, 0,                                // OpDecorate %8 SpecId 0
+    0x00040047, 10, 11, 0,                              // OpDecorate %10 BuiltIn Position
+    0x00040047, 11, 30, 0,                              // OpDecorate %11 Location 0
+    0x00020013, 1,                                      // %1 = OpTypeVoid
+    0x00030021, 2, 1,                                   // %2 = OpTypeFunction %1
+    0x00030016, 3, 32,                                  // %3 = OpTypeFloat 32
+    0x00040017, 4, 3, 4,                                // %4 = OpTypeVector %3 4
+    0x00040020, 5, 3, 4,                                // %5 = OpTypePointer Output %4
+    0x0004002B, 3, 6, 0,                                // %6 = OpConstant %3 0.0
+    0x0004002B, 3, 7, 0x3f800000,                       // %7 = OpConstant %3 1.0
+    0x00040032, 3, 8, 0,                                // %8 = OpSpecConstant %3 0.0
+    0x0007002C, 4, 9, 6, 6, 6, 7,                       // %9 = OpConstantComposite %4 %6 %6 %6 %7
+    0x0004003B, 5, 10, 3,                               // %10 = OpVariable %5 Output
+    0x0004003B, 5, 11, 3,                               // %11 = OpVariable %5 Output
+    0x00050036, 1, 12, 0, 2,                            // %12 = OpFunction %1 None %2
+    0x000200F8, 14,                                     // %14 = OpLabel
+    0x0003003E, 10, 9,                                  // OpStore %10 %9
+    0x000100FD,                                         // OpReturn
+    0x00010038,                                         // OpFunctionEnd
+    0x00050036, 1, 13, 0, 2,                            // %13 = OpFunction %1 None %2
+    0x000200F8, 15,                                     // %15 = OpLabel
+    0x00070050, 4, 16, 8, 6, 6, 7,                      // %16 = OpCompositeConstruct %4 %8 %6 %6 %7
+    0x0003003E, 11, 16,                                 // OpStore %11 %16
+    0x000100FD,                                         // OpReturn
+    0x00010038,                                         // OpFunctionEnd
+];
+
+#[test]
+fn spirv_specialization_constant() {
+    use glium::program::{ProgramCreationInput, SpecializationConstant, SpirvEntryPoint, SpirvProgram};
+    use glium::Surface;
+
+    let display = support::build_display();
+
+    let binary = SPECIALIZED_SPIRV.iter().flat_map(|word| word.to_ne_bytes().to_vec())
+                                  .collect::<Vec<u8>>();
+    let vertex_shader = SpirvEntryPoint { binary: &binary, entry_point: "vmain" };
+    let fragment_shader = SpirvEntryPoint { binary: &binary, entry_point: "fmain" };
+
+    let draw = |constants: &[(u32, SpecializationConstant)]| {
+        let program = glium::Program::new(&display,
+            ProgramCreationInput::SpirV(SpirvProgram::from_vs_and_fs(vertex_shader, fragment_shader)
+                .specialization_constants(constants)));
+
+        let program = match program {
+            Err(glium::program::ProgramCreationError::CompilationNotSupported) => return None,
+            p => p.unwrap()
+        };
+
+        let texture = glium::texture::Texture2d::empty(&display, 1, 1).unwrap();
+        texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+        texture.as_surface().draw(glium::vertex::EmptyVertexAttributes { len: 1 },
+                                  &glium::index::NoIndices(glium::index::PrimitiveType::Points),
+                                  &program, &uniform!{}, &Default::default()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        Some(data[0][0])
+    };
+
+    let default = match draw(&[]) {
+        Some(color) => color,
+        None => return
+    };
+    assert_eq!(default, (0, 0, 0, 255));
+
+    let specialized = draw(&[(0, SpecializationConstant::Float(1.0))]).unwrap();
+    assert_eq!(specialized, (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 // This test is disabled because some OpenGL drivers don't catch
 // the linking error (even though they are supposed to)
 #[test]
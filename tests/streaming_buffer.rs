@@ -0,0 +1,164 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::buffer::{StreamingBuffer, StreamingBufferFullError};
+
+mod support;
+
+#[test]
+fn write_read() {
+    let display = support::build_display();
+
+    let stream = StreamingBuffer::new(&display, 1024).unwrap();
+    let a = stream.write(&[1u32, 2, 3][..]).unwrap();
+    let b = stream.write(&5u8).unwrap();
+
+    let a = match a.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(a, [1, 2, 3]);
+    assert_eq!(b.read().unwrap(), 5);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn wrap_around() {
+    let display = support::build_display();
+
+    let stream = StreamingBuffer::new(&display, 1024).unwrap();
+    let data = [7u8; 600];
+
+    // the second write doesn't fit after the first one and must restart at the beginning
+    for _ in 0 .. 3 {
+        let slice = stream.write(&data[..]).unwrap();
+
+        match slice.read() {
+            Ok(r) => assert_eq!(r, &data[..]),
+            Err(glium::buffer::ReadError::NotSupported) => return,
+            e => { e.unwrap(); }
+        };
+
+        stream.end_frame();
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_full() {
+    let display = support::build_display();
+
+    let stream = StreamingBuffer::new(&display, 1024).unwrap();
+    let first = [1u8; 600];
+    let second = [2u8; 600];
+
+    // both slices are written before any draw, so the second one can't reuse the first one
+    let a = stream.write(&first[..]).unwrap();
+    assert_eq!(stream.write(&second[..]).unwrap_err(), StreamingBufferFullError);
+
+    match a.read() {
+        Ok(r) => assert_eq!(r, &first[..]),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => { e.unwrap(); }
+    };
+
+    stream.end_frame();
+    let b = stream.write(&second[..]).unwrap();
+    assert_eq!(b.read().unwrap(), &second[..]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn too_large() {
+    let display = support::build_display();
+
+    let stream = StreamingBuffer::new(&display, 16).unwrap();
+    let _ = stream.write(&[0u8; 32][..]);
+}
+
+#[test]
+fn draw() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: (f32, f32, f32),
+    }
+
+    implement_uniform_block!(Data, color);
+
+    let stream = StreamingBuffer::new(&display, 4096).unwrap();
+
+    // the regions are recycled many times while the previous draws may still be in progress
+    let texture = support::build_renderable_texture(&display);
+    let mut target = texture.as_surface();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    for i in 0 .. 200 {
+        let color = if i == 199 { (1.0, 1.0, 1.0) } else { (rand::random(), 0.0, 0.0) };
+
+        let vertices = stream.write_vertices(&[
+            Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+            Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+        ]).unwrap();
+        let indices = stream.write_indices(glium::index::PrimitiveType::TriangleStrip,
+                                           &[0u16, 1, 2, 3]).unwrap();
+        let block = stream.write(&Data { color }).unwrap();
+
+        target.draw(vertices, &indices, &program, &uniform!{ MyBlock: block },
+                    &Default::default()).unwrap();
+        stream.end_frame();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -24,21 +24,49 @@ pub fn build_display() -> glium::Display {
 }
 
 /// Builds a headless display for tests.
+///
+/// Renders in software through OSMesa, so that the tests can run without a GPU.
 #[cfg(feature = "test_headless")]
 pub fn build_display() -> glium::HeadlessRenderer {
     let version = parse_version();
-    let hrb = glutin::HeadlessRendererBuilder::new(1024, 768)
+    let cb = glutin::ContextBuilder::new()
+        .with_gl_debug_flag(true)
+        .with_gl(version);
+    glium::HeadlessRenderer::osmesa(cb, (1024, 768)).unwrap()
+}
+
+/// Builds a headless context that shares its buffers and textures with `display`.
+///
+/// Returns `None` if the backend can't create such a context.
+#[cfg(not(feature = "test_headless"))]
+pub fn build_shared_context(display: &glium::Display) -> Option<glium::HeadlessRenderer> {
+    let version = parse_version();
+    let event_loop = glutin::event_loop::EventLoop::new();
+    let gl_window = display.gl_window();
+    let cb = glutin::ContextBuilder::new()
         .with_gl_debug_flag(true)
         .with_gl(version)
-        .build()
-        .unwrap();
-    glium::HeadlessRenderer::new(hrb).unwrap()
+        .with_shared_lists(gl_window.context());
+    let context = cb.build_headless(&event_loop, glutin::dpi::PhysicalSize::new(1, 1)).ok()?;
+    unsafe {
+        glium::HeadlessRenderer::new_shared(context, display.get_context().get_share_group(),
+                                            Default::default()).ok()
+    }
+}
+
+/// Builds a headless context that shares its buffers and textures with `display`.
+///
+/// OSMesa contexts can't share their lists, so this always returns `None`.
+#[cfg(feature = "test_headless")]
+pub fn build_shared_context(_: &glium::HeadlessRenderer) -> Option<glium::HeadlessRenderer> {
+    None
 }
 
 /// Rebuilds an existing display.
 ///
 /// In real applications this is used for things such as switching to fullscreen. Some things are
 /// invalidated during a rebuild, and this has to be handled by glium.
+#[cfg(not(feature = "test_headless"))]
 pub fn rebuild_display(display: &glium::Display) {
     let version = parse_version();
     let event_loop = glutin::event_loop::EventLoop::new();
@@ -0,0 +1,274 @@
+#[macro_use]
+extern crate glium;
+
+use glium::texture::container::{Container, ContainerError, ContainerFormat, ContainerTexture};
+use glium::texture::{CompressedFormat, CompressedSrgbFormat, CubeLayer};
+
+mod support;
+
+fn build_ktx2(vk_format: u32, width: u32, height: u32, layers: u32, faces: u32,
+              levels: &[Vec<u8>]) -> Vec<u8>
+{
+    let mut header = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+    for &value in &[vk_format, 1, width, height, 0, layers, faces, levels.len() as u32, 0] {
+        header.extend_from_slice(&value.to_le_bytes());
+    }
+    header.resize(80, 0);
+
+    let mut offset = 80 + levels.len() * 24;
+    let mut data = Vec::new();
+    for level in levels {
+        header.extend_from_slice(&(offset as u64).to_le_bytes());
+        header.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(level.len() as u64).to_le_bytes());
+        data.extend_from_slice(level);
+        offset += level.len();
+    }
+
+    header.extend_from_slice(&data);
+    header
+}
+
+fn build_dds(four_cc: &[u8; 4], width: u32, height: u32, mipmaps: u32, caps2: u32,
+             dx10: Option<(u32, u32, u32)>, data: &[u8]) -> Vec<u8>
+{
+    let mut file = vec![0; 128];
+    file[0 .. 4].copy_from_slice(b"DDS ");
+    file[4 .. 8].copy_from_slice(&124u32.to_le_bytes());
+    file[8 .. 12].copy_from_slice(&(0x1007u32 | 0x20000).to_le_bytes());
+    file[12 .. 16].copy_from_slice(&height.to_le_bytes());
+    file[16 .. 20].copy_from_slice(&width.to_le_bytes());
+    file[28 .. 32].copy_from_slice(&mipmaps.to_le_bytes());
+    file[76 .. 80].copy_from_slice(&32u32.to_le_bytes());
+    file[80 .. 84].copy_from_slice(&4u32.to_le_bytes());
+    file[84 .. 88].copy_from_slice(four_cc);
+    file[112 .. 116].copy_from_slice(&caps2.to_le_bytes());
+
+    if let Some((dxgi_format, misc_flag, array_size)) = dx10 {
+        for &value in &[dxgi_format, 3, misc_flag, array_size, 0] {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    file.extend_from_slice(data);
+    file
+}
+
+/// An 8x8 BC1 texture with its full mipmaps chain.
+fn ktx2_bc1_mipmaps() -> Vec<u8> {
+    build_ktx2(131, 8, 8, 0, 1, &[vec![0; 32], vec![1; 8], vec![2; 8], vec![3; 8]])
+}
+
+/// A 4x4 DXT1 cubemap whose faces are filled with their index.
+fn dds_dxt1_cubemap() -> Vec<u8> {
+    let data = (0 .. 6).flat_map(|face| vec![face as u8; 8]).collect::<Vec<_>>();
+    build_dds(b"DXT1", 4, 4, 1, 0x200 | 0xFC00, None, &data)
+}
+
+#[test]
+fn ktx2_mipmaps() {
+    let data = ktx2_bc1_mipmaps();
+    let container = Container::parse(&data).unwrap();
+
+    assert_eq!(container.get_format(),
+               ContainerFormat::Compressed(CompressedFormat::S3tcDxt1NoAlpha));
+    assert_eq!(container.get_width(), 8);
+    assert_eq!(container.get_height(), 8);
+    assert_eq!(container.get_array_size(), None);
+    assert!(!container.is_cubemap());
+    assert_eq!(container.get_mipmap_levels(), 4);
+
+    assert_eq!(container.get_image(0, 0, None).unwrap().len(), 32);
+    assert_eq!(container.get_image(2, 0, None).unwrap(), &[2; 8][..]);
+    assert!(container.get_image(4, 0, None).is_none());
+    assert!(container.get_image(0, 1, None).is_none());
+    assert!(container.get_image(0, 0, Some(CubeLayer::PositiveX)).is_none());
+}
+
+#[test]
+fn ktx2_cubemap_array() {
+    let level = (0 .. 12).flat_map(|image| vec![image as u8; 16]).collect::<Vec<_>>();
+    let data = build_ktx2(158, 4, 4, 2, 6, &[level]);
+    let container = Container::parse_ktx2(&data).unwrap();
+
+    assert_eq!(container.get_format(),
+               ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Astc4x4));
+    assert_eq!(container.get_array_size(), Some(2));
+    assert!(container.is_cubemap());
+    assert_eq!(container.get_image(0, 1, Some(CubeLayer::NegativeX)).unwrap(), &[7; 16][..]);
+    assert!(container.get_image(0, 0, None).is_none());
+}
+
+#[test]
+fn ktx2_truncated() {
+    let mut data = ktx2_bc1_mipmaps();
+    data.pop();
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn ktx2_huge_dimensions() {
+    let data = build_ktx2(131, u32::MAX, 4, 0, 1, &[vec![0; 8]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn ktx2_wrong_level_size() {
+    let data = build_ktx2(131, 8, 8, 0, 1, &[vec![0; 32], vec![0; 16]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn ktx2_supercompressed() {
+    let mut data = ktx2_bc1_mipmaps();
+    data[44] = 1;
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedFeature);
+}
+
+#[test]
+fn ktx2_unsupported_format() {
+    // VK_FORMAT_R8G8B8A8_UNORM
+    let data = build_ktx2(37, 1, 1, 0, 1, &[vec![0; 4]]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::UnsupportedFormat);
+}
+
+#[test]
+fn dds_mipmaps() {
+    let data = build_dds(b"DXT5", 8, 4, 2, 0, None, &[0; 32 + 16]);
+    let container = Container::parse(&data).unwrap();
+
+    assert_eq!(container.get_format(),
+               ContainerFormat::Compressed(CompressedFormat::S3tcDxt5Alpha));
+    assert_eq!(container.get_width(), 8);
+    assert_eq!(container.get_height(), 4);
+    assert_eq!(container.get_mipmap_levels(), 2);
+    assert_eq!(container.get_image(0, 0, None).unwrap().len(), 32);
+    assert_eq!(container.get_image(1, 0, None).unwrap().len(), 16);
+}
+
+#[test]
+fn dds_cubemap() {
+    let data = dds_dxt1_cubemap();
+    let container = Container::parse_dds(&data).unwrap();
+
+    assert!(container.is_cubemap());
+    assert_eq!(container.get_array_size(), None);
+    assert_eq!(container.get_image(0, 0, Some(CubeLayer::NegativeY)).unwrap(), &[3; 8][..]);
+    assert_eq!(container.get_image(0, 0, Some(CubeLayer::NegativeZ)).unwrap(), &[5; 8][..]);
+}
+
+#[test]
+fn dds_dx10_array() {
+    // each layer contains a 4x4 and a 2x2 image
+    let data = (0 .. 6).flat_map(|image| vec![image as u8; 16]).collect::<Vec<_>>();
+    let data = build_dds(b"DX10", 4, 4, 2, 0, Some((99, 0, 3)), &data);
+    let container = Container::parse(&data).unwrap();
+
+    assert_eq!(container.get_format(),
+               ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Bptc));
+    assert_eq!(container.get_array_size(), Some(3));
+    assert_eq!(container.get_mipmap_levels(), 2);
+    assert_eq!(container.get_image(0, 1, None).unwrap(), &[2; 16][..]);
+    assert_eq!(container.get_image(1, 2, None).unwrap(), &[5; 16][..]);
+}
+
+#[test]
+fn dds_truncated() {
+    let data = build_dds(b"DXT1", 8, 8, 1, 0, None, &[0; 24]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn dds_huge_dimensions() {
+    let data = build_dds(b"DXT1", u32::MAX, 4, 1, 0, None, &[0; 8]);
+    assert_eq!(Container::parse(&data).unwrap_err(), ContainerError::InvalidData);
+}
+
+#[test]
+fn unknown_container() {
+    assert_eq!(Container::parse(b"\x89PNG\r\n\x1a\n").unwrap_err(),
+               ContainerError::UnknownContainer);
+}
+
+#[test]
+fn load_ktx2_mipmaps() {
+    let display = support::build_display();
+
+    if !CompressedFormat::S3tcDxt1NoAlpha.is_supported(&display) {
+        return;
+    }
+
+    let texture = glium::texture::container::load(&display, &ktx2_bc1_mipmaps()).unwrap();
+
+    match texture {
+        ContainerTexture::Texture2d(_) => (),
+        _ => panic!(),
+    }
+
+    assert_eq!(texture.get_width(), 8);
+    assert_eq!(texture.get_height(), Some(8));
+    assert_eq!(texture.get_mipmap_levels(), 4);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_ktx2_partial_mipmaps() {
+    let display = support::build_display();
+
+    if !CompressedFormat::S3tcDxt1NoAlpha.is_supported(&display) {
+        return;
+    }
+
+    let data = build_ktx2(131, 8, 8, 0, 1, &[vec![0; 32], vec![0; 8]]);
+    let texture = glium::texture::container::load(&display, &data).unwrap();
+    assert_eq!(texture.get_mipmap_levels(), 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_dds_cubemap() {
+    let display = support::build_display();
+
+    if !CompressedFormat::S3tcDxt1Alpha.is_supported(&display) ||
+       !glium::texture::is_cubemaps_supported(&display)
+    {
+        return;
+    }
+
+    let texture = glium::texture::container::load(&display, &dds_dxt1_cubemap()).unwrap();
+
+    match texture {
+        ContainerTexture::Cubemap(_) => (),
+        _ => panic!(),
+    }
+
+    assert_eq!(texture.get_width(), 4);
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn load_dds_array() {
+    let display = support::build_display();
+
+    if !CompressedFormat::S3tcDxt5Alpha.is_supported(&display) ||
+       !glium::texture::is_texture_2d_array_supported(&display)
+    {
+        return;
+    }
+
+    let data = build_dds(b"DX10", 4, 4, 1, 0, Some((77, 0, 2)), &[0; 32]);
+    let texture = glium::texture::container::load(&display, &data).unwrap();
+
+    match texture {
+        ContainerTexture::Texture2dArray(_) => (),
+        _ => panic!(),
+    }
+
+    assert_eq!(texture.get_array_size(), Some(2));
+
+    display.assert_no_error(None);
+}
@@ -177,6 +177,52 @@ fn compressed_texture_2d_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn compressed_texture_2d_etc2_creation() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedFormat::Etc2Rgb8;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // 2x2 blocks of 8 bytes
+    let data = vec![0u8; 32];
+    let texture = glium::texture::CompressedTexture2d::with_compressed_data(&display, &data, 8, 8,
+                        format, glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    assert_eq!(texture.get_width(), 8);
+    assert_eq!(texture.get_height(), Some(8));
+
+    if let Some((read_format, read_data)) = texture.read_compressed_data() {
+        assert_eq!(read_format, format);
+        assert_eq!(read_data.len(), data.len());
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_astc_creation() {
+    let display = support::build_display();
+
+    let format = glium::texture::CompressedSrgbFormat::Astc6x6;
+    if !format.is_supported(&display) {
+        return;
+    }
+
+    // a 10x10 image is rounded up to 2x2 blocks of 16 bytes
+    let data = vec![0u8; 64];
+    let texture = glium::texture::CompressedSrgbTexture2d::with_compressed_data(&display, &data,
+                        10, 10, format, glium::texture::CompressedMipmapsOption::NoMipmap)
+                        .unwrap();
+
+    assert_eq!(texture.get_width(), 10);
+    assert_eq!(texture.get_height(), Some(10));
+
+    display.assert_no_error(None);
+}
+
 macro_rules! empty_texture_test {
     ($test_name:ident, $tex_ty:ident, [$($dims:expr),+],
      $w:expr, $h:expr, $d:expr, $s:expr) =>
@@ -408,3 +454,45 @@ fn upload_from_pixel_buffer_inverted() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_2d_array_layer_view() {
+    use glium::texture::{TextureViewCreationError, UncompressedFloatFormat};
+
+    let display = support::build_display();
+
+    let array = glium::texture::Texture2dArray::new(&display, vec![
+        vec![vec![(255u8, 0u8, 0u8, 255u8)]],
+        vec![vec![(0u8, 255u8, 0u8, 255u8)]],
+    ]).unwrap();
+
+    let view = match glium::texture::Texture2d::new_view(&array, UncompressedFloatFormat::U8U8U8U8,
+                                                         0 .. 1, 1 .. 2)
+    {
+        Ok(v) => v,
+        Err(TextureViewCreationError::NotSupported) => return,
+        Err(TextureViewCreationError::MutableStorage) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(view.dimensions(), (1, 1));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = view.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    match glium::texture::Texture2d::new_view(&array, UncompressedFloatFormat::U8U8U8U8, 0 .. 1,
+                                              1 .. 3)
+    {
+        Err(TextureViewCreationError::InvalidLayers) => (),
+        r => panic!("{:?}", r),
+    };
+
+    match glium::texture::Texture2d::new_view(&array, UncompressedFloatFormat::U16U16U16U16,
+                                              0 .. 1, 0 .. 1)
+    {
+        Err(TextureViewCreationError::IncompatibleFormat) => (),
+        r => panic!("{:?}", r),
+    };
+
+    display.assert_no_error(None);
+}
@@ -134,3 +134,78 @@ read_texture_test!(read_unsignedtexture2darray, UnsignedTexture2dArray, (u8, u8,
 read_texture_test!(read_unsignedtexture3d, UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn texture_2d_transfer_same_share_group() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]).unwrap();
+
+    let transfer = texture.into_transfer().unwrap();
+    assert_eq!(transfer.dimensions(),
+               glium::texture::Dimensions::Texture2d { width: 2, height: 2 });
+
+    // the transfer is meant to be sent to another thread
+    let transfer = std::thread::spawn(move || transfer).join().unwrap();
+
+    let texture = glium::texture::Texture2d::from_transfer(&display, transfer).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_transfer_shared_context() {
+    let display = support::build_display();
+    let shared = match support::build_shared_context(&display) {
+        Some(c) => c,
+        None => return
+    };
+
+    let texture = glium::texture::Texture2d::new(&shared, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]).unwrap();
+
+    let transfer = texture.into_transfer().unwrap();
+    let texture = glium::texture::Texture2d::from_transfer(&display, transfer).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+    shared.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_transfer_other_share_group() {
+    let display = support::build_display();
+    let other = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]).unwrap();
+
+    let transfer = texture.into_transfer().unwrap();
+    let transfer = match glium::texture::Texture2d::from_transfer(&other, transfer) {
+        Err(transfer) => transfer,
+        Ok(_) => panic!()
+    };
+
+    let texture = glium::texture::Texture2d::from_transfer(&display, transfer).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+    other.assert_no_error(None);
+}
@@ -130,3 +130,56 @@ fn bindless_texture() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_swizzle() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(255, 0, 0, 0), (255, 0, 0, 0)],
+        vec![(255, 0, 0, 0), (255, 0, 0, 0u8)],
+    ]).unwrap();
+
+    match texture.set_swizzle(glium::texture::Swizzle::grayscale()) {
+        Ok(_) => (),
+        Err(glium::texture::SwizzleNotSupportedError) => return,
+    };
+
+    assert_eq!(texture.get_swizzle(), glium::texture::Swizzle::grayscale());
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
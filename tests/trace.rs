@@ -0,0 +1,87 @@
+#![cfg(feature = "trace")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::glutin;
+use glium::backend::glutin::headless::GlutinBackend;
+use glium::texture::{Dimensions, MipmapsOption, UncompressedFloatFormat};
+use glium::trace::Replayer;
+use glium::{GlObject, Surface};
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+mod support;
+
+/// A trace that stays accessible after it has been given to a context.
+#[derive(Clone, Default)]
+struct SharedTrace(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedTrace {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn build_headless_context() -> glutin::Context<glutin::NotCurrent> {
+    let event_loop = glutin::event_loop::EventLoop::new();
+    glutin::ContextBuilder::new()
+        .build_headless(&event_loop, glutin::dpi::PhysicalSize::new(1024, 768))
+        .unwrap()
+}
+
+#[test]
+fn replay_draw() {
+    let trace = SharedTrace::default();
+
+    // recording a draw on a texture
+    let (texture_id, data) = {
+        let display = glium::HeadlessRenderer::with_trace(build_headless_context(),
+                                                          Default::default(),
+                                                          trace.clone()).unwrap();
+
+        let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                  &Default::default()).unwrap();
+
+        display.assert_no_error(None);
+        display.flush_trace().unwrap();
+
+        // the destruction of the resources is recorded too, and must not be replayed
+        let data = trace.0.borrow().clone();
+        (texture.get_id(), data)
+    };
+
+    let backend = Rc::new(unsafe { GlutinBackend::new(build_headless_context()) });
+    let mut replayer = unsafe { Replayer::new(backend.clone(), &data[..]) }.unwrap();
+    unsafe { replayer.replay_all() }.unwrap();
+
+    // checking the content of the texture in the context of the replay
+    let context = unsafe {
+        glium::backend::Context::new(backend, true, Default::default())
+    }.unwrap();
+
+    let texture = unsafe {
+        glium::Texture2d::from_id(&context, UncompressedFloatFormat::U8U8U8U8, texture_id, false,
+                                  MipmapsOption::NoMipmap,
+                                  Dimensions::Texture2d { width: 1024, height: 1024 })
+    };
+
+    let pixels: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in pixels.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    context.assert_no_error(None);
+}
@@ -337,3 +337,141 @@ fn empty_uniform_buffer() {
 
     display.assert_no_error(None);
 }
+
+fn build_block_array_program<F: ?Sized>(display: &F, length: usize) -> Option<glium::Program>
+    where F: glium::backend::Facade
+{
+    let fragment = format!("
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {{
+                vec4 colors[{}];
+            }};
+
+            void main() {{
+                gl_FragColor = colors[0];
+            }}
+        ", length);
+
+    glium::Program::from_source(display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        &fragment,
+        None).ok()
+}
+
+#[test]
+fn block_slice_with_offset() {
+    use glium::CapabilitiesSource;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match build_block_array_program(&display, 1) {
+        Some(p) => p,
+        None => return
+    };
+
+    let alignment = display.get_capabilities().uniform_buffer_offset_alignment;
+    let index = std::cmp::max(alignment as usize, 16) / 16;
+
+    let mut data = vec![[0.0f32, 0.0, 0.0, 1.0]; index + 1];
+    data[index] = [1.0, 1.0, 0.0, 1.0];
+    let buffer = glium::buffer::Buffer::new(&display, &data[..],
+                                            glium::buffer::BufferType::UniformBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+
+    let uniforms = uniform!{
+        MyBlock: buffer.slice(index .. index + 1).unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn block_slice_misaligned() {
+    use glium::CapabilitiesSource;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_block_array_program(&display, 1) {
+        Some(p) => p,
+        None => return
+    };
+
+    // an offset of 16 bytes is valid if the backend has no stricter requirement
+    if display.get_capabilities().uniform_buffer_offset_alignment <= 16 {
+        return;
+    }
+
+    let buffer = glium::buffer::Buffer::new(&display, &[[0.0f32; 4]; 2][..],
+                                            glium::buffer::BufferType::UniformBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+
+    let uniforms = uniform!{
+        MyBlock: buffer.slice(1 .. 2).unwrap()
+    };
+
+    let mut target = display.draw();
+    match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockOffsetNotAligned { ref name, .. })
+            if name == &"MyBlock" => (),
+        a => panic!("{:?}", a)
+    }
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn block_slice_too_small() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match build_block_array_program(&display, 4) {
+        Some(p) => p,
+        None => return
+    };
+
+    let buffer = glium::buffer::Buffer::new(&display, &[[0.0f32; 4]; 4][..],
+                                            glium::buffer::BufferType::UniformBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+
+    let uniforms = uniform!{
+        MyBlock: buffer.slice(0 .. 1).unwrap()
+    };
+
+    let mut target = display.draw();
+    match target.draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformBlockBufferTooSmall { ref name, expected, obtained })
+            if name == &"MyBlock" => {
+            assert_eq!(expected, 64);
+            assert_eq!(obtained, 16);
+        },
+        a => panic!("{:?}", a)
+    }
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
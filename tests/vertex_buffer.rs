@@ -88,3 +88,48 @@ fn transform_feedback() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn growable_vertex_buffer() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex {
+        field: [u32; 2],
+    }
+
+    implement_vertex!(Vertex, field);
+
+    let mut vb = glium::vertex::GrowableVertexBuffer::with_capacity(&display, 2).unwrap();
+    assert_eq!(vb.capacity(), 2);
+    assert!(vb.is_empty());
+
+    vb.push(Vertex { field: [1, 2] });
+    vb.extend_from_slice(&[Vertex { field: [3, 4] }, Vertex { field: [5, 6] }]);
+    assert_eq!(vb.len(), 3);
+    assert!(vb.capacity() >= 3);
+
+    vb.reserve(100);
+    assert!(vb.capacity() >= 103);
+    assert_eq!(vb.slice(1 ..).unwrap().len(), 2);
+    assert!(vb.slice(2 .. 4).is_none());
+
+    let data = match vb.as_slice().read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, [Vertex { field: [1, 2] }, Vertex { field: [3, 4] },
+                      Vertex { field: [5, 6] }]);
+
+    vb.truncate(1);
+    vb.shrink_to_fit();
+    assert_eq!(vb.capacity(), 1);
+    assert_eq!(vb.as_slice().read().unwrap(), [Vertex { field: [1, 2] }]);
+
+    vb.clear();
+    assert!(vb.is_empty());
+
+    display.assert_no_error(None);
+}